@@ -12,7 +12,9 @@ use tscore_span::source_map::{respan, Spanned};
 use tscore_span::symbol::{kw, sym, Ident, Symbol};
 use tscore_span::{Span, DUMMY_SP};
 
-use super::{Block, Decorator, Expr, ExprWithTypeArgs, Fn, Generics, JSDoc, Lit, Pat, Ty};
+use super::{
+  Block, Decorator, Expr, ExprWithTypeArgs, Fn, Generics, IndexSig, JSDoc, Lit, Pat, Ty,
+};
 
 use std::cmp::Ordering;
 use std::convert::TryFrom;
@@ -28,12 +30,11 @@ pub enum PropName {
   PrivateIdent(Ident),
 }
 
+/// A property declaration, the name and modifiers are those of its [ClassElement].
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct Prop {
-  pub name: Ident,
-  pub ty: Option<Ty>,
-  pub init: Option<Expr>,
-  pub decorators: Vec<Decorator>,
+  pub ty: Option<P<Ty>>,
+  pub init: Option<P<Expr>>,
   pub span: Span,
 }
 
@@ -45,12 +46,12 @@ pub enum ClassElementKind {
   Constructor(P<Fn>),
   Getter(P<Fn>),
   Setter(P<Fn>),
-  IndexSig(P<Ty>),
+  IndexSig(P<IndexSig>),
   Method(P<Fn>),
   Property(P<Prop>),
 }
 
-#[derive(Clone, Encodable, Decodable, Debug)]
+#[derive(Clone, Copy, PartialEq, Encodable, Decodable, Debug)]
 pub enum Visibility {
   Public,
   Protected,
@@ -60,13 +61,21 @@ pub enum Visibility {
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct ClassElement {
   pub kind: ClassElementKind,
-  pub vis: Visibility,
-  pub name: PropName,
+  /// `None` without an accessibility modifier, which means public.
+  pub vis: Option<Visibility>,
+  /// `None` for a `;`, a constructor and an index signature.
+  pub name: Option<PropName>,
   pub is_static: bool,
   pub is_abstract: bool,
+  pub is_override: bool,
+  /// `declare a: T`
+  pub is_declare: bool,
+  /// `a!: T`
   pub definite: bool,
+  /// `a?: T` or `a?() {}`
   pub optional: bool,
   pub readonly: bool,
+  pub decorators: Vec<Decorator>,
   pub span: Span,
 }
 
@@ -83,7 +92,7 @@ pub struct Class {
   pub generics: Generics,
   pub heritages: Vec<Heritage>,
   pub span: Span,
-  pub elementss: Vec<ClassElement>,
+  pub elements: Vec<ClassElement>,
   pub is_abstract: bool,
   pub decorators: Vec<Decorator>,
 }
@@ -3,10 +3,12 @@ use tscore_span::Span;
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct NameBinding {
-  name: Ident,
+  pub name: Ident,
   /// with preceding `as` keyword
-  alias: Option<Ident>,
-  span: Span,
+  pub alias: Option<Ident>,
+  /// `type a` in `import { type a } from 'module-name'`
+  pub type_only: bool,
+  pub span: Span,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
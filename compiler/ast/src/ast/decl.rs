@@ -13,7 +13,8 @@ use tscore_span::symbol::{kw, sym, Ident, Symbol};
 use tscore_span::{Span, DUMMY_SP};
 
 use super::{
-  Class, Expr, Fn, FnSig, Generics, Heritage, JSDoc, NameBinding, Pat, PropName, StrLit, Ty,
+  Block, Class, EntityName, Expr, Fn, FnParam, FnSig, Generics, Heritage, JSDoc, NameBinding, Pat,
+  PropName, StrLit, Ty,
 };
 
 use std::cmp::Ordering;
@@ -55,13 +56,44 @@ pub struct Decorator {
   pub expr: P<Expr>,
 }
 
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct PropSig {
+  pub name: PropName,
+  pub optional: bool,
+  pub readonly: bool,
+  pub ty: Option<P<Ty>>,
+  pub span: Span,
+}
+
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct MethodSig {
+  pub name: PropName,
+  pub optional: bool,
+  pub sig: FnSig,
+  pub span: Span,
+}
+
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct IndexSig {
+  pub readonly: bool,
+  pub params: Vec<FnParam>,
+  pub ty: Option<P<Ty>>,
+  pub span: Span,
+}
+
+/// A member of an interface or an object type literal.
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub enum InterfaceElement {
-  CallSig(P<Ty>),
-  ConstructorSig(P<Ty>),
-  PropSig(P<PropName>, P<Ty>),
-  MethodSig(P<PropName>, P<Ty>),
-  IndexSig(P<Ty>),
+  /// `(a: A): B`
+  CallSig(P<FnSig>),
+  /// `new (a: A): B`
+  ConstructorSig(P<FnSig>),
+  /// `readonly a?: A`
+  PropSig(P<PropSig>),
+  /// `a?(b: B): C`
+  MethodSig(P<MethodSig>),
+  /// `readonly [key: string]: A`
+  IndexSig(P<IndexSig>),
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
@@ -81,6 +113,54 @@ pub struct TypeAlias {
   pub span: Span,
 }
 
+/// `enum A { a, b = 1 }` or `const enum A { .. }`.
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct Enum {
+  pub name: Ident,
+  pub is_const: bool,
+  pub members: Vec<EnumMember>,
+  pub span: Span,
+}
+
+/// `a = 1`, `'b'` or `[c]` in an enum, the checker reports names that are neither
+/// identifiers nor strings.
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct EnumMember {
+  pub name: PropName,
+  pub init: Option<P<Expr>>,
+  pub span: Span,
+}
+
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub enum ModuleName {
+  /// `namespace A {}` or `module A {}`
+  Ident(Ident),
+  /// `declare module 'a' {}`
+  Str(StrLit),
+  /// `declare global {}`
+  Global(Ident),
+}
+
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub enum ModuleBody {
+  Block(P<Block>),
+  /// The rest of a dotted name, `B.C { .. }` in `namespace A.B.C { .. }`.
+  Decl(P<ModuleDecl>),
+}
+
+/// A namespace, an ambient module or a global augmentation.
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct ModuleDecl {
+  pub name: ModuleName,
+  /// `true` for `namespace A {}`, `false` for `module A {}`.
+  pub is_namespace: bool,
+  /// `None` for the shorthand `declare module 'a';`.
+  pub body: Option<ModuleBody>,
+  pub span: Span,
+}
+
+/// An import declaration. Without any binding the module is only run for its side
+/// effects: `import 'module-name'`
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct Import {
   /// Default binding.
@@ -98,17 +178,47 @@ pub struct Import {
   /// Namespace import binding.
   ///
   /// `import * as name from 'module-name'`
-  pub namespcae: Option<Ident>,
+  pub namespace: Option<Ident>,
   /// `import { export1, export2 as alias2 } from 'module-name'`
   pub names: Vec<NameBinding>,
   pub from: StrLit,
   pub type_only: bool,
+  /// `import json from './a.json' with { type: 'json' }`
+  pub attributes: Vec<ImportAttribute>,
+  pub span: Span,
+}
+
+/// `type: 'json'` in `with { type: 'json' }`. The older `assert { .. }` is parsed the same.
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct ImportAttribute {
+  /// An identifier or a string.
+  pub name: PropName,
+  pub value: P<Expr>,
+  pub span: Span,
+}
+
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub enum ModuleRef {
+  /// `require('module-name')`
+  Require(StrLit),
+  /// `A.B`
+  Entity(P<EntityName>),
+}
+
+/// `import a = require('module-name')` or the alias `import a = A.B`.
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct ImportEquals {
+  pub name: Ident,
+  pub type_only: bool,
+  pub module_ref: ModuleRef,
   pub span: Span,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct Export {
   pub type_only: bool,
+  /// `export * from 'module-name'`
+  pub all: bool,
   ///
   /// `export * as name from 'module-name'`
   ///
@@ -121,7 +231,17 @@ pub struct Export {
   /// ```js
   /// export { ... } from 'module-name'
   /// ```
-  pub from: StrLit,
+  pub from: Option<StrLit>,
+  pub attributes: Vec<ImportAttribute>,
+  pub span: Span,
+}
+
+/// `export default a` or `export = a`.
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct ExportAssign {
+  pub expr: P<Expr>,
+  /// `true` for `export =`.
+  pub is_export_equals: bool,
   pub span: Span,
 }
 
@@ -146,13 +266,27 @@ pub enum DeclKind {
   ///
   /// See [Import] for more detail.
   Import(P<Import>),
+  ImportEquals(P<ImportEquals>),
   Export(P<Export>),
+  ExportAssign(P<ExportAssign>),
+  /// `export as namespace A`, which makes a module a global too.
+  ExportAsNamespace(Ident),
   Type(P<TypeAlias>),
+  Enum(P<Enum>),
+  /// `namespace A {}`, `declare module 'a' {}` or `declare global {}`.
+  Module(P<ModuleDecl>),
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct Decl {
   pub kind: DeclKind,
+  /// Whether the declaration is ambient: it follows `declare`, is nested in an ambient
+  /// declaration or is in a `.d.ts` file.
+  pub ambient: bool,
+  /// `export const a = 1`
+  pub is_export: bool,
+  /// `export default function () {}`
+  pub is_default: bool,
   pub span: Span,
 }
 
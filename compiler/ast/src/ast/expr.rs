@@ -1,8 +1,10 @@
-use super::{Class, Fn, Generics, Lit, Spanned, TemplateLit, Ty};
+use super::{ArrayLit, Class, Fn, Lit, ObjectLit, Pat, Spanned, TemplateLit, Ty};
 use crate::ptr::P;
-use tscore_span::{symbol::Ident, Span};
+use crate::token::{self, Token};
+use tscore_span::symbol::{kw, Ident, Symbol};
+use tscore_span::Span;
 
-#[derive(Clone, Encodable, Decodable, Debug)]
+#[derive(Clone, Copy, PartialEq, Encodable, Decodable, Debug)]
 pub enum BinOpKind {
   /// The `+` operator (addition)
   Add,
@@ -54,11 +56,103 @@ pub enum BinOpKind {
   In,
   /// The `instanceof` operator
   InstanceOf,
+  /// The `,` operator (sequence)
+  Comma,
+}
+
+impl BinOpKind {
+  /// The binary operator of a token. `,` is left out, the parser handles it separately.
+  pub fn from_token(t: &Token) -> Option<BinOpKind> {
+    use BinOpKind::*;
+    Some(match t.kind {
+      token::BinOp(op) => match op {
+        token::Plus => Add,
+        token::Minus => Sub,
+        token::Star => Mul,
+        token::StarStar => Exp,
+        token::Slash => Div,
+        token::Percent => Mod,
+        token::Shl => Shl,
+        token::Shr => Shr,
+        token::Ushr => Ushr,
+        token::And => BitAnd,
+        token::Or => BitOr,
+        token::Caret => BitXor,
+        token::AndAnd => And,
+        token::OrOr => Or,
+        token::QuestionQuestion => Qq,
+      },
+      token::EqEq => EqEq,
+      token::EqEqEq => EqEqEq,
+      token::NotEq => NotEq,
+      token::NotEqEq => NotEqEq,
+      token::Lt => Lt,
+      token::Le => Le,
+      token::Gt => Gt,
+      token::Ge => Ge,
+      token::Ident(name) if name == kw::In => In,
+      token::Ident(name) if name == kw::InstanceOf => InstanceOf,
+      _ => return None,
+    })
+  }
+
+  /// How tightly the operator binds, higher binds tighter. The levels follow
+  /// TypeScript's `OperatorPrecedence`, where `??` ranks with the conditional operator.
+  pub fn precedence(self) -> u8 {
+    use BinOpKind::*;
+    match self {
+      Comma => 0,
+      Qq => 4,
+      Or => 5,
+      And => 6,
+      BitOr => 7,
+      BitXor => 8,
+      BitAnd => 9,
+      EqEq | EqEqEq | NotEq | NotEqEq => 10,
+      Lt | Le | Gt | Ge | In | InstanceOf => 11,
+      Shl | Shr | Ushr => 12,
+      Add | Sub => 13,
+      Mul | Div | Mod => 14,
+      Exp => 15,
+    }
+  }
+
+  pub fn as_str(self) -> &'static str {
+    use BinOpKind::*;
+    match self {
+      Add => "+",
+      Sub => "-",
+      Mul => "*",
+      Div => "/",
+      Mod => "%",
+      And => "&&",
+      Or => "||",
+      Qq => "??",
+      BitXor => "^",
+      BitAnd => "&",
+      BitOr => "|",
+      Shl => "<<",
+      Shr => ">>",
+      Ushr => ">>>",
+      Exp => "**",
+      EqEq => "==",
+      EqEqEq => "===",
+      Lt => "<",
+      Le => "<=",
+      NotEq => "!=",
+      NotEqEq => "!==",
+      Ge => ">=",
+      Gt => ">",
+      In => "in",
+      InstanceOf => "instanceof",
+      Comma => ",",
+    }
+  }
 }
 
 pub type BinOp = Spanned<BinOpKind>;
 
-#[derive(Clone, Encodable, Decodable, Debug)]
+#[derive(Clone, Copy, PartialEq, Encodable, Decodable, Debug)]
 pub enum AssignOpKind {
   /// `=`
   Assign,
@@ -82,7 +176,7 @@ pub enum AssignOpKind {
   BitOrAssign,
   /// `^=`
   BitXorAssign,
-  /// `&==`
+  /// `&=`
   BitAndAssign,
   /// `**=`
   ExpAssign,
@@ -94,12 +188,41 @@ pub enum AssignOpKind {
   QqAssign,
 }
 
+impl AssignOpKind {
+  /// The assignment operator of a token.
+  pub fn from_token(t: &Token) -> Option<AssignOpKind> {
+    use AssignOpKind::*;
+    Some(match t.kind {
+      token::Eq => Assign,
+      token::BinOpEq(op) => match op {
+        token::Plus => AddAssign,
+        token::Minus => SubAssign,
+        token::Star => MulAssign,
+        token::StarStar => ExpAssign,
+        token::Slash => DivAssign,
+        token::Percent => ModAssign,
+        token::Shl => ShlAssign,
+        token::Shr => ShrAssign,
+        token::Ushr => UshrAssign,
+        token::And => BitAndAssign,
+        token::Or => BitOrAssign,
+        token::Caret => BitXorAssign,
+        token::AndAnd => AndAssign,
+        token::OrOr => OrAssign,
+        token::QuestionQuestion => QqAssign,
+      },
+      _ => return None,
+    })
+  }
+}
+
 pub type AssignOp = Spanned<AssignOpKind>;
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct AssignExpr {
   pub span: Span,
-  pub left: P<Expr>,
+  /// An identifier, a destructuring pattern as in `[a, b] = c`, or another target like `a.b`.
+  pub left: P<Pat>,
   pub operator: AssignOp,
   pub right: P<Expr>,
 }
@@ -125,7 +248,7 @@ pub struct AssignExpr {
 //   // `as`
 // }
 
-#[derive(Clone, Encodable, Decodable, Debug)]
+#[derive(Clone, Copy, PartialEq, Encodable, Decodable, Debug)]
 pub enum UnaryOpKind {
   Del,
   Void,
@@ -134,11 +257,27 @@ pub enum UnaryOpKind {
   Plus,
   Minus,
   Tilde,
+  Not,
+}
+
+impl UnaryOpKind {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      UnaryOpKind::Del => "delete",
+      UnaryOpKind::Void => "void",
+      UnaryOpKind::Typeof => "typeof",
+      UnaryOpKind::Await => "await",
+      UnaryOpKind::Plus => "+",
+      UnaryOpKind::Minus => "-",
+      UnaryOpKind::Tilde => "~",
+      UnaryOpKind::Not => "!",
+    }
+  }
 }
 
 pub type UnaryOp = Spanned<UnaryOpKind>;
 
-#[derive(Clone, Encodable, Decodable, Debug)]
+#[derive(Clone, Copy, PartialEq, Encodable, Decodable, Debug)]
 pub enum UpdateOpKind {
   PlusPlus,
   MinusMinus,
@@ -175,7 +314,8 @@ pub struct CallExpr {
   pub span: Span,
   pub expr: P<Expr>,
   pub args: Vec<P<Expr>>,
-  pub type_params: Generics,
+  /// `f<T>()`, empty without `<..>`.
+  pub type_args: Vec<P<Ty>>,
   pub optional: bool,
 }
 
@@ -183,39 +323,52 @@ pub struct CallExpr {
 pub struct NewExpr {
   pub span: Span,
   pub expr: P<Expr>,
-  pub args: Vec<P<Expr>>,
-  pub type_params: Generics,
+  /// `None` for `new A` without parentheses.
+  pub args: Option<Vec<P<Expr>>>,
+  /// `new A<T>()`, empty without `<..>`.
+  pub type_args: Vec<P<Ty>>,
 }
 
+/// A JSX element `<a>..</a>`, a self-closing element `<a />` or a fragment `<>..</>`.
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct JsxEl {
   pub span: Span,
   /// optional for fragment element
-  pub openingEl: Option<JsxOpeningEl>,
+  pub opening_el: Option<JsxOpeningEl>,
   /// optional for fragment or self closing element
-  pub closingEl: Option<JsxClosingEl>,
+  pub closing_el: Option<JsxClosingEl>,
   pub children: Vec<P<JsxChild>>,
 }
 
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub enum JsxPropKind {
+  /// `a`, `a="b"`, `a={b}` or `a=<b />`, the value of `a={b}` is `b`.
+  Attr(Ident, Option<P<Expr>>),
+  /// `{...a}`
+  Spread(P<Expr>),
+}
+
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct JsxProp {
   pub span: Span,
-  pub name: Ident,
-  pub init: P<Expr>,
+  pub kind: JsxPropKind,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct JsxOpeningEl {
   pub span: Span,
   pub tag: JsxTagNameKind,
-  pub type_args: Generics,
+  /// `<a<T>>`
+  pub type_args: Vec<P<Ty>>,
   pub props: Vec<P<JsxProp>>,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub enum JsxTagNameKind {
+  /// `a`, also `a-b` and the namespaced `a:b`.
   Ident(Ident),
-  This,
+  This(Span),
+  /// `a.b.c`
   PropAccess(P<PropAccessExpr>),
 }
 
@@ -225,16 +378,18 @@ pub struct JsxClosingEl {
   pub tag: JsxTagNameKind,
 }
 
+/// `{a}` or `{...a}` between tags, `{}` has no expression.
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct JsxExpr {
   pub span: Span,
   pub spread: bool,
-  pub epxr: P<Expr>,
+  pub expr: Option<P<Expr>>,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub enum JsxChildKind {
-  Text,
+  /// The text with HTML entities decoded.
+  Text(Symbol, /* only whitespace with a line break */ bool),
   Expr(P<JsxExpr>),
   El(P<JsxEl>),
 }
@@ -253,22 +408,35 @@ pub enum ExprKind {
   Update(UpdateOp, P<Expr>, /* prefix */ bool),
   Binary(BinOp, P<Expr>, P<Expr>),
   Lit(P<Lit>),
+  /// `[a, , ...b]`
+  Array(P<ArrayLit>),
+  /// `{ a, b: 1, ...c }`
+  Object(P<ObjectLit>),
+  /// An identifier reference. A missing expression is parsed as an empty identifier.
+  Ident(Ident),
+  /// A private name, only valid as the left operand of `in`: `#a in b`.
+  PrivateIdent(Ident),
   This,
   Super,
   Import,
-  Yield(Option<P<Expr>>, /* TBD */ bool),
+  Yield(Option<P<Expr>>, /* delegate, `yield*` */ bool),
   // TBD
   // SyntheticExpression
   Cond(P<CondExpr>),
   Fn(P<Fn>),
   ArrowFn(P<Fn>),
+  Class(P<Class>),
   Paren(P<Expr>),
   Spread(P<Expr>),
   PropAccess(P<PropAccessExpr>),
   ElementAccess(P<ElementAccessExpr>),
   Call(P<CallExpr>),
+  /// An instantiation expression, `f<T>` not followed by arguments.
+  WithTypeArgs(P<ExprWithTypeArgs>),
   New(P<NewExpr>),
-  TaggedTemplate(P<Expr>, P<TemplateLit>),
+  /// A tag with its type arguments, empty without `<..>`, and a template: ``tag<T>`a${b}` ``.
+  TaggedTemplate(P<Expr>, Vec<P<Ty>>, P<TemplateLit>),
+  /// `new.target` or `import.meta`, the identifier is the property name.
   Meta(Ident),
   JsxEl(P<JsxEl>),
   Assign(P<AssignExpr>),
@@ -286,4 +454,8 @@ pub struct Expr {
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
-pub struct ExprWithTypeArgs {}
+pub struct ExprWithTypeArgs {
+  pub expr: P<Expr>,
+  pub type_args: Vec<P<Ty>>,
+  pub span: Span,
+}
@@ -12,13 +12,24 @@ use tscore_span::source_map::{respan, Spanned};
 use tscore_span::symbol::{kw, sym, Ident, Symbol};
 use tscore_span::{Span, DUMMY_SP};
 
-use super::{Block, Decorator, Expr, FnSig, Generics, JSDoc, Lit, Pat, Ty};
+use super::{Block, Decorator, Expr, FnSig, Generics, JSDoc, Lit, Pat, Ty, Visibility};
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct FnParam {
-  pub ty: P<Ty>,
   pub name: P<Pat>,
+  /// `a?: T`
+  pub optional: bool,
+  /// `...a`
+  pub rest: bool,
+  pub ty: Option<P<Ty>>,
+  pub init: Option<P<Expr>>,
+  /// The accessibility of a parameter property, `constructor(private a) {}`.
+  pub vis: Option<Visibility>,
+  /// `constructor(readonly a) {}`
+  pub readonly: bool,
+  pub is_override: bool,
   pub decorators: Vec<Decorator>,
+  pub span: Span,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
@@ -39,8 +50,12 @@ pub struct Fn {
   pub name: Option<Ident>,
   /// Function Signature
   pub sig: FnSig,
-  /// TODO: what if a ts function declaration?
-  pub body: P<FnBody>,
+  /// `None` for an overload signature or an ambient declaration.
+  ///
+  /// ```ts
+  /// function fn(a: string): void;
+  /// ```
+  pub body: Option<P<FnBody>>,
   /// Indicate whether a function is `async` function.
   ///
   /// ```js
@@ -33,8 +33,6 @@ pub enum LitKind {
   RegExp,
   /// `null`
   Null,
-  Array(P<ArrayLit>),
-  Object(P<ObjectLit>),
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
@@ -44,7 +42,7 @@ pub struct Lit {
   pub span: Span,
 }
 
-/// Same as `Lit` but restricted t ostring literals.
+/// Same as `Lit` but restricted to string literals.
 #[derive(Clone, Copy, Encodable, Decodable, Debug)]
 pub struct StrLit {
   pub span: Span,
@@ -80,6 +78,8 @@ pub struct ObjectLit {
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct ObjectLitEl {
   pub kind: ObjectLitElKind,
+  /// The name of a method or an accessor, that of a property is in its [PropAssign].
+  pub name: Option<PropName>,
   pub span: Span,
 }
 
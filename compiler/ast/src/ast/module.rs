@@ -21,4 +21,5 @@ use std::fmt;
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct Module {
   pub items: Vec<Stmt>,
+  pub span: Span,
 }
@@ -1,15 +1,42 @@
-use super::{ArrayLit, ObjectLit};
+use super::{Expr, PropName};
 use crate::ptr::P;
 use tscore_span::symbol::Ident;
+use tscore_span::Span;
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub enum PatKind {
   Ident(Ident),
-  Array(P<ArrayLit>),
-  Object(P<ObjectLit>),
+  /// `[a, , ...b]`
+  Array(P<BindingPattern>),
+  /// `{ a, b: c, ...d }`
+  Object(P<BindingPattern>),
+  /// A target that binds nothing, e.g. `a.b` in `[a.b] = c`. Only in assignments
+  /// and `for .. in`/`for .. of` loops, declarations bind identifiers.
+  Expr(P<Expr>),
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct Pat {
-  kind: PatKind,
+  pub kind: PatKind,
+  pub span: Span,
+}
+
+/// The elements of a destructuring pattern, see [PatKind].
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct BindingPattern {
+  pub elements: Vec<BindingElement>,
+  pub span: Span,
+}
+
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct BindingElement {
+  /// `a` in `{ a: b }`, `None` for a shorthand `{ b }` and in array patterns.
+  pub prop_name: Option<PropName>,
+  /// `None` for a hole, `[, a]`.
+  pub name: Option<P<Pat>>,
+  /// `{ a = 1 }`
+  pub init: Option<P<Expr>>,
+  /// `...a`
+  pub rest: bool,
+  pub span: Span,
 }
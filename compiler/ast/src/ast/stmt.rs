@@ -2,7 +2,7 @@ use crate::ptr::P;
 use crate::token::{self, CommentKind, DelimToken, Token};
 use crate::tokenstream::{DelimSpan, LazyTokenStream, TokenStream, TokenTree};
 
-use super::{Decl, DeclList, Expr, Pat, StrLit, VarDeclList};
+use super::{Decl, DeclList, Expr, Pat, StrLit, VarDecl, VarDeclList};
 
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
 use rustc_data_structures::stack::ensure_sufficient_stack;
@@ -29,11 +29,13 @@ pub struct Block {
 pub enum ForVal {
   Expr(P<Expr>),
   Decl(P<VarDeclList>),
+  /// The target of `for (a in b)` or `for ([a, b] of c)`.
+  Pat(P<Pat>),
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct ForCond {
-  pub init: Option<P<Expr>>,
+  pub init: Option<ForVal>,
   pub test: Option<P<Expr>>,
   pub update: Option<P<Expr>>,
 }
@@ -42,19 +44,22 @@ pub struct ForCond {
 pub enum IterCondKind {
   Do(P<Expr>),
   For(P<ForCond>),
-  ForInOf(P<ForVal>, P<Expr>, /* awaitness */ bool),
+  /// `for (x in o)`
+  ForIn(P<ForVal>, P<Expr>),
+  /// `for (x of xs)`, or `for await (x of xs)`
+  ForOf(P<ForVal>, P<Expr>, /* awaitness */ bool),
   While(P<Expr>),
 }
 
+/// The head of a loop, i.e. everything but the body.
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct IterCond {
   pub kind: IterCondKind,
-  pub stmts: Vec<Stmt>,
   pub span: Span,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
-pub struct Label(Ident);
+pub struct Label(pub Ident);
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub enum SwitchBranch {
@@ -64,8 +69,10 @@ pub enum SwitchBranch {
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct CatchBlock {
-  decl: Option</* VarDecl */ Decl>,
-  block: Block,
+  /// Optional since ES2019, `try { .. } catch { .. }`.
+  pub decl: Option<VarDecl>,
+  pub block: Block,
+  pub span: Span,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
@@ -1,14 +1,15 @@
-use tscore_span::symbol::Ident;
+use tscore_span::symbol::{Ident, Symbol};
 use tscore_span::Span;
 
-use super::{ExprWithTypeArgs, FnParam, Lit, PropName};
+use super::{ExprWithTypeArgs, FnParam, InterfaceElement, Lit, PropName, StrLit};
 use crate::ptr::P;
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct GenericParam {
   pub name: Ident,
   pub constraint: Option<P<Ty>>,
-  pub defualt: Option<P<Ty>>,
+  pub default: Option<P<Ty>>,
+  pub span: Span,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
@@ -56,10 +57,10 @@ pub struct RefTy {
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct CondTy(
-  /* check */ P<Ty>,
-  /* extends */ P<Ty>,
-  /* true */ P<Ty>,
-  /* false */ P<Ty>,
+  /* check */ pub P<Ty>,
+  /* extends */ pub P<Ty>,
+  /* true */ pub P<Ty>,
+  /* false */ pub P<Ty>,
 );
 
 #[derive(Clone, Encodable, Decodable, Debug)]
@@ -77,24 +78,26 @@ pub struct OpTy {
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
-pub struct IndexTy(/* object */ P<Ty>, /* index */ P<Ty>);
+pub struct IndexTy(/* object */ pub P<Ty>, /* index */ pub P<Ty>);
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct TemplateTySpan {
   pub span: Span,
   pub ty: P<Ty>,
-  pub lit: Option<P<Ty>>,
+  pub lit: Option<StrLit>,
 }
 
+/// Same as [super::TemplateLit] but with types in the substitutions.
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct TemplateTy {
   pub span: Span,
-  pub head: Option<Span>,
+  pub head: Option<StrLit>,
   pub spans: Vec<P<TemplateTySpan>>,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub enum ModFlag {
+  /// The modifier without a sign, `readonly` or `?`.
   Raw,
   Plus,
   Minus,
@@ -104,12 +107,25 @@ pub enum ModFlag {
 pub struct MapTy {
   pub readonly_flag: Option<ModFlag>,
   pub optional_flag: Option<ModFlag>,
-  pub type_params: Generics,
-  pub optional: bool,
-  pub ty: Ty,
+  /// `P in K`, with `K` as the constraint.
+  pub type_param: GenericParam,
+  /// `T` in `{ [P in K as T]: U }`
+  pub name_type: Option<P<Ty>>,
+  pub ty: Option<P<Ty>>,
   pub span: Span,
-  /// TBD
-  pub name_type: Option<Ty>,
+}
+
+/// ```ts
+/// function isString(a: unknown): a is string
+/// function assertIsString(a: unknown): asserts a is string
+/// function assert(a: unknown): asserts a
+/// ```
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub struct PredicateTy {
+  pub asserts: bool,
+  /// The parameter name, or `this`.
+  pub param: Ident,
+  pub ty: Option<P<Ty>>,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
@@ -143,7 +159,7 @@ pub enum TyKind {
   /// let a: string
   /// //     ^^^^^^ StringKeyword
   /// ```
-  Keyword,
+  Keyword(Symbol),
   /// ```ts
   /// let a: string | null
   /// //              ^^^^
@@ -153,14 +169,17 @@ pub enum TyKind {
   /// let a: { name: string }
   /// //     ^^^^^^^^^^^^^^^^
   /// ```
-  TyLit(Vec<P<Ty>>),
+  TyLit(Vec<InterfaceElement>),
   /// ```ts
   /// let a: A[]
   /// ```
   Arr(P<Ty>),
   /// ```ts
   /// let a: [number, string]
+  /// let a: [name: string, age?: number]
   /// ```
+  ///
+  /// Named members carry their name and `?` in [Ty::name] and [Ty::optional].
   Tuple(Vec<P<Ty>>),
   /// ```ts
   /// let a: [number, ...string, number]
@@ -217,12 +236,19 @@ pub enum TyKind {
   /// type T3 = `${'top' | 'bottom'}-${'left' | 'right'}`;
   /// ```
   Template(P<TemplateTy>),
+  /// ```ts
+  /// function isString(a: unknown): a is string
+  /// //                             ^^^^^^^^^^^
+  /// ```
+  Predicate(P<PredicateTy>),
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
 pub struct Ty {
   pub kind: TyKind,
+  /// The name of a tuple member, `a` in `[a?: string]`.
   pub name: Option<PropName>,
+  /// Whether a named tuple member is optional.
   pub optional: bool,
   pub span: Span,
 }
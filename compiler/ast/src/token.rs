@@ -1,6 +1,10 @@
+#[cfg(test)]
+mod tests;
+
 pub use BinOpToken::*;
 pub use DelimToken::*;
 pub use LitKind::*;
+pub use TemplateKind::*;
 pub use TokenKind::*;
 pub use TriviaKind::*;
 
@@ -15,8 +19,8 @@ use std::borrow::Cow;
 use std::{fmt, mem};
 use tscore_span::source_map::SourceMap;
 use tscore_span::symbol::{kw, sym};
-use tscore_span::symbol::{Ident, Symbol};
-use tscore_span::{self, FileName, RealFileName, Span, DUMMY_SP};
+use tscore_span::symbol::{Ident, KeywordKind, Symbol};
+use tscore_span::{self, BytePos, FileName, RealFileName, Span, DUMMY_SP};
 
 #[derive(Clone, PartialEq, Encodable, Decodable, Debug, HashStable_Generic)]
 pub struct Token {
@@ -74,6 +78,13 @@ pub enum TokenKind {
 
   /// Identifier token.
   Ident(Symbol),
+  /// A private name of a class member, e.g. `#a`, the symbol includes the `#`.
+  PrivateIdent(Symbol),
+
+  /// Text between JSX tags, the symbol is the raw text.
+  JsxText(Symbol),
+  /// JSX text made of whitespace only, including at least one line break.
+  JsxTextAllWhiteSpaces(Symbol),
 }
 
 #[derive(Clone, Copy, PartialEq, Encodable, Decodable, Debug, HashStable_Generic)]
@@ -83,7 +94,7 @@ pub enum TriviaKind {
   NewLine,
   Whitespace,
   Shebang,
-  ConflictMakrer,
+  ConflictMarker,
 }
 
 #[derive(Clone, Copy, PartialEq, Encodable, Decodable, Debug, HashStable_Generic)]
@@ -136,14 +147,30 @@ pub enum QuoteKind {
   Double,
 }
 
+/// Which part of a template literal a `LitKind::Template` token is.
+#[derive(Clone, Copy, PartialEq, Encodable, Decodable, Debug, HashStable_Generic)]
+pub enum TemplateKind {
+  /// `` `foo` ``
+  NoSubstitution,
+  /// `` `foo${ ``
+  Head,
+  /// `}foo${`
+  Middle,
+  /// `` }foo` ``
+  Tail,
+}
+
 #[derive(Clone, Copy, PartialEq, Encodable, Decodable, Debug, HashStable_Generic)]
 pub enum LitKind {
   Bool, // AST only, must never appear in a `Token`
+  Null, // AST only, must never appear in a `Token`
   Num,
   Str(QuoteKind),
   BigInt,
+  /// The symbol is the pattern between the slashes, the suffix holds the flags.
   RegExp,
-  Template,
+  /// The symbol is the raw text between the delimiters.
+  Template(TemplateKind),
 }
 
 /// A literal token.
@@ -154,6 +181,77 @@ pub struct Lit {
   pub suffix: Option<Symbol>,
 }
 
+impl BinOpToken {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Plus => "+",
+      Minus => "-",
+      Star => "*",
+      StarStar => "**",
+      Slash => "/",
+      Percent => "%",
+      Shl => "<<",
+      Shr => ">>",
+      Ushr => ">>>",
+      And => "&",
+      Or => "|",
+      Caret => "^",
+      AndAnd => "&&",
+      OrOr => "||",
+      QuestionQuestion => "??",
+    }
+  }
+}
+
+/// Prints the token as it is written in source, as used in messages like `';' expected.`.
+impl fmt::Display for TokenKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let s = match self {
+      Unknown => "unknown token",
+      Eof => "end of file",
+      Trivia(_) => "trivia",
+      OpenDelim(Paren) => "(",
+      OpenDelim(Bracket) => "[",
+      OpenDelim(Brace) => "{",
+      OpenDelim(NoDelim) | CloseDelim(NoDelim) => "",
+      CloseDelim(Paren) => ")",
+      CloseDelim(Bracket) => "]",
+      CloseDelim(Brace) => "}",
+      Dot => ".",
+      DotDotDot => "...",
+      Semi => ";",
+      Comma => ",",
+      QuestionDot => "?.",
+      Lt => "<",
+      LtSlash => "</",
+      Le => "<=",
+      Gt => ">",
+      Ge => ">=",
+      Eq => "=",
+      EqEq => "==",
+      EqEqEq => "===",
+      NotEq => "!=",
+      NotEqEq => "!==",
+      Arrow => "=>",
+      BinOp(op) => op.as_str(),
+      BinOpEq(op) => return write!(f, "{}=", op.as_str()),
+      Not => "!",
+      Tilde => "~",
+      PlusPlus => "++",
+      MinusMinus => "--",
+      Colon => ":",
+      At => "@",
+      Question => "?",
+      Backtick => "`",
+      Literal(lit) => return write!(f, "{}", lit),
+      Ident(name) | PrivateIdent(name) | JsxText(name) | JsxTextAllWhiteSpaces(name) => {
+        return write!(f, "{}", name);
+      }
+    };
+    f.write_str(s)
+  }
+}
+
 impl fmt::Display for Lit {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let Lit { kind, symbol, suffix } = *self;
@@ -165,6 +263,13 @@ impl fmt::Display for Lit {
           write!(f, "'{}'", symbol)?
         }
       }
+      RegExp => write!(f, "/{}/", symbol)?,
+      Template(template_kind) => match template_kind {
+        NoSubstitution => write!(f, "`{}`", symbol)?,
+        Head => write!(f, "`{}${{", symbol)?,
+        Middle => write!(f, "}}{}${{", symbol)?,
+        Tail => write!(f, "}}{}`", symbol)?,
+      },
       _ => write!(f, "{}", symbol)?,
     }
 
@@ -187,16 +292,17 @@ impl LitKind {
   pub fn descr(self) -> &'static str {
     match self {
       Bool => panic!("literal token contains `Lit::Bool`"),
+      Null => panic!("literal token contains `Lit::Null`"),
       Num => "number",
       Str(_) => "string",
       BigInt => "BigInt",
       RegExp => "RegExp",
-      Template => "template string",
+      Template(_) => "template string",
     }
   }
 
   crate fn may_have_suffix(self) -> bool {
-    matches!(self, BigInt)
+    matches!(self, BigInt | RegExp)
   }
 }
 
@@ -206,6 +312,43 @@ impl Lit {
   }
 }
 
+impl TokenKind {
+  /// Splits a compound punctuator into its first character and the rest, the inverse
+  /// of [`Token::glue`]. For example `>>=` is split into `>` and `>=`, which the
+  /// parser needs to close nested type arguments like `Array<Array<T>>`.
+  /// The first piece is always a single character.
+  pub fn break_two_token_op(&self) -> Option<(TokenKind, TokenKind)> {
+    Some(match *self {
+      Le => (Lt, Eq),
+      LtSlash => (Lt, BinOp(Slash)),
+      BinOp(Shl) => (Lt, Lt),
+      BinOpEq(Shl) => (Lt, Le),
+      Ge => (Gt, Eq),
+      BinOp(Shr) => (Gt, Gt),
+      BinOpEq(Shr) => (Gt, Ge),
+      BinOp(Ushr) => (Gt, BinOp(Shr)),
+      BinOpEq(Ushr) => (Gt, BinOpEq(Shr)),
+      EqEq => (Eq, Eq),
+      EqEqEq => (Eq, EqEq),
+      Arrow => (Eq, Gt),
+      NotEq => (Not, Eq),
+      NotEqEq => (Not, EqEq),
+      PlusPlus => (BinOp(Plus), BinOp(Plus)),
+      MinusMinus => (BinOp(Minus), BinOp(Minus)),
+      BinOp(StarStar) => (BinOp(Star), BinOp(Star)),
+      BinOpEq(StarStar) => (BinOp(Star), BinOpEq(Star)),
+      BinOp(AndAnd) => (BinOp(And), BinOp(And)),
+      BinOpEq(AndAnd) => (BinOp(And), BinOpEq(And)),
+      BinOp(OrOr) => (BinOp(Or), BinOp(Or)),
+      BinOpEq(OrOr) => (BinOp(Or), BinOpEq(Or)),
+      BinOp(QuestionQuestion) => (Question, Question),
+      QuestionDot => (Question, Dot),
+      BinOpEq(op) if !matches!(op, QuestionQuestion) => (BinOp(op), Eq),
+      _ => return None,
+    })
+  }
+}
+
 impl Token {
   pub fn new(kind: TokenKind, span: Span) -> Self {
     Token { kind, span }
@@ -226,20 +369,72 @@ impl Token {
     mem::replace(self, Token::dummy())
   }
 
+  /// Splits a compound punctuator like `>>` into two tokens, see
+  /// [`TokenKind::break_two_token_op`]. Each piece gets its own part of the span.
+  pub fn break_two_token_op(&self) -> Option<(Token, Token)> {
+    let (first, second) = self.kind.break_two_token_op()?;
+    let mid = self.span.lo() + BytePos(1);
+    Some((Token::new(first, self.span.with_hi(mid)), Token::new(second, self.span.with_lo(mid))))
+  }
+
+  /// Returns `true` if the token is a punctuator other than a delimiter, e.g. `+=` or `?.`.
   pub fn is_op(&self) -> bool {
-    todo!()
+    !matches!(
+      self.kind,
+      Unknown
+        | Eof
+        | Trivia(..)
+        | OpenDelim(..)
+        | CloseDelim(..)
+        | Backtick
+        | Literal(..)
+        | Ident(..)
+        | PrivateIdent(..)
+        | JsxText(..)
+        | JsxTextAllWhiteSpaces(..)
+    )
   }
 
   pub fn is_like_plus(&self) -> bool {
-    todo!()
+    matches!(self.kind, BinOp(Plus) | BinOpEq(Plus))
   }
 
+  /// Returns `true` if the token can appear at the start of an expression.
   pub fn can_begin_expr(&self) -> bool {
-    todo!()
+    match self.kind {
+      Ident(name) => ident_can_begin_expr(name),
+      // `#a in b`.
+      PrivateIdent(_) => true,
+      OpenDelim(Paren) | OpenDelim(Bracket) | OpenDelim(Brace) => true,
+      Literal(lit) => !matches!(lit.kind, Template(Middle) | Template(Tail)),
+      // Unary operators.
+      BinOp(Plus) | BinOp(Minus) | Not | Tilde | PlusPlus | MinusMinus => true,
+      // A regular expression, before the slash is rescanned.
+      BinOp(Slash) | BinOpEq(Slash) => true,
+      // A type assertion or a JSX element, and decorators of a class expression.
+      Lt | At => true,
+      _ => false,
+    }
   }
 
+  /// Returns `true` if the token can appear at the start of a type.
+  ///
+  /// Some tokens need more context: `-` only begins a type before a number,
+  /// e.g. `-1`, and `(` and `function` not at the start of a parameter.
   pub fn can_begin_type(&self) -> bool {
-    todo!()
+    match self.kind {
+      Ident(name) => ident_can_begin_type(name),
+      OpenDelim(Paren) | OpenDelim(Bracket) | OpenDelim(Brace) => true,
+      Literal(lit) => {
+        matches!(lit.kind, Num | Str(_) | BigInt | Template(NoSubstitution) | Template(Head))
+      }
+      // A generic function type, a leading union or intersection operator,
+      // a negative number literal type, or JSDoc types like `*`, `?string` and `!string`.
+      Lt | BinOp(Or) | BinOp(And) | BinOp(Minus) | BinOp(Star) | Question | Not => true,
+      // A rest element of a tuple type.
+      DotDotDot => true,
+      _ => false,
+    }
   }
 
   pub fn is_lit(&self) -> bool {
@@ -265,11 +460,23 @@ impl Token {
     self.ident().map_or(false, |ident| ident.name == name)
   }
 
-  /// Returns `true` if the token is either a special identifier or a keyword.
+  /// Returns `true` if the token is a reserved word, like `class`.
   pub fn is_reserved_ident(&self) -> bool {
     self.ident().map_or(false, Ident::is_reserved)
   }
 
+  /// Returns `true` if the token is the given keyword, `kw`.
+  pub fn is_keyword(&self, kw: Symbol) -> bool {
+    self.is_ident_named(kw) && kw.is_keyword()
+  }
+
+  /// Classifies the token if it is a keyword. Keywords are scanned as
+  /// identifiers, so the parser decides from the kind whether an identifier
+  /// acts as a keyword at its position.
+  pub fn keyword_kind(&self) -> Option<KeywordKind> {
+    self.ident().and_then(|ident| ident.name.keyword_kind())
+  }
+
   pub fn glue(&self, joint: &Token) -> Option<Token> {
     let kind = match self.kind {
       Eq => match joint.kind {
@@ -286,7 +493,7 @@ impl Token {
         Eq => Le,
         Lt => BinOp(Shl),
         Le => BinOpEq(Shl),
-        BinOp(Slash) => Arrow,
+        BinOp(Slash) => LtSlash,
         _ => return None,
       },
       Gt => match joint.kind {
@@ -313,6 +520,7 @@ impl Token {
         BinOp(And) if op == And => BinOp(AndAnd),
         BinOp(Or) if op == Or => BinOp(OrOr),
         BinOp(Star) if op == Star => BinOp(StarStar),
+        BinOpEq(Star) if op == Star => BinOpEq(StarStar),
         BinOpEq(And) if op == And => BinOpEq(AndAnd),
         BinOpEq(Or) if op == Or => BinOpEq(OrOr),
         _ => return None,
@@ -329,6 +537,46 @@ impl Token {
   }
 }
 
+/// Returns `true` if an identifier token with the given name can begin an expression.
+/// Besides identifiers and contextual keywords, only some reserved words can.
+fn ident_can_begin_expr(name: Symbol) -> bool {
+  !name.is_reserved()
+    || [
+      kw::Class,
+      kw::Delete,
+      kw::False,
+      kw::Function,
+      kw::Import,
+      kw::New,
+      kw::Null,
+      kw::Super,
+      kw::This,
+      kw::True,
+      kw::TypeOf,
+      kw::Void,
+    ]
+    .contains(&name)
+}
+
+/// Returns `true` if an identifier token with the given name can begin a type.
+/// This includes type keywords like `string` and type operators like `keyof`,
+/// but only some reserved words.
+fn ident_can_begin_type(name: Symbol) -> bool {
+  !name.is_reserved()
+    || [
+      kw::False,
+      kw::Function,
+      kw::Import,
+      kw::New,
+      kw::Null,
+      kw::This,
+      kw::True,
+      kw::TypeOf,
+      kw::Void,
+    ]
+    .contains(&name)
+}
+
 impl PartialEq<TokenKind> for Token {
   fn eq(&self, rhs: &TokenKind) -> bool {
     self.kind == *rhs
@@ -0,0 +1,296 @@
+use super::*;
+
+use crate::tokenstream::{Spacing, TokenStream, TokenStreamBuilder};
+
+use tscore_span::with_default_session_globals;
+
+fn tok(kind: TokenKind) -> Token {
+  Token::new(kind, DUMMY_SP)
+}
+
+fn lit(kind: LitKind) -> TokenKind {
+  Literal(Lit::new(kind, kw::Empty, None))
+}
+
+fn ident(name: &str) -> TokenKind {
+  Ident(Symbol::intern(name))
+}
+
+#[test]
+fn is_op() {
+  with_default_session_globals(|| {
+    let ops = [
+      Dot,
+      DotDotDot,
+      Semi,
+      Comma,
+      QuestionDot,
+      Lt,
+      LtSlash,
+      Le,
+      Gt,
+      Ge,
+      Eq,
+      EqEq,
+      EqEqEq,
+      NotEq,
+      NotEqEq,
+      Arrow,
+      BinOp(Plus),
+      BinOpEq(QuestionQuestion),
+      Not,
+      Tilde,
+      PlusPlus,
+      MinusMinus,
+      Colon,
+      At,
+      Question,
+    ];
+    for kind in ops.iter() {
+      assert!(tok(kind.clone()).is_op(), "{:?}", kind);
+    }
+
+    let non_ops = [
+      Unknown,
+      Eof,
+      Trivia(Whitespace),
+      OpenDelim(Paren),
+      CloseDelim(Brace),
+      Backtick,
+      lit(Num),
+      ident("a"),
+      PrivateIdent(kw::Empty),
+      JsxText(kw::Empty),
+      JsxTextAllWhiteSpaces(kw::Empty),
+    ];
+    for kind in non_ops.iter() {
+      assert!(!tok(kind.clone()).is_op(), "{:?}", kind);
+    }
+  })
+}
+
+#[test]
+fn is_like_plus() {
+  assert!(tok(BinOp(Plus)).is_like_plus());
+  assert!(tok(BinOpEq(Plus)).is_like_plus());
+  assert!(!tok(PlusPlus).is_like_plus());
+  assert!(!tok(BinOp(Minus)).is_like_plus());
+}
+
+#[test]
+fn can_begin_expr() {
+  with_default_session_globals(|| {
+    let starts = [
+      ident("a"),
+      ident("async"),
+      ident("await"),
+      ident("yield"),
+      ident("this"),
+      ident("super"),
+      ident("null"),
+      ident("true"),
+      ident("function"),
+      ident("class"),
+      ident("new"),
+      ident("typeof"),
+      ident("void"),
+      ident("delete"),
+      ident("import"),
+      PrivateIdent(Symbol::intern("#a")),
+      OpenDelim(Paren),
+      OpenDelim(Bracket),
+      OpenDelim(Brace),
+      lit(Num),
+      lit(Str(QuoteKind::Single)),
+      lit(BigInt),
+      lit(RegExp),
+      lit(Template(NoSubstitution)),
+      lit(Template(Head)),
+      BinOp(Plus),
+      BinOp(Minus),
+      Not,
+      Tilde,
+      PlusPlus,
+      MinusMinus,
+      BinOp(Slash),
+      BinOpEq(Slash),
+      Lt,
+      At,
+    ];
+    for kind in starts.iter() {
+      assert!(tok(kind.clone()).can_begin_expr(), "{:?}", kind);
+    }
+
+    let non_starts = [
+      ident("if"),
+      ident("var"),
+      ident("in"),
+      ident("instanceof"),
+      ident("extends"),
+      lit(Template(Middle)),
+      lit(Template(Tail)),
+      CloseDelim(Paren),
+      OpenDelim(NoDelim),
+      BinOp(Star),
+      BinOpEq(Plus),
+      Dot,
+      DotDotDot,
+      Semi,
+      Comma,
+      Arrow,
+      Eq,
+      Gt,
+      Colon,
+      Question,
+      Unknown,
+      Eof,
+      Trivia(NewLine),
+      Backtick,
+      JsxText(kw::Empty),
+    ];
+    for kind in non_starts.iter() {
+      assert!(!tok(kind.clone()).can_begin_expr(), "{:?}", kind);
+    }
+  })
+}
+
+#[test]
+fn can_begin_type() {
+  with_default_session_globals(|| {
+    let starts = [
+      ident("Foo"),
+      ident("any"),
+      ident("unknown"),
+      ident("string"),
+      ident("symbol"),
+      ident("never"),
+      ident("undefined"),
+      ident("object"),
+      ident("keyof"),
+      ident("unique"),
+      ident("infer"),
+      ident("asserts"),
+      ident("readonly"),
+      ident("typeof"),
+      ident("void"),
+      ident("null"),
+      ident("this"),
+      ident("true"),
+      ident("new"),
+      ident("import"),
+      ident("function"),
+      OpenDelim(Paren),
+      OpenDelim(Bracket),
+      OpenDelim(Brace),
+      lit(Num),
+      lit(Str(QuoteKind::Double)),
+      lit(BigInt),
+      lit(Template(NoSubstitution)),
+      lit(Template(Head)),
+      Lt,
+      BinOp(Or),
+      BinOp(And),
+      BinOp(Minus),
+      BinOp(Star),
+      Question,
+      Not,
+      DotDotDot,
+    ];
+    for kind in starts.iter() {
+      assert!(tok(kind.clone()).can_begin_type(), "{:?}", kind);
+    }
+
+    let non_starts = [
+      ident("class"),
+      ident("delete"),
+      ident("super"),
+      ident("if"),
+      lit(RegExp),
+      lit(Template(Tail)),
+      CloseDelim(Bracket),
+      BinOp(Plus),
+      BinOp(Slash),
+      PlusPlus,
+      Tilde,
+      Dot,
+      Comma,
+      Arrow,
+      Colon,
+      At,
+      Eof,
+      Unknown,
+    ];
+    for kind in non_starts.iter() {
+      assert!(!tok(kind.clone()).can_begin_type(), "{:?}", kind);
+    }
+  })
+}
+
+#[test]
+fn break_two_token_op() {
+  let sp = |lo, hi| Span::new(BytePos(lo), BytePos(hi));
+  let (first, second) = Token::new(BinOpEq(Ushr), sp(5, 9)).break_two_token_op().unwrap();
+  assert_eq!(first, Token::new(Gt, sp(5, 6)));
+  assert_eq!(second, Token::new(BinOpEq(Shr), sp(6, 9)));
+
+  let (first, second) = second.break_two_token_op().unwrap();
+  assert_eq!(first, Token::new(Gt, sp(6, 7)));
+  assert_eq!(second, Token::new(Ge, sp(7, 9)));
+
+  assert_eq!(tok(Gt).break_two_token_op(), None);
+  assert_eq!(tok(DotDotDot).break_two_token_op(), None);
+  assert_eq!(tok(BinOpEq(QuestionQuestion)).break_two_token_op(), None);
+}
+
+#[test]
+fn break_two_token_op_is_inverse_of_glue() {
+  let kinds = [
+    Le,
+    LtSlash,
+    BinOp(Shl),
+    BinOpEq(Shl),
+    Ge,
+    BinOp(Shr),
+    BinOpEq(Shr),
+    BinOp(Ushr),
+    BinOpEq(Ushr),
+    EqEq,
+    EqEqEq,
+    Arrow,
+    NotEq,
+    NotEqEq,
+    PlusPlus,
+    MinusMinus,
+    BinOp(StarStar),
+    BinOpEq(StarStar),
+    BinOp(AndAnd),
+    BinOpEq(AndAnd),
+    BinOp(OrOr),
+    BinOpEq(OrOr),
+    BinOp(QuestionQuestion),
+    QuestionDot,
+    BinOpEq(Plus),
+    BinOpEq(Percent),
+    BinOpEq(Caret),
+  ];
+  for kind in kinds.iter() {
+    let (first, second) = tok(kind.clone()).break_two_token_op().expect("breakable token");
+    assert_eq!(first.glue(&second).map(|token| token.kind), Some(kind.clone()), "{:?}", kind);
+  }
+}
+
+#[test]
+fn break_two_token_op_keeps_spacing() {
+  let sp = |lo, hi| Span::new(BytePos(lo), BytePos(hi));
+  let tree = TokenTree::token(BinOp(Shr), sp(0, 2));
+  let (first, second) = tree.break_two_token_op(Spacing::Alone).unwrap();
+  assert_eq!(first, (TokenTree::token(Gt, sp(0, 1)), Spacing::Joint));
+  assert_eq!(second, (TokenTree::token(Gt, sp(1, 2)), Spacing::Alone));
+
+  let mut builder = TokenStreamBuilder::new();
+  builder.push(TokenStream::new(vec![first]));
+  builder.push(TokenStream::new(vec![second]));
+  let stream = builder.build();
+  assert_eq!(stream.len(), 1);
+  assert!(stream.eq_unspanned(&tree.into()));
+}
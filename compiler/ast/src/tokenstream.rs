@@ -89,6 +89,19 @@ impl TokenTree {
     }
   }
 
+  /// Splits a compound punctuator like `>>` into two token trees, the inverse of the
+  /// gluing done by `TokenStreamBuilder::push`. The first piece is `Joint` so that pushing
+  /// both pieces glues them back together, and the second keeps the given `spacing`.
+  pub fn break_two_token_op(&self, spacing: Spacing) -> Option<(TreeAndSpacing, TreeAndSpacing)> {
+    match self {
+      TokenTree::Token(token) => {
+        let (first, second) = token.break_two_token_op()?;
+        Some(((TokenTree::Token(first), Spacing::Joint), (TokenTree::Token(second), spacing)))
+      }
+      TokenTree::Delimited(..) => None,
+    }
+  }
+
   pub fn joint(self) -> TokenStream {
     TokenStream::new(vec![(self, Spacing::Joint)])
   }
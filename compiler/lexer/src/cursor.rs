@@ -50,11 +50,6 @@ impl<'a> Cursor<'a> {
     self.nth_char(0)
   }
 
-  /// Peeks the second symbol from the input stream without consuming it.
-  pub(crate) fn second(&self) -> char {
-    self.nth_char(1)
-  }
-
   /// Checks if there is nothing more to consume.
   pub(crate) fn is_eof(&self) -> bool {
     self.chars.as_str().is_empty()
@@ -65,6 +60,11 @@ impl<'a> Cursor<'a> {
     self.initial_len - self.chars.as_str().len()
   }
 
+  /// Returns the remaining input.
+  pub(crate) fn as_str(&self) -> &'a str {
+    self.chars.as_str()
+  }
+
   /// Returns a `Chars` iterator over the remaining characters.
   fn chars(&self) -> Chars<'a> {
     self.chars.clone()
@@ -81,4 +81,11 @@ impl<'a> Cursor<'a> {
 
     Some(c)
   }
+
+  /// Moves `n` characters forward.
+  pub(crate) fn bump_n(&mut self, n: usize) {
+    for _ in 0..n {
+      self.bump();
+    }
+  }
 }
@@ -1,23 +1,19 @@
-//! Low-level Rust lexer.
+//! Low-level ECMAScript lexer.
 //!
-//! The idea with `librustc_lexer` is to make a reusable library,
-//! by separating out pure lexing and rustc-specific concerns, like spans,
-//! error reporting, and interning.  So, rustc_lexer operates directly on `&str`,
+//! The idea with `tscore_lexer` is to make a reusable library,
+//! by separating out pure lexing and tscore-specific concerns, like spans,
+//! error reporting, and interning.  So, tscore_lexer operates directly on `&str`,
 //! produces simple tokens which are a pair of type-tag and a bit of original text,
 //! and does not report errors, instead storing them as flags on the token.
 //!
-//! Tokens produced by this lexer are not yet ready for parsing the Rust syntax.
-//! For that see [`librustc_parse::lexer`], which converts this basic token stream
-//! into wide tokens used by actual parser.
-//!
-//! The purpose of this crate is to convert raw sources into a labeled sequence
-//! of well-known token types, so building an actual Rust token stream will
-//! be easier.
+//! Tokens follow the lexical grammar of ECMAScript, see
+//! [ECMA262](https://tc39.es/ecma262/#sec-ecmascript-language-lexical-grammar).
+//! Punctuators are produced one char at a time and gluing them is left to the consumer,
+//! as are the goal symbols the grammar can't pick on its own: a `/` is always a
+//! [`TokenKind::Slash`], never the start of a regular expression.
 //!
 //! The main entity of this crate is the [`TokenKind`] enum which represents common
 //! lexeme types.
-//!
-//! [`librustc_parse::lexer`]: ../rustc_parse/lexer/index.html
 // We want to be able to build this crate with a stable compiler, so no
 // `#![feature]` attributes should be added.
 
@@ -55,8 +51,7 @@ pub enum TokenKind {
   LineComment,
   /// `/* block comment */`
   ///
-  /// Block comments can be recursive, so the sequence like `/* /* */`
-  /// will not be considered terminated and will result in a parsing error.
+  /// Block comments don't nest, so the sequence like `/* /* */` is a complete comment.
   BlockComment { terminated: bool },
   /// Any whitespace characters sequence.
   Whitespace,
@@ -65,7 +60,8 @@ pub enum TokenKind {
   /// "ident" or "continue"
   /// At this step keywords are also considered identifiers.
   Ident,
-  /// "12_u8", "1.0e-40". See `LiteralKind` for more details.
+  /// "1_000", "1.0e-40", "123n", "'abc'" or "`abc${". See `LiteralKind` for more details.
+  /// Only numbers have a suffix, the `n` of a bigint.
   Literal { kind: LiteralKind, suffix_start: usize },
   // One-char tokens:
   /// ";"
@@ -96,8 +92,6 @@ pub enum TokenKind {
   Question,
   /// ":"
   Colon,
-  /// "$"
-  Dollar,
   /// "="
   Eq,
   /// "!"
@@ -130,10 +124,13 @@ pub enum TokenKind {
 /// see more https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Lexical_grammar
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LiteralKind {
-  /// "12_u8", "0o100", "0b120i99", "123n", "0x123n", ""
+  /// "1_000", "0o100", "0b120", "123n", "0x123n", "1e"
   Numeric { base: Base, empty_int: bool, empty_exponent: bool },
-  /// ""abc"", ""abc", "'abc'", "`abc${d}`"
+  /// ""abc"", ""abc", "'abc'"
   Str { terminated: bool },
+  /// "`abc`", "`abc${", "}abc${", "}abc`". Only the parts ending with a backtick
+  /// can be unterminated.
+  Template { kind: TemplateKind, terminated: bool },
 }
 
 /// Base of numeric literal encoding according to its prefix.
@@ -143,51 +140,94 @@ pub enum Base {
   Binary,
   /// Literal starts with "0o".
   Octal,
+  /// Literal starts with "0" followed by octal digits only, e.g. "0777".
+  /// Not allowed in strict mode code.
+  LegacyOctal,
   /// Literal starts with "0x".
   Hexadecimal,
   /// Literal doesn't contain a prefix.
   Decimal,
 }
 
-/// `rustc` allows files to have a shebang, e.g. "#!/usr/bin/rustrun",
-/// but shebang isn't a part of rust syntax.
-pub fn strip_shebang(input: &str) -> Option<usize> {
-  // Shebang must start with `#!` literally, without any preceding whitespace.
-  // For simplicity we consider any line starting with `#!` a shebang,
-  // regardless of restrictions put on shebangs by specific platforms.
-  if let Some(input_tail) = input.strip_prefix("#!") {
-    // Ok, this is a shebang but if the next non-whitespace token is `[`,
-    // then it may be valid Rust code, so consider it Rust code.
-    let next_non_whitespace_token = tokenize(input_tail).map(|tok| tok.kind).find(|tok| {
-      !matches!(
-        tok,
-        TokenKind::Whitespace
-          | TokenKind::LineBreak
-          | TokenKind::LineComment
-          | TokenKind::BlockComment { .. }
-      )
-    });
-    if next_non_whitespace_token != Some(TokenKind::OpenBracket) {
-      // No other choice than to consider this a shebang.
-      return Some(2 + input_tail.lines().next().unwrap_or_default().len());
-    }
+/// The part of a template literal a template token is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TemplateKind {
+  /// "`abc`"
+  NoSubstitution,
+  /// "`abc${"
+  Head,
+  /// "}abc${"
+  Middle,
+  /// "}abc`"
+  Tail,
+}
+
+impl TemplateKind {
+  /// Whether the part ends with `${`, opening a substitution.
+  pub fn has_substitution(self) -> bool {
+    matches!(self, TemplateKind::Head | TemplateKind::Middle)
   }
-  None
+}
+
+/// Returns the length of the hashbang comment the input starts with, e.g. "#!/usr/bin/env node".
+/// It must be at the very start of the source and runs up to the first line terminator.
+/// See [ECMA262](https://tc39.es/ecma262/#sec-hashbang).
+pub fn strip_shebang(input: &str) -> Option<usize> {
+  let input_tail = input.strip_prefix("#!")?;
+  Some(2 + input_tail.find(is_line_break).unwrap_or_else(|| input_tail.len()))
 }
 
 /// Parses the first token from the provided input string.
+///
+/// A `}` is always a [`TokenKind::CloseBrace`], use [`continue_template`] when it
+/// ends a template substitution instead.
 pub fn first_token(input: &str) -> Token {
   debug_assert!(!input.is_empty());
   Cursor::new(input).advance_token()
 }
 
+/// Parses the template middle or tail the input starts with, e.g. "}abc${" or "}abc`".
+pub fn continue_template(input: &str) -> Token {
+  debug_assert!(input.starts_with('}'));
+  let mut cursor = Cursor::new(input);
+  cursor.bump();
+  let token_kind = cursor.template(false);
+  Token::new(token_kind, cursor.len_consumed())
+}
+
 /// Creates an iterator that produces tokens from the input string.
+///
+/// Template literals are tracked across tokens, so the `}` closing a substitution
+/// continues its template.
 pub fn tokenize(mut input: &str) -> impl Iterator<Item = Token> + '_ {
+  // Open braces in each enclosing template substitution, innermost last.
+  let mut brace_depths: Vec<usize> = Vec::new();
   std::iter::from_fn(move || {
     if input.is_empty() {
       return None;
     }
-    let token = first_token(input);
+    let token = if input.starts_with('}') && brace_depths.last() == Some(&0) {
+      brace_depths.pop();
+      continue_template(input)
+    } else {
+      first_token(input)
+    };
+    match token.kind {
+      OpenBrace => {
+        if let Some(depth) = brace_depths.last_mut() {
+          *depth += 1;
+        }
+      }
+      CloseBrace => {
+        if let Some(depth) = brace_depths.last_mut() {
+          *depth -= 1;
+        }
+      }
+      Literal { kind: Template { kind, .. }, .. } if kind.has_substitution() => {
+        brace_depths.push(0);
+      }
+      _ => (),
+    }
     input = &input[token.len..];
     Some(token)
   })
@@ -197,10 +237,9 @@ pub fn tokenize(mut input: &str) -> impl Iterator<Item = Token> + '_ {
 /// See [ECMA262](https://tc39.es/ecma262/#sec-white-space)
 /// for definitions of these classes.
 pub fn is_whitespace(c: char) -> bool {
-  // This is Pattern_White_Space.
+  // This is <TAB>, <VT>, <FF>, <ZWNBSP> and the Space_Separator category (<USP>).
   //
-  // Note that this set is stable (ie, it doesn't change with different
-  // Unicode versions), so it's ok to just hard-code the values.
+  // Note that ZWNJ and ZWJ are identifier parts rather than whitespace.
 
   matches!(
     c,
@@ -215,7 +254,8 @@ pub fn is_whitespace(c: char) -> bool {
         // other Unicode Space Separators
         // see [Space]https://en.wikipedia.org/wiki/Space_%28punctuation%29#Spaces_in_Unicode
         | '\u{1680}' // Ogham space mark
-        | '\u{180E}' // Mongolian Vowel Separator
+        | '\u{2000}' // En Quad
+        | '\u{2001}' // Em Quad
         | '\u{2002}' // En Space
         | '\u{2003}' // Em space
         | '\u{2004}' // Three-Per-Em Space
@@ -225,12 +265,8 @@ pub fn is_whitespace(c: char) -> bool {
         | '\u{2008}' // Punctuation Space
         | '\u{2009}' // Thin Space
         | '\u{200A}' // Hair space
-        | '\u{200B}' // Zero Width Space
-        | '\u{200C}' // Zero Width Non Joiner
-        | '\u{200D}' // Zero Width Joiner
         | '\u{202F}' // Narrow No-Break Space
         | '\u{205F}' // Medium Mathematical Space
-        | '\u{2060}' // Word Joiner
         | '\u{3000}' // Ideographic Space
   )
 }
@@ -294,7 +330,7 @@ fn lookup_in_unicode_map(code: u32, map: &[u32]) -> bool {
 /// a formal definition of valid identifier name.
 #[inline]
 pub fn is_id_start(c: char) -> bool {
-  lookup_in_unicode_map(c as u32, &UNICODE_ESNEXT_IDENTIFIER_START)
+  c == '$' || c == '_' || lookup_in_unicode_map(c as u32, &UNICODE_ESNEXT_IDENTIFIER_START)
 }
 
 /// True if `c` is valid as a non-first character of an identifier.
@@ -302,7 +338,26 @@ pub fn is_id_start(c: char) -> bool {
 /// a formal definition of valid identifier name.
 #[inline]
 pub fn is_id_part(c: char) -> bool {
-  lookup_in_unicode_map(c as u32, &UNICODE_ESNEXT_IDENTIFIER_PART)
+  matches!(c, '$' | '\u{200C}' | '\u{200D}')
+    || lookup_in_unicode_map(c as u32, &UNICODE_ESNEXT_IDENTIFIER_PART)
+}
+
+/// Decodes a `\uXXXX` or `\u{X...}` escape, `input` being the text right after the backslash.
+/// Returns the escaped character and the length of the escape without the backslash,
+/// or `None` if the escape is malformed or escapes a surrogate.
+pub fn unicode_escape(input: &str) -> Option<(char, usize)> {
+  let rest = input.strip_prefix('u')?;
+  let (digits, len) = if let Some(rest) = rest.strip_prefix('{') {
+    let end = rest.find('}')?;
+    (&rest[..end], end + 3)
+  } else {
+    (rest.get(..4)?, 5)
+  };
+  if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+    return None;
+  }
+  let code = u32::from_str_radix(digits, 16).ok()?;
+  std::char::from_u32(code).map(|c| (c, len))
 }
 
 /// The passed string is lexically an identifier.
@@ -337,6 +392,15 @@ impl Cursor<'_> {
       // start as identifier).
       c if is_id_start(c) => self.ident(),
 
+      // Identifier starting with an escape, e.g. `\u0061bc`.
+      '\\' => match unicode_escape(self.as_str()) {
+        Some((c, len)) if is_id_start(c) => {
+          self.bump_n(len);
+          self.ident()
+        }
+        _ => Unknown,
+      },
+
       // Numeric literal.
       '0'..='9' => {
         let literal_kind = self.number();
@@ -353,16 +417,16 @@ impl Cursor<'_> {
       }
 
       // String literal.
-      '"' | '\'' | '`' => {
+      '"' | '\'' => {
         let terminated = self.string();
         let suffix_start = self.len_consumed();
-        if terminated {
-          self.eat_literal_suffix();
-        }
         let kind = Str { terminated };
         Literal { kind, suffix_start }
       }
 
+      // Template literal, up to the end or the first substitution.
+      '`' => self.template(true),
+
       // One-symbol tokens.
       ';' => Semi,
       ',' => Comma,
@@ -378,7 +442,6 @@ impl Cursor<'_> {
       '~' => Tilde,
       '?' => Question,
       ':' => Colon,
-      '$' => Dollar,
       '=' => Eq,
       '!' => Bang,
       '<' => Lt,
@@ -398,7 +461,7 @@ impl Cursor<'_> {
   fn line_comment(&mut self) -> TokenKind {
     debug_assert!(self.prev() == '/' && self.first() == '/');
     self.bump();
-    self.eat_while(|c| c != '\n');
+    self.eat_while(|c| !is_line_break(c));
     LineComment
   }
 
@@ -406,28 +469,17 @@ impl Cursor<'_> {
     debug_assert!(self.prev() == '/' && self.first() == '*');
     self.bump();
 
-    let mut depth = 1usize;
     while let Some(c) = self.bump() {
-      match c {
-        '/' if self.first() == '*' => {
-          self.bump();
-          depth += 1;
-        }
-        '*' if self.first() == '/' => {
-          self.bump();
-          depth -= 1;
-          if depth == 0 {
-            // This block comment is closed, so for a construction like "/* */ */"
-            // there will be a successfully parsed block comment "/* */"
-            // and " */" will be processed separately.
-            break;
-          }
-        }
-        _ => (),
+      // Comments don't nest, so for a construction like "/* /* */ */"
+      // there will be a successfully parsed block comment "/* /* */"
+      // and " */" will be processed separately.
+      if c == '*' && self.first() == '/' {
+        self.bump();
+        return BlockComment { terminated: true };
       }
     }
 
-    BlockComment { terminated: depth == 0 }
+    BlockComment { terminated: false }
   }
 
   fn whitespace(&mut self) -> TokenKind {
@@ -443,9 +495,14 @@ impl Cursor<'_> {
   }
 
   fn ident(&mut self) -> TokenKind {
-    debug_assert!(is_id_start(self.prev()));
-    // Start is already eaten, eat the rest of identifier.
-    self.eat_while(is_id_part);
+    // Start is already eaten, eat the rest of identifier, including escapes.
+    loop {
+      self.eat_while(is_id_part);
+      match unicode_escape(self.as_str().strip_prefix('\\').unwrap_or_default()) {
+        Some((c, len)) if is_id_part(c) => self.bump_n(len + 1),
+        _ => break,
+      }
+    }
     Ident
   }
 
@@ -475,19 +532,34 @@ impl Cursor<'_> {
           self.bump();
           self.eat_hexadecimal_digits()
         }
-        // Not a base prefix.
-        '0'..='9' | '_' | '.' | 'e' | 'E' => {
-          self.eat_decimal_digits();
+        // A legacy octal literal like "0777", unless it has an "8" or a "9" in it.
+        '0'..='9' | '_' => {
+          let mut octal = true;
+          self.eat_while(|c| match c {
+            '0'..='7' | '_' => true,
+            '8' | '9' => {
+              octal = false;
+              true
+            }
+            _ => false,
+          });
+          if octal {
+            // Legacy octal literals have no fraction or exponent.
+            return Numeric { base: Base::LegacyOctal, empty_int: false, empty_exponent: false };
+          }
           true
         }
-        // Just a 0.
-        _ => return Numeric { base, empty_int: false, empty_exponent: false },
+        // Just a 0, possibly with a fraction or an exponent.
+        _ => true,
       };
       // Base prefix was provided, but there were no digits
       // after it, e.g. "0x".
       if !has_digits {
         return Numeric { base, empty_int: true, empty_exponent: false };
       }
+      if base != Base::Decimal {
+        return Numeric { base, empty_int: false, empty_exponent: false };
+      }
     } else if self.prev() == '.' {
       float = true;
       self.eat_decimal_digits();
@@ -496,25 +568,13 @@ impl Cursor<'_> {
       self.eat_decimal_digits();
     };
 
+    // Unlike in Rust, "1." is a complete number, so "1.toString()" is
+    // "1." followed by "toString" and "1..toString()" is a method call.
+    if !float && self.first() == '.' {
+      self.bump();
+      self.eat_decimal_digits();
+    }
     match self.first() {
-      // Don't be greedy if this is actually an
-      // integer literal followed by field/method access
-      // (`12.1`, `12.foo()`)
-      '.' if !float && self.second().is_digit(10) => {
-        // might have stuff after the ., and if it does, it needs to start
-        // with a number
-        self.bump();
-        let mut empty_exponent = false;
-        self.eat_decimal_digits();
-        match self.first() {
-          'e' | 'E' => {
-            self.bump();
-            empty_exponent = !self.eat_float_exponent();
-          }
-          _ => (),
-        }
-        Numeric { base, empty_int: false, empty_exponent }
-      }
       'e' | 'E' => {
         self.bump();
         let empty_exponent = !self.eat_float_exponent();
@@ -526,21 +586,53 @@ impl Cursor<'_> {
 
   /// Eats string and returns true
   /// if it is terminated.
+  ///
+  /// Only <LF> and <CR> end a string early, <LS> and <PS> are allowed in it.
   fn string(&mut self) -> bool {
     let quote = self.prev();
     debug_assert!(quote == '"' || quote == '\'');
-    while let Some(c) = self.bump() {
-      match c {
-        c if c == quote => {
-          return true;
-        }
-        '\\' if self.first() == '\\' || self.first() == quote => {
-          self.bump();
+    loop {
+      match self.first() {
+        '\n' | '\r' => return false,
+        _ if self.is_eof() => return false,
+        _ => (),
+      }
+      match self.bump() {
+        Some(c) if c == quote => return true,
+        // Escapes include line continuations, "\\\r\n" being a single one.
+        Some('\\') => {
+          if self.bump() == Some('\r') && self.first() == '\n' {
+            self.bump();
+          }
         }
         _ => (),
       }
     }
-    false
+  }
+
+  /// Eats the rest of a template token, the opening backtick or `}` is already eaten.
+  fn template(&mut self, started_with_backtick: bool) -> TokenKind {
+    let (no_substitution, substitution) = if started_with_backtick {
+      (TemplateKind::NoSubstitution, TemplateKind::Head)
+    } else {
+      (TemplateKind::Tail, TemplateKind::Middle)
+    };
+    let (kind, terminated) = loop {
+      match self.bump() {
+        Some('`') => break (no_substitution, true),
+        Some('$') if self.first() == '{' => {
+          self.bump();
+          break (substitution, true);
+        }
+        Some('\\') => {
+          self.bump();
+        }
+        Some(_) => (),
+        None => break (no_substitution, false),
+      }
+    };
+    let suffix_start = self.len_consumed();
+    Literal { kind: Template { kind, terminated }, suffix_start }
   }
 
   fn eat_decimal_digits(&mut self) -> bool {
@@ -587,18 +679,12 @@ impl Cursor<'_> {
     self.eat_decimal_digits()
   }
 
+  /// Eats the `n` of a bigint. An identifier right after a number is not part of it,
+  /// which is an error for the consumer to report.
   fn eat_literal_suffix(&mut self) {
-    self.eat_identifier();
-  }
-
-  // Eats the identifier.
-  fn eat_identifier(&mut self) {
-    if !is_id_start(self.first()) {
-      return;
+    if self.first() == 'n' {
+      self.bump();
     }
-    self.bump();
-
-    self.eat_while(is_id_part);
   }
 
   /// Eats symbols while predicate returns true or until the end of file is reached.
@@ -10,45 +10,34 @@ fn test_valid_shebang() {
 
 #[test]
 fn test_shebang_second_line() {
-  // Because shebangs are interpreted by the kernel, they must be on the first line
-  let input = "\n#!/bin/bash";
+  // Hashbang comments are only allowed at the very start of the source.
+  let input = "\n#!/usr/bin/node";
   assert_eq!(strip_shebang(input), None);
 }
 
 #[test]
-fn test_shebang_space() {
-  let input = "#!    /bin/bash";
-  assert_eq!(strip_shebang(input), Some(input.len()));
-}
-
-#[test]
-fn test_shebang_empty_shebang() {
-  let input = "#!    \n[attribute(foo)]";
+fn test_shebang_after_whitespace() {
+  let input = " #!/usr/bin/node";
   assert_eq!(strip_shebang(input), None);
 }
 
 #[test]
-fn test_invalid_shebang_comment() {
-  let input = "#!//bin/ami/a/comment\n[";
-  assert_eq!(strip_shebang(input), None)
-}
-
-#[test]
-fn test_invalid_shebang_another_comment() {
-  let input = "#!/*bin/ami/a/comment*/\n[attribute";
-  assert_eq!(strip_shebang(input), None)
+fn test_shebang_space() {
+  let input = "#!    /bin/bash";
+  assert_eq!(strip_shebang(input), Some(input.len()));
 }
 
 #[test]
-fn test_shebang_valid_rust_after() {
-  let input = "#!/*bin/ami/a/comment*/\npub fn main() {}";
-  assert_eq!(strip_shebang(input), Some(23))
+fn test_shebang_ends_at_any_line_terminator() {
+  assert_eq!(strip_shebang("#!node\r\n"), Some(6));
+  assert_eq!(strip_shebang("#!node\u{2028}x"), Some(6));
 }
 
 #[test]
-fn test_shebang_followed_by_attrib() {
-  let input = "#!/bin/rust-scripts\n#![allow_unused(true)]";
-  assert_eq!(strip_shebang(input), Some(19));
+fn test_shebang_followed_by_bracket() {
+  // Unlike Rust attributes, a `[` on the next line doesn't matter.
+  let input = "#!/usr/bin/node\n[1, 2].forEach(f)";
+  assert_eq!(strip_shebang(input), Some(15));
 }
 
 fn check_lexing(src: &str, expect: Expect) {
@@ -115,16 +104,57 @@ fn comment_flavors() {
 }
 
 #[test]
-fn nested_block_comments() {
+fn block_comments_do_not_nest() {
   check_lexing(
     "/* /* */ */'a'",
     expect![[r#"
-            Token { kind: BlockComment { terminated: true }, len: 11 }
+            Token { kind: BlockComment { terminated: true }, len: 8 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Star, len: 1 }
+            Token { kind: Slash, len: 1 }
             Token { kind: Literal { kind: Str { terminated: true }, suffix_start: 3 }, len: 3 }
         "#]],
   )
 }
 
+#[test]
+fn unterminated_block_comment() {
+  check_lexing(
+    "/* abc",
+    expect![[r#"
+            Token { kind: BlockComment { terminated: false }, len: 6 }
+        "#]],
+  )
+}
+
+#[test]
+fn line_terminators() {
+  // LineTerminator :: <LF> <CR> <LS> <PS>, a single line comment ends at any of them.
+  check_lexing(
+    "// a\r\n// b\u{2028}// c\u{2029}",
+    expect![[r#"
+            Token { kind: LineComment, len: 4 }
+            Token { kind: LineBreak, len: 2 }
+            Token { kind: LineComment, len: 4 }
+            Token { kind: LineBreak, len: 3 }
+            Token { kind: LineComment, len: 4 }
+            Token { kind: LineBreak, len: 3 }
+        "#]],
+  )
+}
+
+#[test]
+fn white_space() {
+  // WhiteSpace :: <TAB> <VT> <FF> <ZWNBSP> <USP>
+  check_lexing(
+    "\t\u{B}\u{C} \u{A0}\u{FEFF}\u{1680}\u{2000}\u{200A}\u{202F}\u{205F}\u{3000}\u{200B}",
+    expect![[r#"
+            Token { kind: Whitespace, len: 27 }
+            Token { kind: Unknown, len: 3 }
+        "#]],
+  )
+}
+
 #[test]
 fn characters() {
   check_lexing(
@@ -166,6 +196,11 @@ fn literals() {
 0b123n
 0x123n
 0123n
+1.
+1.e3
+08.5
+0777.5
+1in
 "####,
     expect![[r#"
             Token { kind: LineBreak, len: 1 }
@@ -187,7 +222,7 @@ fn literals() {
             Token { kind: LineBreak, len: 1 }
             Token { kind: Literal { kind: Numeric { base: Decimal, empty_int: false, empty_exponent: false }, suffix_start: 4 }, len: 4 }
             Token { kind: LineBreak, len: 1 }
-            Token { kind: Literal { kind: Numeric { base: Decimal, empty_int: false, empty_exponent: false }, suffix_start: 4 }, len: 4 }
+            Token { kind: Literal { kind: Numeric { base: LegacyOctal, empty_int: false, empty_exponent: false }, suffix_start: 4 }, len: 4 }
             Token { kind: LineBreak, len: 1 }
             Token { kind: Literal { kind: Numeric { base: Octal, empty_int: false, empty_exponent: false }, suffix_start: 4 }, len: 4 }
             Token { kind: LineBreak, len: 1 }
@@ -213,8 +248,130 @@ fn literals() {
             Token { kind: LineBreak, len: 1 }
             Token { kind: Literal { kind: Numeric { base: Hexadecimal, empty_int: false, empty_exponent: false }, suffix_start: 5 }, len: 6 }
             Token { kind: LineBreak, len: 1 }
-            Token { kind: Literal { kind: Numeric { base: Decimal, empty_int: false, empty_exponent: false }, suffix_start: 4 }, len: 5 }
+            Token { kind: Literal { kind: Numeric { base: LegacyOctal, empty_int: false, empty_exponent: false }, suffix_start: 4 }, len: 5 }
+            Token { kind: LineBreak, len: 1 }
+            Token { kind: Literal { kind: Numeric { base: Decimal, empty_int: false, empty_exponent: false }, suffix_start: 2 }, len: 2 }
+            Token { kind: LineBreak, len: 1 }
+            Token { kind: Literal { kind: Numeric { base: Decimal, empty_int: false, empty_exponent: false }, suffix_start: 4 }, len: 4 }
+            Token { kind: LineBreak, len: 1 }
+            Token { kind: Literal { kind: Numeric { base: Decimal, empty_int: false, empty_exponent: false }, suffix_start: 4 }, len: 4 }
+            Token { kind: LineBreak, len: 1 }
+            Token { kind: Literal { kind: Numeric { base: LegacyOctal, empty_int: false, empty_exponent: false }, suffix_start: 4 }, len: 4 }
+            Token { kind: Literal { kind: Numeric { base: Decimal, empty_int: false, empty_exponent: false }, suffix_start: 2 }, len: 2 }
+            Token { kind: LineBreak, len: 1 }
+            Token { kind: Literal { kind: Numeric { base: Decimal, empty_int: false, empty_exponent: false }, suffix_start: 1 }, len: 1 }
+            Token { kind: Ident, len: 2 }
+            Token { kind: LineBreak, len: 1 }
+        "#]],
+  )
+}
+
+#[test]
+fn escaped_identifiers() {
+  check_lexing(
+    r"\u0061bc a\u{62}\u{0063} \u0030 \u{D800}",
+    expect![[r#"
+            Token { kind: Ident, len: 8 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Ident, len: 15 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Unknown, len: 1 }
+            Token { kind: Ident, len: 5 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Unknown, len: 1 }
+            Token { kind: Ident, len: 1 }
+            Token { kind: OpenBrace, len: 1 }
+            Token { kind: Ident, len: 4 }
+            Token { kind: CloseBrace, len: 1 }
+        "#]],
+  )
+}
+
+#[test]
+fn strings() {
+  // Strings can't span lines, except with a line continuation or through <LS> and <PS>.
+  check_lexing(
+    "'a\\\r\nb' \"\u{2028}\u{2029}\" 'a\\'b' 'c\nd' \"e",
+    expect![[r#"
+            Token { kind: Literal { kind: Str { terminated: true }, suffix_start: 7 }, len: 7 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Literal { kind: Str { terminated: true }, suffix_start: 8 }, len: 8 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Literal { kind: Str { terminated: true }, suffix_start: 6 }, len: 6 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Literal { kind: Str { terminated: false }, suffix_start: 2 }, len: 2 }
             Token { kind: LineBreak, len: 1 }
+            Token { kind: Ident, len: 1 }
+            Token { kind: Literal { kind: Str { terminated: false }, suffix_start: 4 }, len: 4 }
+        "#]],
+  )
+}
+
+#[test]
+fn templates() {
+  check_lexing(
+    "`a\nb` `a${b}c${d}e` `a\\${b\\`` `${ {a: `${b}`} }`",
+    expect![[r#"
+            Token { kind: Literal { kind: Template { kind: NoSubstitution, terminated: true }, suffix_start: 5 }, len: 5 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Literal { kind: Template { kind: Head, terminated: true }, suffix_start: 4 }, len: 4 }
+            Token { kind: Ident, len: 1 }
+            Token { kind: Literal { kind: Template { kind: Middle, terminated: true }, suffix_start: 4 }, len: 4 }
+            Token { kind: Ident, len: 1 }
+            Token { kind: Literal { kind: Template { kind: Tail, terminated: true }, suffix_start: 3 }, len: 3 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Literal { kind: Template { kind: NoSubstitution, terminated: true }, suffix_start: 9 }, len: 9 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Literal { kind: Template { kind: Head, terminated: true }, suffix_start: 3 }, len: 3 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: OpenBrace, len: 1 }
+            Token { kind: Ident, len: 1 }
+            Token { kind: Colon, len: 1 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Literal { kind: Template { kind: Head, terminated: true }, suffix_start: 3 }, len: 3 }
+            Token { kind: Ident, len: 1 }
+            Token { kind: Literal { kind: Template { kind: Tail, terminated: true }, suffix_start: 2 }, len: 2 }
+            Token { kind: CloseBrace, len: 1 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Literal { kind: Template { kind: Tail, terminated: true }, suffix_start: 2 }, len: 2 }
+        "#]],
+  )
+}
+
+#[test]
+fn unterminated_templates() {
+  check_lexing(
+    "`a${b}c",
+    expect![[r#"
+            Token { kind: Literal { kind: Template { kind: Head, terminated: true }, suffix_start: 4 }, len: 4 }
+            Token { kind: Ident, len: 1 }
+            Token { kind: Literal { kind: Template { kind: Tail, terminated: false }, suffix_start: 2 }, len: 2 }
+        "#]],
+  );
+  check_lexing(
+    "`a${",
+    expect![[r#"
+            Token { kind: Literal { kind: Template { kind: Head, terminated: true }, suffix_start: 4 }, len: 4 }
+        "#]],
+  );
+}
+
+#[test]
+fn identifiers() {
+  // IdentifierStart includes `$` and `_`, IdentifierPart also <ZWNJ> and <ZWJ>.
+  check_lexing(
+    "$ _ $a_1 a\u{200C}\u{200D}b #x",
+    expect![[r#"
+            Token { kind: Ident, len: 1 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Ident, len: 1 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Ident, len: 4 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Ident, len: 8 }
+            Token { kind: Whitespace, len: 1 }
+            Token { kind: Pound, len: 1 }
+            Token { kind: Ident, len: 1 }
         "#]],
   )
 }
@@ -1,5 +1,8 @@
 //! Utilities for validating string and char literals and turning them into
 //! values they represent.
+//!
+//! The `Mode`s follow Rust rules, JavaScript string and template literals are
+//! handled by [`unescape_js_literal`] with a [`JsMode`].
 
 use std::ops::Range;
 use std::str::Chars;
@@ -56,6 +59,15 @@ pub enum EscapeError {
   NonAsciiCharInByte,
   /// Non-ascii character in byte string literal.
   NonAsciiCharInByteString,
+
+  /// Legacy octal escape in strict mode code or a template, e.g. '\01'.
+  LegacyOctalEscape,
+  /// '\8' or '\9' in strict mode code or a template.
+  NonOctalDecimalEscape,
+  /// Less than 4 hex digits in '\uXXXX', e.g. '\u12'.
+  TooShortUnicodeEscape,
+  /// Raw '\n' or '\r' in a string, only <LS> and <PS> can appear unescaped.
+  UnescapedLineTerminator,
 }
 
 /// Takes a contents of a literal (without quotes) and produces a
@@ -339,3 +351,235 @@ fn byte_from_char(c: char) -> u8 {
 fn is_ascii(x: u32) -> bool {
   x <= 0x7F
 }
+
+/// What kind of JavaScript literal do we unescape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsMode {
+  /// A string literal in sloppy mode code, legacy octal escapes are allowed.
+  Str,
+  /// A string literal in strict mode code.
+  StrictStr,
+  /// The cooked value of a template literal part, between its delimiters.
+  Template,
+}
+
+impl JsMode {
+  fn allows_legacy_octal(self) -> bool {
+    self == JsMode::Str
+  }
+}
+
+/// Takes a contents of a JavaScript string literal (without quotes) or template
+/// part (without delimiters) and produces a sequence of values or errors.
+///
+/// Values are code points, which can be lone surrogates: `\uD83D\uDE00` produces
+/// a high and a low surrogate that make up one character in UTF-16, see
+/// [`encode_utf16`]. Line continuations produce no value.
+pub fn unescape_js_literal<F>(literal_text: &str, mode: JsMode, callback: &mut F)
+where
+  F: FnMut(Range<usize>, Result<u32, EscapeError>),
+{
+  let initial_len = literal_text.len();
+  let mut chars = literal_text.chars();
+  while let Some(first_char) = chars.next() {
+    let start = initial_len - chars.as_str().len() - first_char.len_utf8();
+
+    let result = match first_char {
+      '\\' => match scan_js_escape(&mut chars, mode) {
+        Ok(Some(value)) => Ok(value),
+        // A line continuation.
+        Ok(None) => continue,
+        Err(err) => Err(err),
+      },
+      // A template normalizes both <CR><LF> and <CR> to <LF>.
+      '\r' if mode == JsMode::Template => {
+        eat_lf(&mut chars);
+        Ok('\n' as u32)
+      }
+      '\n' | '\r' if mode != JsMode::Template => Err(EscapeError::UnescapedLineTerminator),
+      c => Ok(c as u32),
+    };
+    let end = initial_len - chars.as_str().len();
+    callback(start..end, result);
+  }
+}
+
+/// Unescapes a whole JavaScript string literal or template part into its UTF-16 value,
+/// passing every error with its range to `on_error`. Returns `None` if there was one.
+pub fn unescape_js_value<F>(literal_text: &str, mode: JsMode, on_error: &mut F) -> Option<Vec<u16>>
+where
+  F: FnMut(Range<usize>, EscapeError),
+{
+  let mut value = Some(Vec::with_capacity(literal_text.len()));
+  unescape_js_literal(literal_text, mode, &mut |range, result| match result {
+    Ok(code) => {
+      if let Some(value) = &mut value {
+        encode_utf16(code, value);
+      }
+    }
+    Err(err) => {
+      value = None;
+      on_error(range, err);
+    }
+  });
+  value
+}
+
+/// Unescapes a whole JavaScript string literal or template part into its UTF-16 string
+/// value, or returns the first error with its range. For a template this is the cooked
+/// value, which tagged templates allow to be invalid.
+pub fn js_string_value(
+  literal_text: &str,
+  mode: JsMode,
+) -> Result<Vec<u16>, (Range<usize>, EscapeError)> {
+  let mut error = None;
+  let value = unescape_js_value(literal_text, mode, &mut |range, err| {
+    if error.is_none() {
+      error = Some((range, err));
+    }
+  });
+  value.ok_or_else(|| error.unwrap())
+}
+
+/// Unescapes a template part (without delimiters) into its cooked and raw UTF-16 values,
+/// passing every error of the cooked value to `on_error`. The cooked value is `None` if
+/// there was one, the raw value as seen by `String.raw` keeps escapes and only normalizes
+/// line terminators.
+pub fn js_template_value<F>(literal_text: &str, on_error: &mut F) -> (Option<Vec<u16>>, Vec<u16>)
+where
+  F: FnMut(Range<usize>, EscapeError),
+{
+  let cooked = unescape_js_value(literal_text, JsMode::Template, on_error);
+  let mut raw = Vec::with_capacity(literal_text.len());
+  let mut chars = literal_text.chars();
+  while let Some(c) = chars.next() {
+    let c = if c == '\r' {
+      eat_lf(&mut chars);
+      '\n'
+    } else {
+      c
+    };
+    encode_utf16(c as u32, &mut raw);
+  }
+  (cooked, raw)
+}
+
+/// Appends the UTF-16 code units of `code` to `value`. Surrogates are kept as they are.
+pub fn encode_utf16(code: u32, value: &mut Vec<u16>) {
+  if code > 0xFFFF {
+    let code = code - 0x10000;
+    value.push(0xD800 + (code >> 10) as u16);
+    value.push(0xDC00 + (code & 0x3FF) as u16);
+  } else {
+    value.push(code as u16);
+  }
+}
+
+fn eat_lf(chars: &mut Chars<'_>) {
+  if chars.clone().next() == Some('\n') {
+    chars.next();
+  }
+}
+
+/// Scans a JavaScript escape, the '\\' is already eaten. Returns `None` for a
+/// line continuation.
+fn scan_js_escape(chars: &mut Chars<'_>, mode: JsMode) -> Result<Option<u32>, EscapeError> {
+  let second_char = chars.next().ok_or(EscapeError::LoneSlash)?;
+
+  let res = match second_char {
+    'b' => '\u{8}',
+    't' => '\t',
+    'n' => '\n',
+    'v' => '\u{B}',
+    'f' => '\u{C}',
+    'r' => '\r',
+
+    // Line continuations, <CR><LF> being a single line terminator.
+    '\r' => {
+      eat_lf(chars);
+      return Ok(None);
+    }
+    '\n' | '\u{2028}' | '\u{2029}' => return Ok(None),
+
+    '0' if !chars.clone().next().map_or(false, |c| c.is_ascii_digit()) => '\0',
+    '0'..='7' => {
+      if !mode.allows_legacy_octal() {
+        return Err(EscapeError::LegacyOctalEscape);
+      }
+      // At most three digits, as long as the value fits in a byte.
+      let mut value = second_char.to_digit(8).unwrap();
+      let max_digits = if second_char <= '3' { 2 } else { 1 };
+      for _ in 0..max_digits {
+        match chars.clone().next().and_then(|c| c.to_digit(8)) {
+          Some(digit) => {
+            chars.next();
+            value = value * 8 + digit;
+          }
+          None => break,
+        }
+      }
+      return Ok(Some(value));
+    }
+    '8' | '9' if !mode.allows_legacy_octal() => return Err(EscapeError::NonOctalDecimalEscape),
+
+    'x' => {
+      // Parse hexadecimal character code, any value up to 0xFF. An invalid character
+      // is not part of the escape.
+      let mut value = 0;
+      for _ in 0..2 {
+        let c = chars.clone().next().ok_or(EscapeError::TooShortHexEscape)?;
+        let digit = c.to_digit(16).ok_or(EscapeError::InvalidCharInHexEscape)?;
+        chars.next();
+        value = value * 16 + digit;
+      }
+      return Ok(Some(value));
+    }
+
+    'u' => return scan_js_unicode_escape(chars).map(Some),
+
+    // Any other character, including '"', '\'' and '\\', escapes itself.
+    c => c,
+  };
+  Ok(Some(res as u32))
+}
+
+/// Scans the rest of a '\uXXXX' or '\u{X...}' escape, the '\\u' is already eaten.
+/// Surrogates are allowed, a pair of escapes can make up a character.
+fn scan_js_unicode_escape(chars: &mut Chars<'_>) -> Result<u32, EscapeError> {
+  if chars.clone().next() != Some('{') {
+    let mut value = 0;
+    for _ in 0..4 {
+      let c = chars.clone().next().ok_or(EscapeError::TooShortUnicodeEscape)?;
+      let digit = c.to_digit(16).ok_or(EscapeError::TooShortUnicodeEscape)?;
+      chars.next();
+      value = value * 16 + digit;
+    }
+    return Ok(value);
+  }
+  chars.next();
+
+  // Any number of digits, as long as the value is in range. An invalid character or
+  // the closing brace of an out of range value is not part of the escape.
+  let mut value: u32 = match chars.clone().next().ok_or(EscapeError::UnclosedUnicodeEscape)? {
+    '}' => return Err(EscapeError::EmptyUnicodeEscape),
+    c => c.to_digit(16).ok_or(EscapeError::InvalidCharInUnicodeEscape)?,
+  };
+  chars.next();
+  loop {
+    match chars.clone().next() {
+      None => return Err(EscapeError::UnclosedUnicodeEscape),
+      Some('}') => break,
+      Some(c) => {
+        let digit = c.to_digit(16).ok_or(EscapeError::InvalidCharInUnicodeEscape)?;
+        chars.next();
+        // Saturate, the value is out of range already.
+        value = value.saturating_mul(16).saturating_add(digit);
+      }
+    }
+  }
+  if value > 0x10FFFF {
+    return Err(EscapeError::OutOfRangeUnicodeEscape);
+  }
+  chars.next();
+  Ok(value)
+}
@@ -271,3 +271,115 @@ fn test_unescape_raw_byte_str() {
     &[(0..4, Err(EscapeError::NonAsciiCharInByteString)), (4..5, Ok(byte_from_char('a')))],
   );
 }
+
+#[test]
+fn test_unescape_js_str_good() {
+  fn check(literal_text: &str, expected: &str) {
+    let expected: Vec<u16> = expected.encode_utf16().collect();
+    assert_eq!(js_string_value(literal_text, JsMode::StrictStr), Ok(expected));
+  }
+
+  check("foo", "foo");
+  check("", "");
+  check(r#"\'\"\\\b\t\n\v\f\r"#, "'\"\\\u{8}\t\n\u{B}\u{C}\r");
+  check(r"\a\c\-\%", "ac-%");
+  check(r"\0", "\0");
+  check(r"\x00\x41\xff\xFF", "\0A\u{FF}\u{FF}");
+  check(r"A\u{41}\u{000000041}\u{10FFFF}", "AAA\u{10FFFF}");
+  check(r"😀 \u{1F600} 😀", "😀 😀 😀");
+  check("a\\\nb\\\r\nc\\\rd\\\u{2028}e\\\u{2029}f", "abcdef");
+  check("a\u{2028}b\u{2029}c", "a\u{2028}b\u{2029}c");
+}
+
+#[test]
+fn test_unescape_js_str_lone_surrogates() {
+  assert_eq!(js_string_value(r"\uD83D", JsMode::Str), Ok(vec![0xD83D]));
+  assert_eq!(js_string_value(r"\uDE00\uD83D", JsMode::Str), Ok(vec![0xDE00, 0xD83D]));
+  assert_eq!(js_string_value(r"\u{D83D}", JsMode::Str), Ok(vec![0xD83D]));
+}
+
+#[test]
+fn test_unescape_js_str_legacy_octal() {
+  fn check(literal_text: &str, expected: &[u16]) {
+    assert_eq!(js_string_value(literal_text, JsMode::Str), Ok(expected.to_vec()));
+  }
+
+  check(r"\1", &[1]);
+  check(r"\12", &[0o12]);
+  check(r"\377", &[0o377]);
+  check(r"\400", &[0o40, '0' as u16]);
+  check(r"\08", &[0, '8' as u16]);
+  check(r"\8\9", &['8' as u16, '9' as u16]);
+
+  for mode in &[JsMode::StrictStr, JsMode::Template] {
+    assert_eq!(js_string_value(r"a\1", *mode), Err((1..3, EscapeError::LegacyOctalEscape)));
+    assert_eq!(js_string_value(r"\08", *mode), Err((0..2, EscapeError::LegacyOctalEscape)));
+    assert_eq!(js_string_value(r"\8", *mode), Err((0..2, EscapeError::NonOctalDecimalEscape)));
+  }
+}
+
+#[test]
+fn test_unescape_js_str_bad() {
+  fn check(literal_text: &str, expected_error: EscapeError) {
+    let actual_result = js_string_value(literal_text, JsMode::Str).map_err(|(_range, err)| err);
+    assert_eq!(actual_result, Err(expected_error));
+  }
+
+  check(r"\", EscapeError::LoneSlash);
+  check("\n", EscapeError::UnescapedLineTerminator);
+  check("\r", EscapeError::UnescapedLineTerminator);
+
+  check(r"\x", EscapeError::TooShortHexEscape);
+  check(r"\x0", EscapeError::TooShortHexEscape);
+  check(r"\xx0", EscapeError::InvalidCharInHexEscape);
+
+  check(r"\u", EscapeError::TooShortUnicodeEscape);
+  check(r"\u12", EscapeError::TooShortUnicodeEscape);
+  check(r"\u12x4", EscapeError::TooShortUnicodeEscape);
+  check(r"\u{", EscapeError::UnclosedUnicodeEscape);
+  check(r"\u{41", EscapeError::UnclosedUnicodeEscape);
+  check(r"\u{}", EscapeError::EmptyUnicodeEscape);
+  check(r"\u{4_1}", EscapeError::InvalidCharInUnicodeEscape);
+  check(r"\u{110000}", EscapeError::OutOfRangeUnicodeEscape);
+  check(r"\u{FFFFFFFFFF}", EscapeError::OutOfRangeUnicodeEscape);
+}
+
+#[test]
+fn test_unescape_js_error_ranges() {
+  fn check(literal_text: &str, expected: &[(Range<usize>, EscapeError)]) {
+    let mut errors = Vec::new();
+    let value = unescape_js_value(literal_text, JsMode::Template, &mut |range, err| {
+      errors.push((range, err));
+    });
+    assert_eq!(value, None);
+    assert_eq!(errors, expected);
+  }
+
+  // Invalid characters and the closing brace of an out of range value are left out.
+  check(
+    r"\x4z\u{12z}",
+    &[(0..3, EscapeError::InvalidCharInHexEscape), (4..9, EscapeError::InvalidCharInUnicodeEscape)],
+  );
+  check(
+    r"\u{}\u{110000}",
+    &[(0..3, EscapeError::EmptyUnicodeEscape), (4..13, EscapeError::OutOfRangeUnicodeEscape)],
+  );
+}
+
+#[test]
+fn test_unescape_js_template() {
+  fn check(literal_text: &str, cooked: Option<&str>, raw: &str) {
+    let cooked = cooked.map(|cooked| cooked.encode_utf16().collect::<Vec<_>>());
+    let mut errors = 0;
+    let value = js_template_value(literal_text, &mut |_range, _err| errors += 1);
+    assert_eq!(value, (cooked.clone(), raw.encode_utf16().collect::<Vec<_>>()));
+    assert_eq!(errors > 0, cooked.is_none());
+  }
+
+  check(r"a\tb", Some("a\tb"), r"a\tb");
+  check("a\r\nb\rc\nd", Some("a\nb\nc\nd"), "a\nb\nc\nd");
+  check("a\\\r\nb", Some("ab"), "a\\\nb");
+  check(r"\u{1F600}", Some("😀"), r"\u{1F600}");
+  check(r"\unicode", None, r"\unicode");
+  check(r"\01", None, r"\01");
+}
@@ -1,4 +1,7 @@
+/// The kinds of lists the parser knows, to recover from errors in them, see
+/// [crate::Parser::parse_list].
 #[rustfmt::skip]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum ParsingContext {
   SourceElements,            // Elements in source file
   BlockStatements,           // Statements in block
@@ -27,6 +30,18 @@ pub enum ParsingContext {
   Count                      // Number of parsing contexts
 }
 
+/// The grammar parameters in effect while parsing, e.g. whether `in` may be a binary
+/// operator. Saved and restored around nested constructs by [crate::Parser].
+pub mod ContextFlags {
+  pub const None: u8 = 0;
+  pub const DisallowIn: u8 = 1 << 0; // `in` is not an operator, e.g. in a `for` initializer
+  pub const Yield: u8 = 1 << 1; // `yield` is an operator, i.e. in a generator
+  pub const Await: u8 = 1 << 2; // `await` is an operator, e.g. in an async function
+  pub const DisallowConditionalTypes: u8 = 1 << 3; // e.g. in the extends type of a conditional type
+  pub const Decorator: u8 = 1 << 4; // `[` is not an element access, as in `@a [b]() {}`
+  pub const Ambient: u8 = 1 << 5; // after `declare` or in a `.d.ts` file
+}
+
 pub mod TokenFlags {
   pub const None: u16 = 0;
   pub const PrecedingLineBreak: u16 = 1 << 0;
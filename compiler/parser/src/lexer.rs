@@ -1,69 +1,50 @@
 mod cursor;
+mod entities;
 
-use tscore_ast::token::{self, BinOp, BinOpEq, Ident, Lit, LitKind, Token, TokenKind, Trivia};
+use tscore_ast::token::{self, BinOpToken, Lit, LitKind, Token, TokenKind, Trivia};
 use tscore_ast::tokenstream::{Spacing, TokenStream};
 use tscore_common::{LanguageVariant, ScriptTarget};
+pub use tscore_lexer::is_whitespace;
+use tscore_lexer::unescape::{
+  encode_utf16, js_template_value, unescape_js_literal, EscapeError, JsMode,
+};
+use tscore_lexer::unicode_escape;
 use tscore_span::{BytePos, Pos, Span, Symbol};
 
 use super::context::TokenFlags;
 
 use cursor::Cursor;
 
+use std::ops::Range;
 use tracing::debug;
 
+#[cfg(test)]
+mod tests;
+
 pub fn strip_shebang(input: &str) -> Option<usize> {
   // Shebang must start with `#!` literally, without any preceding whitespace.
   // For simplicity we consider any line starting with `#!` a shebang,
   // regardless of restrictions put on shebangs by specific platforms.
-  if let Some(input_tail) = input.strip_prefix("#!") {
-    // Ok, this is a shebang but if the next non-whitespace token is `[`,
-    // then it may be valid Rust code, so consider it Rust code.
-    let ch = input.char_indices().find(|(_, c)| is_line_break(*c))?.0;
-    return Some(ch);
+  if input.starts_with("#!") {
+    return Some(input.find(is_line_break).unwrap_or(input.len()));
   }
   None
 }
 
-/// True if `c` is considered a whitespace according to ECMAScript language definition.
-/// See [ECMA262](https://tc39.es/ecma262/#sec-white-space)
-/// for definitions of these classes.
-pub fn is_whitespace(c: char) -> bool {
-  // This is Pattern_White_Space.
-  //
-  // Note that this set is stable (ie, it doesn't change with different
-  // Unicode versions), so it's ok to just hard-code the values.
+/// The length of a merge conflict marker, e.g. `<<<<<<<`.
+const CONFLICT_MARKER_LEN: usize = 7;
 
-  matches!(
-    c,
-    // Usual ASCII suspects
-    '\u{0009}'       // <TAB>    \t
-        | '\u{000B}' // <VT>     Vertical Tab or Line Tabulation
-        | '\u{000C}' // <FF>     Form Feed
-        | '\u{0020}' // <SP>     " "
-        | '\u{00A0}' // <NBSP>   No-Break space
-        | '\u{FEFF}' // <ZWNBSP> Zero Width No-Nreak Space
-
-        // other Unicode Space Separators
-        // see [Space]https://en.wikipedia.org/wiki/Space_%28punctuation%29#Spaces_in_Unicode
-        | '\u{1680}' // Ogham space mark
-        | '\u{180E}' // Mongolian Vowel Separator
-        | '\u{2002}' // En Space
-        | '\u{2003}' // Em space
-        | '\u{2004}' // Three-Per-Em Space
-        | '\u{2005}' // Four-Per-Em Space
-        | '\u{2006}' // Six-Per-Em Space
-        | '\u{2007}' // Figure Space
-        | '\u{2008}' // Punctuation Space
-        | '\u{2009}' // Thin Space
-        | '\u{200A}' // Hair space
-        | '\u{200B}' // Zero Width Space
-        | '\u{200C}' // Zero Width Non Joiner
-        | '\u{200D}' // Zero Width Joiner
-        | '\u{202F}' // Narrow No-Break Space
-        | '\u{205F}' // Medium Mathematical Space
-        | '\u{2060}' // Word Joiner
-        | '\u{3000}' // Ideographic Space
-  )
+/// Whether `text` starts with a merge conflict marker, i.e. seven `<`, `|`, `=` or `>`.
+/// All but `=======` are followed by a space, like `<<<<<<< HEAD`.
+/// The marker must also be at the start of a line, which is up to the caller.
+fn is_conflict_marker(text: &str) -> bool {
+  let ch = match text.as_bytes().first() {
+    Some(&ch) if matches!(ch, b'<' | b'|' | b'=' | b'>') => ch,
+    _ => return false,
+  };
+  text.len() >= CONFLICT_MARKER_LEN
+    && text.as_bytes()[..CONFLICT_MARKER_LEN].iter().all(|&b| b == ch)
+    && (ch == b'=' || text[CONFLICT_MARKER_LEN..].starts_with(' '))
 }
 
 /// True if `c` is considered a line terminator according to ECMAScript language definition.
@@ -91,6 +72,56 @@ pub static UNICODE_ESNEXT_IDENTIFIER_START: [u32; 1218]  = [65, 90, 97, 122, 170
 #[rustfmt::skip]
 pub static UNICODE_ESNEXT_IDENTIFIER_PART: [u32; 1426] = [48, 57, 65, 90, 95, 95, 97, 122, 170, 170, 181, 181, 183, 183, 186, 186, 192, 214, 216, 246, 248, 705, 710, 721, 736, 740, 748, 748, 750, 750, 768, 884, 886, 887, 890, 893, 895, 895, 902, 906, 908, 908, 910, 929, 931, 1013, 1015, 1153, 1155, 1159, 1162, 1327, 1329, 1366, 1369, 1369, 1376, 1416, 1425, 1469, 1471, 1471, 1473, 1474, 1476, 1477, 1479, 1479, 1488, 1514, 1519, 1522, 1552, 1562, 1568, 1641, 1646, 1747, 1749, 1756, 1759, 1768, 1770, 1788, 1791, 1791, 1808, 1866, 1869, 1969, 1984, 2037, 2042, 2042, 2045, 2045, 2048, 2093, 2112, 2139, 2144, 2154, 2208, 2228, 2230, 2237, 2259, 2273, 2275, 2403, 2406, 2415, 2417, 2435, 2437, 2444, 2447, 2448, 2451, 2472, 2474, 2480, 2482, 2482, 2486, 2489, 2492, 2500, 2503, 2504, 2507, 2510, 2519, 2519, 2524, 2525, 2527, 2531, 2534, 2545, 2556, 2556, 2558, 2558, 2561, 2563, 2565, 2570, 2575, 2576, 2579, 2600, 2602, 2608, 2610, 2611, 2613, 2614, 2616, 2617, 2620, 2620, 2622, 2626, 2631, 2632, 2635, 2637, 2641, 2641, 2649, 2652, 2654, 2654, 2662, 2677, 2689, 2691, 2693, 2701, 2703, 2705, 2707, 2728, 2730, 2736, 2738, 2739, 2741, 2745, 2748, 2757, 2759, 2761, 2763, 2765, 2768, 2768, 2784, 2787, 2790, 2799, 2809, 2815, 2817, 2819, 2821, 2828, 2831, 2832, 2835, 2856, 2858, 2864, 2866, 2867, 2869, 2873, 2876, 2884, 2887, 2888, 2891, 2893, 2902, 2903, 2908, 2909, 2911, 2915, 2918, 2927, 2929, 2929, 2946, 2947, 2949, 2954, 2958, 2960, 2962, 2965, 2969, 2970, 2972, 2972, 2974, 2975, 2979, 2980, 2984, 2986, 2990, 3001, 3006, 3010, 3014, 3016, 3018, 3021, 3024, 3024, 3031, 3031, 3046, 3055, 3072, 3084, 3086, 3088, 3090, 3112, 3114, 3129, 3133, 3140, 3142, 3144, 3146, 3149, 3157, 3158, 3160, 3162, 3168, 3171, 3174, 3183, 3200, 3203, 3205, 3212, 3214, 3216, 3218, 3240, 3242, 3251, 3253, 3257, 3260, 3268, 3270, 3272, 3274, 3277, 3285, 3286, 3294, 3294, 3296, 3299, 3302, 3311, 3313, 3314, 3328, 3331, 3333, 3340, 3342, 3344, 3346, 3396, 3398, 3400, 3402, 3406, 3412, 3415, 3423, 3427, 3430, 3439, 3450, 3455, 3458, 3459, 3461, 3478, 3482, 3505, 3507, 3515, 3517, 3517, 3520, 3526, 3530, 3530, 3535, 3540, 3542, 3542, 3544, 3551, 3558, 3567, 3570, 3571, 3585, 3642, 3648, 3662, 3664, 3673, 3713, 3714, 3716, 3716, 3718, 3722, 3724, 3747, 3749, 3749, 3751, 3773, 3776, 3780, 3782, 3782, 3784, 3789, 3792, 3801, 3804, 3807, 3840, 3840, 3864, 3865, 3872, 3881, 3893, 3893, 3895, 3895, 3897, 3897, 3902, 3911, 3913, 3948, 3953, 3972, 3974, 3991, 3993, 4028, 4038, 4038, 4096, 4169, 4176, 4253, 4256, 4293, 4295, 4295, 4301, 4301, 4304, 4346, 4348, 4680, 4682, 4685, 4688, 4694, 4696, 4696, 4698, 4701, 4704, 4744, 4746, 4749, 4752, 4784, 4786, 4789, 4792, 4798, 4800, 4800, 4802, 4805, 4808, 4822, 4824, 4880, 4882, 4885, 4888, 4954, 4957, 4959, 4969, 4977, 4992, 5007, 5024, 5109, 5112, 5117, 5121, 5740, 5743, 5759, 5761, 5786, 5792, 5866, 5870, 5880, 5888, 5900, 5902, 5908, 5920, 5940, 5952, 5971, 5984, 5996, 5998, 6000, 6002, 6003, 6016, 6099, 6103, 6103, 6108, 6109, 6112, 6121, 6155, 6157, 6160, 6169, 6176, 6264, 6272, 6314, 6320, 6389, 6400, 6430, 6432, 6443, 6448, 6459, 6470, 6509, 6512, 6516, 6528, 6571, 6576, 6601, 6608, 6618, 6656, 6683, 6688, 6750, 6752, 6780, 6783, 6793, 6800, 6809, 6823, 6823, 6832, 6845, 6912, 6987, 6992, 7001, 7019, 7027, 7040, 7155, 7168, 7223, 7232, 7241, 7245, 7293, 7296, 7304, 7312, 7354, 7357, 7359, 7376, 7378, 7380, 7418, 7424, 7673, 7675, 7957, 7960, 7965, 7968, 8005, 8008, 8013, 8016, 8023, 8025, 8025, 8027, 8027, 8029, 8029, 8031, 8061, 8064, 8116, 8118, 8124, 8126, 8126, 8130, 8132, 8134, 8140, 8144, 8147, 8150, 8155, 8160, 8172, 8178, 8180, 8182, 8188, 8255, 8256, 8276, 8276, 8305, 8305, 8319, 8319, 8336, 8348, 8400, 8412, 8417, 8417, 8421, 8432, 8450, 8450, 8455, 8455, 8458, 8467, 8469, 8469, 8472, 8477, 8484, 8484, 8486, 8486, 8488, 8488, 8490, 8505, 8508, 8511, 8517, 8521, 8526, 8526, 8544, 8584, 11264, 11310, 11312, 11358, 11360, 11492, 11499, 11507, 11520, 11557, 11559, 11559, 11565, 11565, 11568, 11623, 11631, 11631, 11647, 11670, 11680, 11686, 11688, 11694, 11696, 11702, 11704, 11710, 11712, 11718, 11720, 11726, 11728, 11734, 11736, 11742, 11744, 11775, 12293, 12295, 12321, 12335, 12337, 12341, 12344, 12348, 12353, 12438, 12441, 12447, 12449, 12538, 12540, 12543, 12549, 12591, 12593, 12686, 12704, 12730, 12784, 12799, 13312, 19893, 19968, 40943, 40960, 42124, 42192, 42237, 42240, 42508, 42512, 42539, 42560, 42607, 42612, 42621, 42623, 42737, 42775, 42783, 42786, 42888, 42891, 42943, 42946, 42950, 42999, 43047, 43072, 43123, 43136, 43205, 43216, 43225, 43232, 43255, 43259, 43259, 43261, 43309, 43312, 43347, 43360, 43388, 43392, 43456, 43471, 43481, 43488, 43518, 43520, 43574, 43584, 43597, 43600, 43609, 43616, 43638, 43642, 43714, 43739, 43741, 43744, 43759, 43762, 43766, 43777, 43782, 43785, 43790, 43793, 43798, 43808, 43814, 43816, 43822, 43824, 43866, 43868, 43879, 43888, 44010, 44012, 44013, 44016, 44025, 44032, 55203, 55216, 55238, 55243, 55291, 63744, 64109, 64112, 64217, 64256, 64262, 64275, 64279, 64285, 64296, 64298, 64310, 64312, 64316, 64318, 64318, 64320, 64321, 64323, 64324, 64326, 64433, 64467, 64829, 64848, 64911, 64914, 64967, 65008, 65019, 65024, 65039, 65056, 65071, 65075, 65076, 65101, 65103, 65136, 65140, 65142, 65276, 65296, 65305, 65313, 65338, 65343, 65343, 65345, 65370, 65382, 65470, 65474, 65479, 65482, 65487, 65490, 65495, 65498, 65500, 65536, 65547, 65549, 65574, 65576, 65594, 65596, 65597, 65599, 65613, 65616, 65629, 65664, 65786, 65856, 65908, 66045, 66045, 66176, 66204, 66208, 66256, 66272, 66272, 66304, 66335, 66349, 66378, 66384, 66426, 66432, 66461, 66464, 66499, 66504, 66511, 66513, 66517, 66560, 66717, 66720, 66729, 66736, 66771, 66776, 66811, 66816, 66855, 66864, 66915, 67072, 67382, 67392, 67413, 67424, 67431, 67584, 67589, 67592, 67592, 67594, 67637, 67639, 67640, 67644, 67644, 67647, 67669, 67680, 67702, 67712, 67742, 67808, 67826, 67828, 67829, 67840, 67861, 67872, 67897, 67968, 68023, 68030, 68031, 68096, 68099, 68101, 68102, 68108, 68115, 68117, 68119, 68121, 68149, 68152, 68154, 68159, 68159, 68192, 68220, 68224, 68252, 68288, 68295, 68297, 68326, 68352, 68405, 68416, 68437, 68448, 68466, 68480, 68497, 68608, 68680, 68736, 68786, 68800, 68850, 68864, 68903, 68912, 68921, 69376, 69404, 69415, 69415, 69424, 69456, 69600, 69622, 69632, 69702, 69734, 69743, 69759, 69818, 69840, 69864, 69872, 69881, 69888, 69940, 69942, 69951, 69956, 69958, 69968, 70003, 70006, 70006, 70016, 70084, 70089, 70092, 70096, 70106, 70108, 70108, 70144, 70161, 70163, 70199, 70206, 70206, 70272, 70278, 70280, 70280, 70282, 70285, 70287, 70301, 70303, 70312, 70320, 70378, 70384, 70393, 70400, 70403, 70405, 70412, 70415, 70416, 70419, 70440, 70442, 70448, 70450, 70451, 70453, 70457, 70459, 70468, 70471, 70472, 70475, 70477, 70480, 70480, 70487, 70487, 70493, 70499, 70502, 70508, 70512, 70516, 70656, 70730, 70736, 70745, 70750, 70751, 70784, 70853, 70855, 70855, 70864, 70873, 71040, 71093, 71096, 71104, 71128, 71133, 71168, 71232, 71236, 71236, 71248, 71257, 71296, 71352, 71360, 71369, 71424, 71450, 71453, 71467, 71472, 71481, 71680, 71738, 71840, 71913, 71935, 71935, 72096, 72103, 72106, 72151, 72154, 72161, 72163, 72164, 72192, 72254, 72263, 72263, 72272, 72345, 72349, 72349, 72384, 72440, 72704, 72712, 72714, 72758, 72760, 72768, 72784, 72793, 72818, 72847, 72850, 72871, 72873, 72886, 72960, 72966, 72968, 72969, 72971, 73014, 73018, 73018, 73020, 73021, 73023, 73031, 73040, 73049, 73056, 73061, 73063, 73064, 73066, 73102, 73104, 73105, 73107, 73112, 73120, 73129, 73440, 73462, 73728, 74649, 74752, 74862, 74880, 75075, 77824, 78894, 82944, 83526, 92160, 92728, 92736, 92766, 92768, 92777, 92880, 92909, 92912, 92916, 92928, 92982, 92992, 92995, 93008, 93017, 93027, 93047, 93053, 93071, 93760, 93823, 93952, 94026, 94031, 94087, 94095, 94111, 94176, 94177, 94179, 94179, 94208, 100343, 100352, 101106, 110592, 110878, 110928, 110930, 110948, 110951, 110960, 111355, 113664, 113770, 113776, 113788, 113792, 113800, 113808, 113817, 113821, 113822, 119141, 119145, 119149, 119154, 119163, 119170, 119173, 119179, 119210, 119213, 119362, 119364, 119808, 119892, 119894, 119964, 119966, 119967, 119970, 119970, 119973, 119974, 119977, 119980, 119982, 119993, 119995, 119995, 119997, 120003, 120005, 120069, 120071, 120074, 120077, 120084, 120086, 120092, 120094, 120121, 120123, 120126, 120128, 120132, 120134, 120134, 120138, 120144, 120146, 120485, 120488, 120512, 120514, 120538, 120540, 120570, 120572, 120596, 120598, 120628, 120630, 120654, 120656, 120686, 120688, 120712, 120714, 120744, 120746, 120770, 120772, 120779, 120782, 120831, 121344, 121398, 121403, 121452, 121461, 121461, 121476, 121476, 121499, 121503, 121505, 121519, 122880, 122886, 122888, 122904, 122907, 122913, 122915, 122916, 122918, 122922, 123136, 123180, 123184, 123197, 123200, 123209, 123214, 123214, 123584, 123641, 124928, 125124, 125136, 125142, 125184, 125259, 125264, 125273, 126464, 126467, 126469, 126495, 126497, 126498, 126500, 126500, 126503, 126503, 126505, 126514, 126516, 126519, 126521, 126521, 126523, 126523, 126530, 126530, 126535, 126535, 126537, 126537, 126539, 126539, 126541, 126543, 126545, 126546, 126548, 126548, 126551, 126551, 126553, 126553, 126555, 126555, 126557, 126557, 126559, 126559, 126561, 126562, 126564, 126564, 126567, 126570, 126572, 126578, 126580, 126583, 126585, 126588, 126590, 126590, 126592, 126601, 126603, 126619, 126625, 126627, 126629, 126633, 126635, 126651, 131072, 173782, 173824, 177972, 177984, 178205, 178208, 183969, 183984, 191456, 194560, 195101, 917760, 917999];
 
+///
+/// As per ECMAScript Language Specification 3th Edition, Section 7.6: Identifiers
+/// IdentifierStart ::
+///     Can contain Unicode 3.0.0 categories:
+///     Uppercase letter (Lu),
+///     Lowercase letter (Ll),
+///     Titlecase letter (Lt),
+///     Modifier letter (Lm),
+///     Other letter (Lo), or
+///     Letter number (Nl).
+/// IdentifierPart :: =
+///     Can contain IdentifierStart + Unicode 3.0.0 categories:
+///     Non-spacing mark (Mn),
+///     Combining spacing mark (Mc),
+///     Decimal number (Nd), or
+///     Connector punctuation (Pc).
+///
+/// Codepoint ranges for ES3 Identifiers are extracted from the Unicode 3.0.0 specification at:
+/// http://www.unicode.org/Public/3.0-Update/UnicodeData-3.0.0.txt
+///
+#[rustfmt::skip]
+pub static UNICODE_ES3_IDENTIFIER_START: [u32; 512] = [170, 170, 181, 181, 186, 186, 192, 214, 216, 246, 248, 543, 546, 563, 592, 685, 688, 696, 699, 705, 720, 721, 736, 740, 750, 750, 890, 890, 902, 902, 904, 906, 908, 908, 910, 929, 931, 974, 976, 983, 986, 1011, 1024, 1153, 1164, 1220, 1223, 1224, 1227, 1228, 1232, 1269, 1272, 1273, 1329, 1366, 1369, 1369, 1377, 1415, 1488, 1514, 1520, 1522, 1569, 1594, 1600, 1610, 1649, 1747, 1749, 1749, 1765, 1766, 1786, 1788, 1808, 1808, 1810, 1836, 1920, 1957, 2309, 2361, 2365, 2365, 2384, 2384, 2392, 2401, 2437, 2444, 2447, 2448, 2451, 2472, 2474, 2480, 2482, 2482, 2486, 2489, 2524, 2525, 2527, 2529, 2544, 2545, 2565, 2570, 2575, 2576, 2579, 2600, 2602, 2608, 2610, 2611, 2613, 2614, 2616, 2617, 2649, 2652, 2654, 2654, 2674, 2676, 2693, 2699, 2701, 2701, 2703, 2705, 2707, 2728, 2730, 2736, 2738, 2739, 2741, 2745, 2749, 2749, 2768, 2768, 2784, 2784, 2821, 2828, 2831, 2832, 2835, 2856, 2858, 2864, 2866, 2867, 2870, 2873, 2877, 2877, 2908, 2909, 2911, 2913, 2949, 2954, 2958, 2960, 2962, 2965, 2969, 2970, 2972, 2972, 2974, 2975, 2979, 2980, 2984, 2986, 2990, 2997, 2999, 3001, 3077, 3084, 3086, 3088, 3090, 3112, 3114, 3123, 3125, 3129, 3168, 3169, 3205, 3212, 3214, 3216, 3218, 3240, 3242, 3251, 3253, 3257, 3294, 3294, 3296, 3297, 3333, 3340, 3342, 3344, 3346, 3368, 3370, 3385, 3424, 3425, 3461, 3478, 3482, 3505, 3507, 3515, 3517, 3517, 3520, 3526, 3585, 3632, 3634, 3635, 3648, 3654, 3713, 3714, 3716, 3716, 3719, 3720, 3722, 3722, 3725, 3725, 3732, 3735, 3737, 3743, 3745, 3747, 3749, 3749, 3751, 3751, 3754, 3755, 3757, 3760, 3762, 3763, 3773, 3773, 3776, 3780, 3782, 3782, 3804, 3805, 3840, 3840, 3904, 3911, 3913, 3946, 3976, 3979, 4096, 4129, 4131, 4135, 4137, 4138, 4176, 4181, 4256, 4293, 4304, 4342, 4352, 4441, 4447, 4514, 4520, 4601, 4608, 4614, 4616, 4678, 4680, 4680, 4682, 4685, 4688, 4694, 4696, 4696, 4698, 4701, 4704, 4742, 4744, 4744, 4746, 4749, 4752, 4782, 4784, 4784, 4786, 4789, 4792, 4798, 4800, 4800, 4802, 4805, 4808, 4814, 4816, 4822, 4824, 4846, 4848, 4878, 4880, 4880, 4882, 4885, 4888, 4894, 4896, 4934, 4936, 4954, 5024, 5108, 5121, 5740, 5743, 5750, 5761, 5786, 5792, 5866, 6016, 6067, 6176, 6263, 6272, 6312, 7680, 7835, 7840, 7929, 7936, 7957, 7960, 7965, 7968, 8005, 8008, 8013, 8016, 8023, 8025, 8025, 8027, 8027, 8029, 8029, 8031, 8061, 8064, 8116, 8118, 8124, 8126, 8126, 8130, 8132, 8134, 8140, 8144, 8147, 8150, 8155, 8160, 8172, 8178, 8180, 8182, 8188, 8319, 8319, 8450, 8450, 8455, 8455, 8458, 8467, 8469, 8469, 8473, 8477, 8484, 8484, 8486, 8486, 8488, 8488, 8490, 8493, 8495, 8497, 8499, 8505, 8544, 8579, 12293, 12295, 12321, 12329, 12337, 12341, 12344, 12346, 12353, 12436, 12445, 12446, 12449, 12538, 12540, 12542, 12549, 12588, 12593, 12686, 12704, 12727, 13312, 19893, 19968, 40869, 40960, 42124, 44032, 55203, 63744, 64045, 64256, 64262, 64275, 64279, 64285, 64285, 64287, 64296, 64298, 64310, 64312, 64316, 64318, 64318, 64320, 64321, 64323, 64324, 64326, 64433, 64467, 64829, 64848, 64911, 64914, 64967, 65008, 65019, 65136, 65138, 65140, 65140, 65142, 65276, 65313, 65338, 65345, 65370, 65382, 65470, 65474, 65479, 65482, 65487, 65490, 65495, 65498, 65500];
+#[rustfmt::skip]
+pub static UNICODE_ES3_IDENTIFIER_PART: [u32; 684] = [170, 170, 181, 181, 186, 186, 192, 214, 216, 246, 248, 543, 546, 563, 592, 685, 688, 696, 699, 705, 720, 721, 736, 740, 750, 750, 768, 846, 864, 866, 890, 890, 902, 902, 904, 906, 908, 908, 910, 929, 931, 974, 976, 983, 986, 1011, 1024, 1153, 1155, 1158, 1164, 1220, 1223, 1224, 1227, 1228, 1232, 1269, 1272, 1273, 1329, 1366, 1369, 1369, 1377, 1415, 1425, 1441, 1443, 1465, 1467, 1469, 1471, 1471, 1473, 1474, 1476, 1476, 1488, 1514, 1520, 1522, 1569, 1594, 1600, 1621, 1632, 1641, 1648, 1747, 1749, 1756, 1759, 1768, 1770, 1773, 1776, 1788, 1808, 1836, 1840, 1866, 1920, 1968, 2305, 2307, 2309, 2361, 2364, 2381, 2384, 2388, 2392, 2403, 2406, 2415, 2433, 2435, 2437, 2444, 2447, 2448, 2451, 2472, 2474, 2480, 2482, 2482, 2486, 2489, 2492, 2492, 2494, 2500, 2503, 2504, 2507, 2509, 2519, 2519, 2524, 2525, 2527, 2531, 2534, 2545, 2562, 2562, 2565, 2570, 2575, 2576, 2579, 2600, 2602, 2608, 2610, 2611, 2613, 2614, 2616, 2617, 2620, 2620, 2622, 2626, 2631, 2632, 2635, 2637, 2649, 2652, 2654, 2654, 2662, 2676, 2689, 2691, 2693, 2699, 2701, 2701, 2703, 2705, 2707, 2728, 2730, 2736, 2738, 2739, 2741, 2745, 2748, 2757, 2759, 2761, 2763, 2765, 2768, 2768, 2784, 2784, 2790, 2799, 2817, 2819, 2821, 2828, 2831, 2832, 2835, 2856, 2858, 2864, 2866, 2867, 2870, 2873, 2876, 2883, 2887, 2888, 2891, 2893, 2902, 2903, 2908, 2909, 2911, 2913, 2918, 2927, 2946, 2947, 2949, 2954, 2958, 2960, 2962, 2965, 2969, 2970, 2972, 2972, 2974, 2975, 2979, 2980, 2984, 2986, 2990, 2997, 2999, 3001, 3006, 3010, 3014, 3016, 3018, 3021, 3031, 3031, 3047, 3055, 3073, 3075, 3077, 3084, 3086, 3088, 3090, 3112, 3114, 3123, 3125, 3129, 3134, 3140, 3142, 3144, 3146, 3149, 3157, 3158, 3168, 3169, 3174, 3183, 3202, 3203, 3205, 3212, 3214, 3216, 3218, 3240, 3242, 3251, 3253, 3257, 3262, 3268, 3270, 3272, 3274, 3277, 3285, 3286, 3294, 3294, 3296, 3297, 3302, 3311, 3330, 3331, 3333, 3340, 3342, 3344, 3346, 3368, 3370, 3385, 3390, 3395, 3398, 3400, 3402, 3405, 3415, 3415, 3424, 3425, 3430, 3439, 3458, 3459, 3461, 3478, 3482, 3505, 3507, 3515, 3517, 3517, 3520, 3526, 3530, 3530, 3535, 3540, 3542, 3542, 3544, 3551, 3570, 3571, 3585, 3642, 3648, 3662, 3664, 3673, 3713, 3714, 3716, 3716, 3719, 3720, 3722, 3722, 3725, 3725, 3732, 3735, 3737, 3743, 3745, 3747, 3749, 3749, 3751, 3751, 3754, 3755, 3757, 3769, 3771, 3773, 3776, 3780, 3782, 3782, 3784, 3789, 3792, 3801, 3804, 3805, 3840, 3840, 3864, 3865, 3872, 3881, 3893, 3893, 3895, 3895, 3897, 3897, 3902, 3911, 3913, 3946, 3953, 3972, 3974, 3979, 3984, 3991, 3993, 4028, 4038, 4038, 4096, 4129, 4131, 4135, 4137, 4138, 4140, 4146, 4150, 4153, 4160, 4169, 4176, 4185, 4256, 4293, 4304, 4342, 4352, 4441, 4447, 4514, 4520, 4601, 4608, 4614, 4616, 4678, 4680, 4680, 4682, 4685, 4688, 4694, 4696, 4696, 4698, 4701, 4704, 4742, 4744, 4744, 4746, 4749, 4752, 4782, 4784, 4784, 4786, 4789, 4792, 4798, 4800, 4800, 4802, 4805, 4808, 4814, 4816, 4822, 4824, 4846, 4848, 4878, 4880, 4880, 4882, 4885, 4888, 4894, 4896, 4934, 4936, 4954, 4969, 4977, 5024, 5108, 5121, 5740, 5743, 5750, 5761, 5786, 5792, 5866, 6016, 6099, 6112, 6121, 6160, 6169, 6176, 6263, 6272, 6313, 7680, 7835, 7840, 7929, 7936, 7957, 7960, 7965, 7968, 8005, 8008, 8013, 8016, 8023, 8025, 8025, 8027, 8027, 8029, 8029, 8031, 8061, 8064, 8116, 8118, 8124, 8126, 8126, 8130, 8132, 8134, 8140, 8144, 8147, 8150, 8155, 8160, 8172, 8178, 8180, 8182, 8188, 8255, 8256, 8319, 8319, 8400, 8412, 8417, 8417, 8450, 8450, 8455, 8455, 8458, 8467, 8469, 8469, 8473, 8477, 8484, 8484, 8486, 8486, 8488, 8488, 8490, 8493, 8495, 8497, 8499, 8505, 8544, 8579, 12293, 12295, 12321, 12335, 12337, 12341, 12344, 12346, 12353, 12436, 12441, 12442, 12445, 12446, 12449, 12542, 12549, 12588, 12593, 12686, 12704, 12727, 13312, 19893, 19968, 40869, 40960, 42124, 44032, 55203, 63744, 64045, 64256, 64262, 64275, 64279, 64285, 64296, 64298, 64310, 64312, 64316, 64318, 64318, 64320, 64321, 64323, 64324, 64326, 64433, 64467, 64829, 64848, 64911, 64914, 64967, 65008, 65019, 65056, 65059, 65075, 65076, 65101, 65103, 65136, 65138, 65140, 65140, 65142, 65276, 65296, 65305, 65313, 65338, 65343, 65343, 65345, 65370, 65381, 65470, 65474, 65479, 65482, 65487, 65490, 65495, 65498, 65500];
+
+///
+/// As per ECMAScript Language Specification 5th Edition, Section 7.6: ISyntaxToken Names and Identifiers
+/// IdentifierStart ::
+///     Can contain Unicode 6.2 categories:
+///     Uppercase letter (Lu),
+///     Lowercase letter (Ll),
+///     Titlecase letter (Lt),
+///     Modifier letter (Lm),
+///     Other letter (Lo), or
+///     Letter number (Nl).
+/// IdentifierPart :: =
+///     Can contain IdentifierStart + Unicode 6.2 categories:
+///     Non-spacing mark (Mn),
+///     Combining spacing mark (Mc),
+///     Decimal number (Nd), or
+///     Connector punctuation (Pc).
+///
+/// Codepoint ranges for ES5 Identifiers are extracted from the Unicode 6.2 specification at:
+/// http://www.unicode.org/Public/6.2.0/ucd/UnicodeData.txt
+///
+#[rustfmt::skip]
+pub static UNICODE_ES5_IDENTIFIER_START: [u32; 740] = [170, 170, 181, 181, 186, 186, 192, 214, 216, 246, 248, 705, 710, 721, 736, 740, 748, 748, 750, 750, 880, 884, 886, 887, 890, 893, 902, 902, 904, 906, 908, 908, 910, 929, 931, 1013, 1015, 1153, 1162, 1319, 1329, 1366, 1369, 1369, 1377, 1415, 1488, 1514, 1520, 1522, 1568, 1610, 1646, 1647, 1649, 1747, 1749, 1749, 1765, 1766, 1774, 1775, 1786, 1788, 1791, 1791, 1808, 1808, 1810, 1839, 1869, 1957, 1969, 1969, 1994, 2026, 2036, 2037, 2042, 2042, 2048, 2069, 2074, 2074, 2084, 2084, 2088, 2088, 2112, 2136, 2208, 2208, 2210, 2220, 2308, 2361, 2365, 2365, 2384, 2384, 2392, 2401, 2417, 2423, 2425, 2431, 2437, 2444, 2447, 2448, 2451, 2472, 2474, 2480, 2482, 2482, 2486, 2489, 2493, 2493, 2510, 2510, 2524, 2525, 2527, 2529, 2544, 2545, 2565, 2570, 2575, 2576, 2579, 2600, 2602, 2608, 2610, 2611, 2613, 2614, 2616, 2617, 2649, 2652, 2654, 2654, 2674, 2676, 2693, 2701, 2703, 2705, 2707, 2728, 2730, 2736, 2738, 2739, 2741, 2745, 2749, 2749, 2768, 2768, 2784, 2785, 2821, 2828, 2831, 2832, 2835, 2856, 2858, 2864, 2866, 2867, 2869, 2873, 2877, 2877, 2908, 2909, 2911, 2913, 2929, 2929, 2947, 2947, 2949, 2954, 2958, 2960, 2962, 2965, 2969, 2970, 2972, 2972, 2974, 2975, 2979, 2980, 2984, 2986, 2990, 3001, 3024, 3024, 3077, 3084, 3086, 3088, 3090, 3112, 3114, 3123, 3125, 3129, 3133, 3133, 3160, 3161, 3168, 3169, 3205, 3212, 3214, 3216, 3218, 3240, 3242, 3251, 3253, 3257, 3261, 3261, 3294, 3294, 3296, 3297, 3313, 3314, 3333, 3340, 3342, 3344, 3346, 3386, 3389, 3389, 3406, 3406, 3424, 3425, 3450, 3455, 3461, 3478, 3482, 3505, 3507, 3515, 3517, 3517, 3520, 3526, 3585, 3632, 3634, 3635, 3648, 3654, 3713, 3714, 3716, 3716, 3719, 3720, 3722, 3722, 3725, 3725, 3732, 3735, 3737, 3743, 3745, 3747, 3749, 3749, 3751, 3751, 3754, 3755, 3757, 3760, 3762, 3763, 3773, 3773, 3776, 3780, 3782, 3782, 3804, 3807, 3840, 3840, 3904, 3911, 3913, 3948, 3976, 3980, 4096, 4138, 4159, 4159, 4176, 4181, 4186, 4189, 4193, 4193, 4197, 4198, 4206, 4208, 4213, 4225, 4238, 4238, 4256, 4293, 4295, 4295, 4301, 4301, 4304, 4346, 4348, 4680, 4682, 4685, 4688, 4694, 4696, 4696, 4698, 4701, 4704, 4744, 4746, 4749, 4752, 4784, 4786, 4789, 4792, 4798, 4800, 4800, 4802, 4805, 4808, 4822, 4824, 4880, 4882, 4885, 4888, 4954, 4992, 5007, 5024, 5108, 5121, 5740, 5743, 5759, 5761, 5786, 5792, 5866, 5870, 5872, 5888, 5900, 5902, 5905, 5920, 5937, 5952, 5969, 5984, 5996, 5998, 6000, 6016, 6067, 6103, 6103, 6108, 6108, 6176, 6263, 6272, 6312, 6314, 6314, 6320, 6389, 6400, 6428, 6480, 6509, 6512, 6516, 6528, 6571, 6593, 6599, 6656, 6678, 6688, 6740, 6823, 6823, 6917, 6963, 6981, 6987, 7043, 7072, 7086, 7087, 7098, 7141, 7168, 7203, 7245, 7247, 7258, 7293, 7401, 7404, 7406, 7409, 7413, 7414, 7424, 7615, 7680, 7957, 7960, 7965, 7968, 8005, 8008, 8013, 8016, 8023, 8025, 8025, 8027, 8027, 8029, 8029, 8031, 8061, 8064, 8116, 8118, 8124, 8126, 8126, 8130, 8132, 8134, 8140, 8144, 8147, 8150, 8155, 8160, 8172, 8178, 8180, 8182, 8188, 8305, 8305, 8319, 8319, 8336, 8348, 8450, 8450, 8455, 8455, 8458, 8467, 8469, 8469, 8473, 8477, 8484, 8484, 8486, 8486, 8488, 8488, 8490, 8493, 8495, 8505, 8508, 8511, 8517, 8521, 8526, 8526, 8544, 8584, 11264, 11310, 11312, 11358, 11360, 11492, 11499, 11502, 11506, 11507, 11520, 11557, 11559, 11559, 11565, 11565, 11568, 11623, 11631, 11631, 11648, 11670, 11680, 11686, 11688, 11694, 11696, 11702, 11704, 11710, 11712, 11718, 11720, 11726, 11728, 11734, 11736, 11742, 11823, 11823, 12293, 12295, 12321, 12329, 12337, 12341, 12344, 12348, 12353, 12438, 12445, 12447, 12449, 12538, 12540, 12543, 12549, 12589, 12593, 12686, 12704, 12730, 12784, 12799, 13312, 19893, 19968, 40908, 40960, 42124, 42192, 42237, 42240, 42508, 42512, 42527, 42538, 42539, 42560, 42606, 42623, 42647, 42656, 42735, 42775, 42783, 42786, 42888, 42891, 42894, 42896, 42899, 42912, 42922, 43000, 43009, 43011, 43013, 43015, 43018, 43020, 43042, 43072, 43123, 43138, 43187, 43250, 43255, 43259, 43259, 43274, 43301, 43312, 43334, 43360, 43388, 43396, 43442, 43471, 43471, 43520, 43560, 43584, 43586, 43588, 43595, 43616, 43638, 43642, 43642, 43648, 43695, 43697, 43697, 43701, 43702, 43705, 43709, 43712, 43712, 43714, 43714, 43739, 43741, 43744, 43754, 43762, 43764, 43777, 43782, 43785, 43790, 43793, 43798, 43808, 43814, 43816, 43822, 43968, 44002, 44032, 55203, 55216, 55238, 55243, 55291, 63744, 64109, 64112, 64217, 64256, 64262, 64275, 64279, 64285, 64285, 64287, 64296, 64298, 64310, 64312, 64316, 64318, 64318, 64320, 64321, 64323, 64324, 64326, 64433, 64467, 64829, 64848, 64911, 64914, 64967, 65008, 65019, 65136, 65140, 65142, 65276, 65313, 65338, 65345, 65370, 65382, 65470, 65474, 65479, 65482, 65487, 65490, 65495, 65498, 65500];
+#[rustfmt::skip]
+pub static UNICODE_ES5_IDENTIFIER_PART: [u32; 856] = [170, 170, 181, 181, 186, 186, 192, 214, 216, 246, 248, 705, 710, 721, 736, 740, 748, 748, 750, 750, 768, 884, 886, 887, 890, 893, 902, 902, 904, 906, 908, 908, 910, 929, 931, 1013, 1015, 1153, 1155, 1159, 1162, 1319, 1329, 1366, 1369, 1369, 1377, 1415, 1425, 1469, 1471, 1471, 1473, 1474, 1476, 1477, 1479, 1479, 1488, 1514, 1520, 1522, 1552, 1562, 1568, 1641, 1646, 1747, 1749, 1756, 1759, 1768, 1770, 1788, 1791, 1791, 1808, 1866, 1869, 1969, 1984, 2037, 2042, 2042, 2048, 2093, 2112, 2139, 2208, 2208, 2210, 2220, 2276, 2302, 2304, 2403, 2406, 2415, 2417, 2423, 2425, 2431, 2433, 2435, 2437, 2444, 2447, 2448, 2451, 2472, 2474, 2480, 2482, 2482, 2486, 2489, 2492, 2500, 2503, 2504, 2507, 2510, 2519, 2519, 2524, 2525, 2527, 2531, 2534, 2545, 2561, 2563, 2565, 2570, 2575, 2576, 2579, 2600, 2602, 2608, 2610, 2611, 2613, 2614, 2616, 2617, 2620, 2620, 2622, 2626, 2631, 2632, 2635, 2637, 2641, 2641, 2649, 2652, 2654, 2654, 2662, 2677, 2689, 2691, 2693, 2701, 2703, 2705, 2707, 2728, 2730, 2736, 2738, 2739, 2741, 2745, 2748, 2757, 2759, 2761, 2763, 2765, 2768, 2768, 2784, 2787, 2790, 2799, 2817, 2819, 2821, 2828, 2831, 2832, 2835, 2856, 2858, 2864, 2866, 2867, 2869, 2873, 2876, 2884, 2887, 2888, 2891, 2893, 2902, 2903, 2908, 2909, 2911, 2915, 2918, 2927, 2929, 2929, 2946, 2947, 2949, 2954, 2958, 2960, 2962, 2965, 2969, 2970, 2972, 2972, 2974, 2975, 2979, 2980, 2984, 2986, 2990, 3001, 3006, 3010, 3014, 3016, 3018, 3021, 3024, 3024, 3031, 3031, 3046, 3055, 3073, 3075, 3077, 3084, 3086, 3088, 3090, 3112, 3114, 3123, 3125, 3129, 3133, 3140, 3142, 3144, 3146, 3149, 3157, 3158, 3160, 3161, 3168, 3171, 3174, 3183, 3202, 3203, 3205, 3212, 3214, 3216, 3218, 3240, 3242, 3251, 3253, 3257, 3260, 3268, 3270, 3272, 3274, 3277, 3285, 3286, 3294, 3294, 3296, 3299, 3302, 3311, 3313, 3314, 3330, 3331, 3333, 3340, 3342, 3344, 3346, 3386, 3389, 3396, 3398, 3400, 3402, 3406, 3415, 3415, 3424, 3427, 3430, 3439, 3450, 3455, 3458, 3459, 3461, 3478, 3482, 3505, 3507, 3515, 3517, 3517, 3520, 3526, 3530, 3530, 3535, 3540, 3542, 3542, 3544, 3551, 3570, 3571, 3585, 3642, 3648, 3662, 3664, 3673, 3713, 3714, 3716, 3716, 3719, 3720, 3722, 3722, 3725, 3725, 3732, 3735, 3737, 3743, 3745, 3747, 3749, 3749, 3751, 3751, 3754, 3755, 3757, 3769, 3771, 3773, 3776, 3780, 3782, 3782, 3784, 3789, 3792, 3801, 3804, 3807, 3840, 3840, 3864, 3865, 3872, 3881, 3893, 3893, 3895, 3895, 3897, 3897, 3902, 3911, 3913, 3948, 3953, 3972, 3974, 3991, 3993, 4028, 4038, 4038, 4096, 4169, 4176, 4253, 4256, 4293, 4295, 4295, 4301, 4301, 4304, 4346, 4348, 4680, 4682, 4685, 4688, 4694, 4696, 4696, 4698, 4701, 4704, 4744, 4746, 4749, 4752, 4784, 4786, 4789, 4792, 4798, 4800, 4800, 4802, 4805, 4808, 4822, 4824, 4880, 4882, 4885, 4888, 4954, 4957, 4959, 4992, 5007, 5024, 5108, 5121, 5740, 5743, 5759, 5761, 5786, 5792, 5866, 5870, 5872, 5888, 5900, 5902, 5908, 5920, 5940, 5952, 5971, 5984, 5996, 5998, 6000, 6002, 6003, 6016, 6099, 6103, 6103, 6108, 6109, 6112, 6121, 6155, 6157, 6160, 6169, 6176, 6263, 6272, 6314, 6320, 6389, 6400, 6428, 6432, 6443, 6448, 6459, 6470, 6509, 6512, 6516, 6528, 6571, 6576, 6601, 6608, 6617, 6656, 6683, 6688, 6750, 6752, 6780, 6783, 6793, 6800, 6809, 6823, 6823, 6912, 6987, 6992, 7001, 7019, 7027, 7040, 7155, 7168, 7223, 7232, 7241, 7245, 7293, 7376, 7378, 7380, 7414, 7424, 7654, 7676, 7957, 7960, 7965, 7968, 8005, 8008, 8013, 8016, 8023, 8025, 8025, 8027, 8027, 8029, 8029, 8031, 8061, 8064, 8116, 8118, 8124, 8126, 8126, 8130, 8132, 8134, 8140, 8144, 8147, 8150, 8155, 8160, 8172, 8178, 8180, 8182, 8188, 8204, 8205, 8255, 8256, 8276, 8276, 8305, 8305, 8319, 8319, 8336, 8348, 8400, 8412, 8417, 8417, 8421, 8432, 8450, 8450, 8455, 8455, 8458, 8467, 8469, 8469, 8473, 8477, 8484, 8484, 8486, 8486, 8488, 8488, 8490, 8493, 8495, 8505, 8508, 8511, 8517, 8521, 8526, 8526, 8544, 8584, 11264, 11310, 11312, 11358, 11360, 11492, 11499, 11507, 11520, 11557, 11559, 11559, 11565, 11565, 11568, 11623, 11631, 11631, 11647, 11670, 11680, 11686, 11688, 11694, 11696, 11702, 11704, 11710, 11712, 11718, 11720, 11726, 11728, 11734, 11736, 11742, 11744, 11775, 11823, 11823, 12293, 12295, 12321, 12335, 12337, 12341, 12344, 12348, 12353, 12438, 12441, 12442, 12445, 12447, 12449, 12538, 12540, 12543, 12549, 12589, 12593, 12686, 12704, 12730, 12784, 12799, 13312, 19893, 19968, 40908, 40960, 42124, 42192, 42237, 42240, 42508, 42512, 42539, 42560, 42607, 42612, 42621, 42623, 42647, 42655, 42737, 42775, 42783, 42786, 42888, 42891, 42894, 42896, 42899, 42912, 42922, 43000, 43047, 43072, 43123, 43136, 43204, 43216, 43225, 43232, 43255, 43259, 43259, 43264, 43309, 43312, 43347, 43360, 43388, 43392, 43456, 43471, 43481, 43520, 43574, 43584, 43597, 43600, 43609, 43616, 43638, 43642, 43643, 43648, 43714, 43739, 43741, 43744, 43759, 43762, 43766, 43777, 43782, 43785, 43790, 43793, 43798, 43808, 43814, 43816, 43822, 43968, 44010, 44012, 44013, 44016, 44025, 44032, 55203, 55216, 55238, 55243, 55291, 63744, 64109, 64112, 64217, 64256, 64262, 64275, 64279, 64285, 64296, 64298, 64310, 64312, 64316, 64318, 64318, 64320, 64321, 64323, 64324, 64326, 64433, 64467, 64829, 64848, 64911, 64914, 64967, 65008, 65019, 65024, 65039, 65056, 65062, 65075, 65076, 65101, 65103, 65136, 65140, 65142, 65276, 65296, 65305, 65313, 65338, 65343, 65343, 65345, 65370, 65382, 65470, 65474, 65479, 65482, 65487, 65490, 65495, 65498, 65500];
+
 fn lookup_in_unicode_map(code: u32, map: &[u32]) -> bool {
   // Bail out quickly if it couldn't possibly be in the map.
   if code < map[0] {
@@ -125,7 +156,11 @@ fn lookup_in_unicode_map(code: u32, map: &[u32]) -> bool {
 /// a formal definition of valid identifier name.
 #[inline]
 pub fn is_id_start(c: char) -> bool {
-  lookup_in_unicode_map(c as u32, &UNICODE_ESNEXT_IDENTIFIER_START)
+  match c {
+    'a'..='z' | 'A'..='Z' | '$' | '_' => true,
+    c if c.is_ascii() => false,
+    c => lookup_in_unicode_map(c as u32, &UNICODE_ESNEXT_IDENTIFIER_START),
+  }
 }
 
 /// True if `c` is valid as a non-first character of an identifier.
@@ -133,7 +168,42 @@ pub fn is_id_start(c: char) -> bool {
 /// a formal definition of valid identifier name.
 #[inline]
 pub fn is_id_part(c: char) -> bool {
-  lookup_in_unicode_map(c as u32, &UNICODE_ESNEXT_IDENTIFIER_PART)
+  match c {
+    'a'..='z' | 'A'..='Z' | '0'..='9' | '$' | '_' => true,
+    // <ZWNJ> and <ZWJ> are allowed in identifier parts.
+    '\u{200C}' | '\u{200D}' => true,
+    c if c.is_ascii() => false,
+    c => lookup_in_unicode_map(c as u32, &UNICODE_ESNEXT_IDENTIFIER_PART),
+  }
+}
+
+/// Like [is_id_start], but with the identifier tables of `lang_version`,
+/// which are narrower for ES3 and ES5.
+pub fn is_identifier_start(c: char, lang_version: ScriptTarget) -> bool {
+  match c {
+    'a'..='z' | 'A'..='Z' | '$' | '_' => true,
+    c if c.is_ascii() => false,
+    c => match lang_version {
+      ScriptTarget::ES3 => lookup_in_unicode_map(c as u32, &UNICODE_ES3_IDENTIFIER_START),
+      ScriptTarget::ES5 => lookup_in_unicode_map(c as u32, &UNICODE_ES5_IDENTIFIER_START),
+      _ => lookup_in_unicode_map(c as u32, &UNICODE_ESNEXT_IDENTIFIER_START),
+    },
+  }
+}
+
+/// Like [is_id_part], but with the identifier tables of `lang_version`,
+/// which are narrower for ES3 and ES5.
+pub fn is_identifier_part(c: char, lang_version: ScriptTarget) -> bool {
+  match c {
+    'a'..='z' | 'A'..='Z' | '0'..='9' | '$' | '_' => true,
+    '\u{200C}' | '\u{200D}' => true,
+    c if c.is_ascii() => false,
+    c => match lang_version {
+      ScriptTarget::ES3 => lookup_in_unicode_map(c as u32, &UNICODE_ES3_IDENTIFIER_PART),
+      ScriptTarget::ES5 => lookup_in_unicode_map(c as u32, &UNICODE_ES5_IDENTIFIER_PART),
+      _ => lookup_in_unicode_map(c as u32, &UNICODE_ESNEXT_IDENTIFIER_PART),
+    },
+  }
 }
 
 /// The passed string is lexically an identifier.
@@ -147,12 +217,136 @@ pub fn is_ident(string: &str) -> bool {
   }
 }
 
+/// Computes the value of a numeric literal token exactly as ECMAScript's `ToNumber` does,
+/// i.e. the mathematical value rounded to the nearest double, ties to even.
+/// `text` is the literal's symbol, numeric separators are allowed.
+pub fn num_lit_value(text: &str) -> f64 {
+  let text: String = text.chars().filter(|&c| c != '_').collect();
+  let bytes = text.as_bytes();
+  if bytes.len() > 1 && bytes[0] == b'0' {
+    match bytes[1] {
+      b'x' | b'X' => return pow2_radix_value(&text[2..], 4),
+      b'b' | b'B' => return pow2_radix_value(&text[2..], 1),
+      b'o' | b'O' => return pow2_radix_value(&text[2..], 3),
+      // Legacy octal literal, e.g. `0777`.
+      _ if bytes.iter().all(|b| matches!(b, b'0'..=b'7')) => {
+        return pow2_radix_value(&text[1..], 3);
+      }
+      _ => (),
+    }
+  }
+  // The standard library parses decimal floats with correct rounding.
+  text.parse().unwrap_or(0.0)
+}
+
+/// Converts digits in a radix of `2^log2_radix` to the nearest double.
+fn pow2_radix_value(digits: &str, log2_radix: u32) -> f64 {
+  // Keep at least 60 significant bits, which is more than enough to round to
+  // the 53 bits of a double. Dropped digits only matter as a "sticky" bit.
+  let mut mantissa: u64 = 0;
+  let mut exp: i32 = 0;
+  let mut sticky = false;
+  for c in digits.chars() {
+    let digit = c.to_digit(1 << log2_radix).unwrap_or(0) as u64;
+    if mantissa >> (64 - log2_radix) == 0 {
+      mantissa = (mantissa << log2_radix) | digit;
+    } else {
+      exp += log2_radix as i32;
+      sticky |= digit != 0;
+    }
+  }
+
+  let bits = 64 - mantissa.leading_zeros();
+  if bits > 53 {
+    let shift = bits - 53;
+    let rest = mantissa & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    mantissa >>= shift;
+    exp += shift as i32;
+    if rest > half || (rest == half && (sticky || mantissa & 1 == 1)) {
+      mantissa += 1;
+    }
+  }
+  mantissa as f64 * 2f64.powi(exp)
+}
+
+/// Interns a UTF-16 string or template value. Lone surrogates, which a `Symbol` can't
+/// represent, become U+FFFD.
+fn utf16_symbol(value: &[u16]) -> Symbol {
+  Symbol::intern(&String::from_utf16_lossy(value))
+}
+
+/// Decodes the HTML entities of JSX text or a JSX attribute string, i.e. named ones like
+/// `&amp;` and numeric ones like `&#123;` or `&#x7B;`. Unknown entities are kept as they are.
+pub fn decode_entities(text: &str) -> String {
+  let mut decoded = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(amp) = rest.find('&') {
+    decoded.push_str(&rest[..amp]);
+    rest = &rest[amp..];
+    let entity = rest[1..].find(';').and_then(|semi| {
+      let name = &rest[1..semi + 1];
+      let c = if let Some(hex) = name.strip_prefix("#x") {
+        u32::from_str_radix(hex, 16).ok().and_then(std::char::from_u32)
+      } else if let Some(dec) = name.strip_prefix('#') {
+        dec.parse().ok().and_then(std::char::from_u32)
+      } else if !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        entities::lookup_entity(name)
+      } else {
+        None
+      };
+      c.map(|c| (c, semi + 2))
+    });
+    match entity {
+      Some((c, len)) => {
+        decoded.push(c);
+        rest = &rest[len..];
+      }
+      None => {
+        decoded.push('&');
+        rest = &rest[1..];
+      }
+    }
+  }
+  decoded.push_str(rest);
+  decoded
+}
+
+/// An error found by the [Scanner], e.g. a malformed numeric literal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScanError {
+  pub msg: String,
+  pub span: Span,
+}
+
+/// A saved [Scanner] state, see [Scanner::snapshot]. Cheap to take, it only
+/// copies the cursor and a few fields.
+#[derive(Clone)]
+pub struct ScannerSnapshot<'a> {
+  cursor: Cursor<'a>,
+  lang_variant: LanguageVariant,
+  token_flags: u16,
+  token_start: BytePos,
+  token_value: Option<Symbol>,
+  errors_len: usize,
+}
+
 pub struct Scanner<'a> {
   cursor: Cursor<'a>,
   lang_version: ScriptTarget,
   skip_trivia: bool,
   lang_variant: LanguageVariant,
   token_flags: u16,
+  token_start: BytePos,
+  /// The cooked value of the last string, template or JSX token, see [Scanner::token_value].
+  token_value: Option<Symbol>,
+  /// Receives the errors, see [Scanner::set_on_error].
+  on_error: Option<Box<dyn FnMut(ScanError) + 'a>>,
+  /// Errors not yet passed to `on_error`, or all of them if it is not set.
+  errors: Vec<ScanError>,
+  /// How many `look_ahead`/`try_scan` calls are running, errors are held back while
+  /// speculating as they may be rolled back.
+  speculation_depth: usize,
 }
 
 impl<'a> Scanner<'a> {
@@ -163,6 +357,11 @@ impl<'a> Scanner<'a> {
       skip_trivia: false,
       lang_variant: LanguageVariant::Standard,
       token_flags: TokenFlags::None,
+      token_start: start_pos,
+      token_value: None,
+      on_error: None,
+      errors: Vec::new(),
+      speculation_depth: 0,
     }
   }
 
@@ -174,6 +373,100 @@ impl<'a> Scanner<'a> {
     self.lang_variant = lang_variant;
   }
 
+  /// Flags of the last scanned token, see [TokenFlags].
+  pub fn token_flags(&self) -> u16 {
+    self.token_flags
+  }
+
+  /// Whether there is a line break between the last scanned token and the one before it.
+  pub fn has_preceding_line_break(&self) -> bool {
+    self.token_flags & TokenFlags::PrecedingLineBreak != 0
+  }
+
+  /// The cooked value of the last scanned template token, the raw value is its symbol.
+  /// `None` if the template contains an invalid escape, which is only allowed in tagged templates.
+  /// For strings it is the value with escapes processed, for JSX text and attribute
+  /// strings the text with HTML entities decoded.
+  pub fn token_value(&self) -> Option<Symbol> {
+    self.token_value
+  }
+
+  /// Sets the sink receiving every error as it is found. Without one, errors are
+  /// collected until [Scanner::take_errors] is called.
+  pub fn set_on_error(&mut self, on_error: impl FnMut(ScanError) + 'a) {
+    self.on_error = Some(Box::new(on_error));
+    self.flush_errors();
+  }
+
+  /// Takes the errors reported since the last call, when no error sink is set.
+  pub fn take_errors(&mut self) -> Vec<ScanError> {
+    std::mem::take(&mut self.errors)
+  }
+
+  /// Passes the pending errors to the error sink, unless speculating.
+  fn flush_errors(&mut self) {
+    if self.speculation_depth > 0 {
+      return;
+    }
+    if let Some(on_error) = &mut self.on_error {
+      self.errors.drain(..).for_each(on_error);
+    }
+  }
+
+  /// Saves the current state, so it can be restored after scanning ahead.
+  pub fn snapshot(&self) -> ScannerSnapshot<'a> {
+    ScannerSnapshot {
+      cursor: self.cursor.clone(),
+      lang_variant: self.lang_variant,
+      token_flags: self.token_flags,
+      token_start: self.token_start,
+      token_value: self.token_value,
+      errors_len: self.errors.len(),
+    }
+  }
+
+  /// Rolls back to `snapshot`, dropping errors reported since it was taken.
+  /// Errors already passed to the error sink can't be dropped, use
+  /// [Scanner::look_ahead] or [Scanner::try_scan] to hold them back.
+  pub fn restore(&mut self, snapshot: ScannerSnapshot<'a>) {
+    self.cursor = snapshot.cursor;
+    self.lang_variant = snapshot.lang_variant;
+    self.token_flags = snapshot.token_flags;
+    self.token_start = snapshot.token_start;
+    self.token_value = snapshot.token_value;
+    self.errors.truncate(snapshot.errors_len);
+  }
+
+  /// Runs `f` and always rolls the scanner back afterwards.
+  pub fn look_ahead<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+    let snapshot = self.snapshot();
+    self.speculation_depth += 1;
+    let result = f(self);
+    self.speculation_depth -= 1;
+    self.restore(snapshot);
+    result
+  }
+
+  /// Runs `f` and rolls the scanner back if it returns `None`, otherwise the
+  /// tokens it scanned stay consumed.
+  pub fn try_scan<T>(&mut self, f: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+    let snapshot = self.snapshot();
+    self.speculation_depth += 1;
+    let result = f(self);
+    self.speculation_depth -= 1;
+    if result.is_none() {
+      self.restore(snapshot);
+    }
+    self.flush_errors();
+    result
+  }
+
+  fn error(&mut self, lo: BytePos, hi: BytePos, msg: &str) {
+    let span = self.mk_sp(lo, hi);
+    self.errors.push(ScanError { msg: msg.to_owned(), span });
+    self.flush_errors();
+  }
+
   fn mk_sp(&self, lo: BytePos, hi: BytePos) -> Span {
     Span::new(lo, hi)
   }
@@ -191,6 +484,8 @@ impl<'a> Scanner<'a> {
   }
 
   pub fn scan(&mut self) -> Token {
+    self.token_flags = TokenFlags::None;
+    self.token_value = None;
     let start = self.cur_pos();
     let text = self.text();
 
@@ -205,85 +500,925 @@ impl<'a> Scanner<'a> {
 
     loop {
       let start = self.cur_pos();
+      self.token_start = start;
       if self.cursor.is_eof() {
         let span = self.mk_sp(start, start);
         return Token::new(token::Eof, span);
       }
 
-      let text = self.text();
       let ch = self.cursor.bump().unwrap();
 
-      match ch {
+      let kind = match ch {
         c if is_line_break(c) => {
           self.token_flags |= TokenFlags::PrecedingLineBreak;
           self.cursor.eat_while(is_line_break);
           if self.skip_trivia {
             continue;
           }
-          return Token::new(Trivia(token::NewLine), self.sp_from(start));
+          Trivia(token::NewLine)
         }
         c if is_whitespace(c) => {
           self.cursor.eat_while(is_whitespace);
           if self.skip_trivia {
             continue;
           }
-          return Token::new(Trivia(token::Whitespace), self.sp_from(start));
+          Trivia(token::Whitespace)
+        }
+        '<' | '|' | '=' | '>' if self.is_conflict_marker_at(start) => {
+          self.scan_conflict_marker(ch, start);
+          if self.skip_trivia {
+            continue;
+          }
+          Trivia(token::ConflictMarker)
         }
         '!' => {
-          if self.cursor.first() == '=' {
+          if self.cursor.eat('=') {
+            if self.cursor.eat('=') {
+              token::NotEqEq
+            } else {
+              token::NotEq
+            }
+          } else {
+            token::Not
+          }
+        }
+        '"' | '\'' => {
+          return self.scan_string(ch);
+        }
+        '%' => self.scan_bin_op(token::Percent),
+        '&' => {
+          if self.cursor.eat('&') {
+            self.scan_bin_op(token::AndAnd)
+          } else {
+            self.scan_bin_op(token::And)
+          }
+        }
+        '(' => token::OpenDelim(token::Paren),
+        ')' => token::CloseDelim(token::Paren),
+        '*' => {
+          if self.cursor.eat('*') {
+            self.scan_bin_op(token::StarStar)
+          } else {
+            self.scan_bin_op(token::Star)
+          }
+        }
+        '+' => {
+          if self.cursor.eat('+') {
+            token::PlusPlus
+          } else {
+            self.scan_bin_op(token::Plus)
+          }
+        }
+        ',' => token::Comma,
+        '-' => {
+          if self.cursor.eat('-') {
+            token::MinusMinus
+          } else {
+            self.scan_bin_op(token::Minus)
+          }
+        }
+        '.' => {
+          if self.cursor.first().is_ascii_digit() {
+            self.scan_number(ch, start)
+          } else if self.cursor.first() == '.' && self.cursor.second() == '.' {
+            self.cursor.bump();
+            self.cursor.bump();
+            token::DotDotDot
+          } else {
+            token::Dot
+          }
+        }
+        '0'..='9' => self.scan_number(ch, start),
+        '/' => match self.cursor.first() {
+          '/' => {
+            self.cursor.eat_while(|c| !is_line_break(c));
+            if self.skip_trivia {
+              continue;
+            }
+            Trivia(token::SingleLineComment)
+          }
+          '*' => {
             self.cursor.bump();
-            if self.cursor.first() == '=' {
-              self.cursor.bump();
-              return Token::new(token::NotEqEq, self.sp_from(start));
+            // `/**/` is an empty comment rather than a JSDoc one.
+            if self.cursor.first() == '*' && self.cursor.second() != '/' {
+              self.token_flags |= TokenFlags::PrecedingJSDocComment;
+            }
+            self.scan_multi_line_comment();
+            if self.skip_trivia {
+              continue;
             }
-            return Token::new(token::NotEq, self.sp_from(start));
+            Trivia(token::MultiLineComment)
+          }
+          _ => self.scan_bin_op(token::Slash),
+        },
+        ':' => token::Colon,
+        ';' => token::Semi,
+        '<' => {
+          if self.cursor.eat('<') {
+            self.scan_bin_op(token::Shl)
+          } else if self.cursor.eat('=') {
+            token::Le
+          } else if self.lang_variant == LanguageVariant::JSX
+            && self.cursor.first() == '/'
+            && self.cursor.second() != '*'
+          {
+            self.cursor.bump();
+            token::LtSlash
+          } else {
+            token::Lt
           }
-
-          return Token::new(token::Not, self.sp_from(start));
         }
+        '=' => {
+          if self.cursor.eat('=') {
+            if self.cursor.eat('=') {
+              token::EqEqEq
+            } else {
+              token::EqEq
+            }
+          } else if self.cursor.eat('>') {
+            token::Arrow
+          } else {
+            token::Eq
+          }
+        }
+        '>' => {
+          if self.cursor.eat('>') {
+            if self.cursor.eat('>') {
+              self.scan_bin_op(token::Ushr)
+            } else {
+              self.scan_bin_op(token::Shr)
+            }
+          } else if self.cursor.eat('=') {
+            token::Ge
+          } else {
+            token::Gt
+          }
+        }
+        '?' => {
+          // `a?.5:b` is a conditional expression rather than an optional chain.
+          if self.cursor.first() == '.' && !self.cursor.second().is_ascii_digit() {
+            self.cursor.bump();
+            token::QuestionDot
+          } else if self.cursor.eat('?') {
+            self.scan_bin_op(token::QuestionQuestion)
+          } else {
+            token::Question
+          }
+        }
+        '[' => token::OpenDelim(token::Bracket),
+        ']' => token::CloseDelim(token::Bracket),
+        '^' => self.scan_bin_op(token::Caret),
+        '{' => token::OpenDelim(token::Brace),
+        '|' => {
+          if self.cursor.eat('|') {
+            self.scan_bin_op(token::OrOr)
+          } else {
+            self.scan_bin_op(token::Or)
+          }
+        }
+        '}' => token::CloseDelim(token::Brace),
+        '~' => token::Tilde,
+        '@' => token::At,
+        '`' => self.scan_template(true, false),
+        '#' => match self.scan_identifier(self.cur_pos()) {
+          token::Ident(name) if !name.as_str().is_empty() => {
+            token::PrivateIdent(Symbol::intern(&format!("#{}", name)))
+          }
+          _ => {
+            self.error(start, self.cur_pos(), "Invalid character.");
+            token::Unknown
+          }
+        },
+        c if is_identifier_start(c, self.lang_version) => self.scan_identifier(start),
+        '\\' => match unicode_escape(self.text()) {
+          Some((c, _)) if is_identifier_start(c, self.lang_version) => self.scan_identifier(start),
+          _ => {
+            self.error(start, self.cur_pos(), "Invalid character.");
+            token::Unknown
+          }
+        },
+        _ => {
+          self.error(start, self.cur_pos(), "Invalid character.");
+          token::Unknown
+        }
+      };
 
-        '"' | '\'' => {
-          return self.scan_string();
+      return Token::new(kind, self.sp_from(start));
+    }
+  }
+
+  /// Rescans only the first character of a compound punctuator, e.g. the `>` of `>>`
+  /// when it closes nested type arguments. The rest is scanned again by the next call
+  /// to `scan`. Other tokens are returned as they are.
+  pub fn rescan_first_char_token(&mut self, token: Token) -> Token {
+    match token.break_two_token_op() {
+      Some((first, _)) => {
+        self.cursor.reset_to(first.span.hi());
+        first
+      }
+      None => token,
+    }
+  }
+
+  /// Rescans a `}` token as the continuation of a template, i.e. a `TemplateMiddle` or
+  /// `TemplateTail`. Called by the parser after the expression of a template span.
+  pub fn rescan_template_token(&mut self, is_tagged: bool) -> Token {
+    debug_assert!(self.cursor.str_from(self.token_start).starts_with('}'));
+    self.cursor.reset_to(self.token_start);
+    self.cursor.bump();
+    self.token_flags &= TokenFlags::PrecedingLineBreak;
+    let kind = self.scan_template(false, !is_tagged);
+    Token::new(kind, self.sp_from(self.token_start))
+  }
+
+  /// Rescans a `NoSubstitution` or `Head` template token of an untagged template,
+  /// reporting the invalid escapes that were only flagged when it was first scanned.
+  pub fn rescan_template_head_or_no_substitution_template(&mut self) -> Token {
+    debug_assert!(self.cursor.str_from(self.token_start).starts_with('`'));
+    self.cursor.reset_to(self.token_start);
+    self.cursor.bump();
+    self.token_flags &= TokenFlags::PrecedingLineBreak;
+    let kind = self.scan_template(true, true);
+    Token::new(kind, self.sp_from(self.token_start))
+  }
+
+  /// Rescans a `/` or `/=` token as a regular expression literal, as only the parser knows
+  /// whether a regex or a division is expected. Other tokens are returned as they are.
+  pub fn rescan_slash_token(&mut self, token: Token) -> Token {
+    match token.kind {
+      token::BinOp(token::Slash) | token::BinOpEq(token::Slash) => (),
+      _ => return token,
+    }
+    let start = token.span.lo();
+    self.cursor.reset_to(start);
+    self.cursor.bump();
+    self.token_flags &= TokenFlags::PrecedingLineBreak;
+
+    let mut in_escape = false;
+    let mut in_class = false;
+    let mut open_groups = Vec::new();
+    let body_end = loop {
+      let c = self.cursor.first();
+      if self.cursor.is_eof() || is_line_break(c) {
+        self.token_flags |= TokenFlags::Unterminated;
+        self.error(start, self.cur_pos(), "Unterminated regular expression literal.");
+        break self.cur_pos();
+      }
+      let pos = self.cur_pos();
+      self.cursor.bump();
+      if in_escape {
+        in_escape = false;
+        continue;
+      }
+      match c {
+        '/' if !in_class => break pos,
+        '\\' => in_escape = true,
+        '[' => in_class = true,
+        ']' => in_class = false,
+        '(' if !in_class => open_groups.push(pos),
+        ')' if !in_class => {
+          if open_groups.pop().is_none() {
+            self.error(
+              pos,
+              self.cur_pos(),
+              "Unexpected ')'. Did you mean to escape it with backslash?",
+            );
+          }
         }
+        _ => (),
+      }
+    };
+    if self.token_flags & TokenFlags::Unterminated == 0 {
+      for pos in open_groups {
+        self.error(pos, pos + BytePos(1), "Unterminated group, ')' expected.");
+      }
+    }
+
+    let flags_start = self.cur_pos();
+    let lang_version = self.lang_version;
+    self.cursor.eat_while(|c| is_identifier_part(c, lang_version));
+    self.check_regexp_flags(flags_start);
 
-        _ => {}
+    let body = Symbol::intern(self.cursor.str_from_to(start + BytePos(1), body_end));
+    let flags = self.cursor.str_from(flags_start);
+    let suffix = if flags.is_empty() { None } else { Some(Symbol::intern(flags)) };
+    Token::new(token::Literal(Lit::new(LitKind::RegExp, body, suffix)), self.sp_from(start))
+  }
+
+  /// Checks the flags of a regular expression literal, which start at `start`
+  /// and end at the current position.
+  fn check_regexp_flags(&mut self, start: BytePos) {
+    let mut seen = String::new();
+    for (i, c) in self.cursor.str_from(start).char_indices() {
+      let lo = start + BytePos::from_usize(i);
+      let hi = lo + BytePos::from_usize(c.len_utf8());
+      let required_target = match c {
+        'g' | 'i' | 'm' => ScriptTarget::ES3,
+        'u' | 'y' => ScriptTarget::ES2015,
+        's' => ScriptTarget::ES2018,
+        'd' | 'v' => ScriptTarget::ESNext,
+        _ => {
+          self.error(lo, hi, "Unknown regular expression flag.");
+          continue;
+        }
+      };
+      if seen.contains(c) {
+        self.error(lo, hi, "Duplicate regular expression flag.");
+        continue;
+      }
+      seen.push(c);
+      if self.lang_version < required_target {
+        let msg = format!(
+          "This regular expression flag is only available when targeting '{}' or later.",
+          format!("{:?}", required_target).to_lowercase()
+        );
+        self.error(lo, hi, &msg);
+      }
+      if (c == 'u' && seen.contains('v')) || (c == 'v' && seen.contains('u')) {
+        self.error(
+          lo,
+          hi,
+          "The Unicode (u) flag and the Unicode Sets (v) flag cannot be set simultaneously.",
+        );
       }
     }
   }
 
-  fn scan_string(&mut self) -> Token {
-    let quote = self.cursor.prev();
+  /// Scans a token between JSX tags: `<`, `</`, `{` or JSX text up to one of them.
+  /// Unless `allow_multiline_jsx_text` is set, the text ends at the first line break
+  /// after some non-whitespace.
+  pub fn scan_jsx_token(&mut self, allow_multiline_jsx_text: bool) -> Token {
     let start = self.cur_pos();
-    debug_assert!(quote == '"' || quote == '\'');
+    self.token_start = start;
+    self.token_flags = TokenFlags::None;
+    self.token_value = None;
 
-    while let Some(c) = self.cursor.bump() {
-      match c {
-        c if c == quote => {
-          let quote_kind = match quote {
-            '\'' => token::QuoteKind::Single,
-            '"' => token::QuoteKind::Double,
-            _ => {
-              todo!("impossible")
+    let kind = match self.cursor.first() {
+      _ if self.cursor.is_eof() => token::Eof,
+      '<' if self.is_conflict_marker_at(start) => {
+        self.cursor.bump();
+        self.scan_conflict_marker('<', start);
+        Trivia(token::ConflictMarker)
+      }
+      '<' => {
+        self.cursor.bump();
+        if self.cursor.eat('/') {
+          token::LtSlash
+        } else {
+          token::Lt
+        }
+      }
+      '{' => {
+        self.cursor.bump();
+        token::OpenDelim(token::Brace)
+      }
+      _ => {
+        let mut only_whitespace_with_line_break = false;
+        let mut seen_non_whitespace = false;
+        loop {
+          let c = self.cursor.first();
+          if self.cursor.is_eof() || c == '{' || c == '<' {
+            break;
+          }
+          let pos = self.cur_pos();
+          match c {
+            '>' => {
+              self.error(pos, pos + BytePos(1), "Unexpected token. Did you mean `{'>'}` or `&gt;`?")
+            }
+            '}' => self.error(
+              pos,
+              pos + BytePos(1),
+              "Unexpected token. Did you mean `{'}'}` or `&rbrace;`?",
+            ),
+            _ => (),
+          }
+          if is_line_break(c) {
+            if !seen_non_whitespace {
+              only_whitespace_with_line_break = true;
+            } else if !allow_multiline_jsx_text {
+              break;
             }
-          };
-          return Token::new(
-            token::Literal(Lit::new(
-              LitKind::Str(quote_kind),
-              Symbol::intern(
-                self.cursor.str_from_to(start, BytePos::from_usize(self.cur_pos().to_usize() - 1)),
-              ),
-              None,
-            )),
-            self.sp_from(BytePos::from_usize(start.to_usize() - 1)),
+          } else if !is_whitespace(c) {
+            seen_non_whitespace = true;
+            only_whitespace_with_line_break = false;
+          }
+          self.cursor.bump();
+        }
+        let text = self.cursor.str_from(start);
+        self.token_value = Some(Symbol::intern(&decode_entities(text)));
+        if only_whitespace_with_line_break {
+          token::JsxTextAllWhiteSpaces(Symbol::intern(text))
+        } else {
+          token::JsxText(Symbol::intern(text))
+        }
+      }
+    };
+    Token::new(kind, self.sp_from(start))
+  }
+
+  /// Scans again from `pos` as between JSX tags, see [Scanner::scan_jsx_token]. The
+  /// parser passes the end of the previous token, so that a token scanned as usual,
+  /// e.g. after a mismatched closing tag, becomes JSX text along with the trivia before it.
+  pub fn rescan_jsx_token(&mut self, pos: BytePos, allow_multiline_jsx_text: bool) -> Token {
+    self.cursor.reset_to(pos);
+    self.scan_jsx_token(allow_multiline_jsx_text)
+  }
+
+  /// Extends an identifier `token` to a JSX name, which may contain dashes and one namespace
+  /// separator, e.g. `data-foo` or `svg:rect`. Other tokens are returned as they are.
+  pub fn scan_jsx_identifier(&mut self, token: Token) -> Token {
+    if !matches!(token.kind, TokenKind::Ident(_)) {
+      return token;
+    }
+    let start = token.span.lo();
+    self.cursor.reset_to(token.span.hi());
+    let mut seen_namespace_separator = false;
+    loop {
+      match self.cursor.first() {
+        '-' => {
+          self.cursor.bump();
+        }
+        ':' if !seen_namespace_separator => {
+          self.cursor.bump();
+          seen_namespace_separator = true;
+        }
+        c if is_identifier_part(c, self.lang_version) => {
+          let lang_version = self.lang_version;
+          self.cursor.eat_while(|c| is_identifier_part(c, lang_version));
+        }
+        _ => break,
+      }
+    }
+    // A trailing namespace separator is not part of the name.
+    if self.cursor.str_from(start).ends_with(':') {
+      self.cursor.reset_to(self.cur_pos() - BytePos(1));
+    }
+    let name = Symbol::intern(self.cursor.str_from(start));
+    Token::new(TokenKind::Ident(name), self.sp_from(start))
+  }
+
+  /// Scans a JSX attribute value. Strings have no escapes there, only HTML entities,
+  /// and may span lines. Anything else is scanned as usual.
+  pub fn scan_jsx_attribute_value(&mut self) -> Token {
+    let quote = self.cursor.first();
+    if quote != '"' && quote != '\'' {
+      return self.scan();
+    }
+    let start = self.cur_pos();
+    self.token_start = start;
+    self.token_flags = TokenFlags::None;
+    self.cursor.bump();
+    self.cursor.eat_while(|c| c != quote);
+    let text = self.cursor.str_from(start + BytePos(1));
+    if !self.cursor.eat(quote) {
+      self.token_flags |= TokenFlags::Unterminated;
+      self.error(start, self.cur_pos(), "Unterminated string literal.");
+    }
+    self.token_value = Some(Symbol::intern(&decode_entities(text)));
+    let quote_kind = if quote == '"' { token::QuoteKind::Double } else { token::QuoteKind::Single };
+    let lit = Lit::new(LitKind::Str(quote_kind), Symbol::intern(text), None);
+    Token::new(token::Literal(lit), self.sp_from(start))
+  }
+
+  /// Scans a token inside a JSDoc comment, for a scanner created over the text of the comment.
+  /// Only the tokens making up JSDoc tags are recognized, anything else is `Unknown`,
+  /// and identifiers may contain dashes, e.g. `@param foo-bar`.
+  pub fn scan_jsdoc_token(&mut self) -> Token {
+    let start = self.cur_pos();
+    self.token_start = start;
+    self.token_flags = TokenFlags::None;
+    self.token_value = None;
+
+    let c = match self.cursor.bump() {
+      Some(c) => c,
+      None => return Token::new(token::Eof, self.mk_sp(start, start)),
+    };
+    let kind = match c {
+      '\t' | '\u{B}' | '\u{C}' | ' ' => {
+        self.cursor.eat_while(|c| matches!(c, '\t' | '\u{B}' | '\u{C}' | ' '));
+        Trivia(token::Whitespace)
+      }
+      '\r' | '\n' => {
+        if c == '\r' {
+          self.cursor.eat('\n');
+        }
+        self.token_flags |= TokenFlags::PrecedingLineBreak;
+        Trivia(token::NewLine)
+      }
+      '@' => token::At,
+      '*' => token::BinOp(token::Star),
+      '{' => token::OpenDelim(token::Brace),
+      '}' => token::CloseDelim(token::Brace),
+      '[' => token::OpenDelim(token::Bracket),
+      ']' => token::CloseDelim(token::Bracket),
+      '<' => token::Lt,
+      '>' => token::Gt,
+      '=' => token::Eq,
+      ',' => token::Comma,
+      '.' => token::Dot,
+      '`' => token::Backtick,
+      c if is_identifier_start(c, self.lang_version) => {
+        let lang_version = self.lang_version;
+        self.cursor.eat_while(|c| is_identifier_part(c, lang_version) || c == '-');
+        TokenKind::Ident(Symbol::intern(self.cursor.str_from(start)))
+      }
+      _ => token::Unknown,
+    };
+    Token::new(kind, self.sp_from(start))
+  }
+
+  /// Scans the optional `=` following a binary operator, e.g. `+` or `+=`.
+  fn scan_bin_op(&mut self, op: BinOpToken) -> TokenKind {
+    if self.cursor.eat('=') {
+      token::BinOpEq(op)
+    } else {
+      token::BinOp(op)
+    }
+  }
+
+  /// Scans an identifier starting at `start`, whose first character, possibly escaped,
+  /// is known to be valid. The symbol is the name with its escapes decoded.
+  fn scan_identifier(&mut self, start: BytePos) -> TokenKind {
+    self.cursor.reset_to(start);
+    let mut name = String::new();
+    loop {
+      let is_valid = |c| {
+        if name.is_empty() {
+          is_identifier_start(c, self.lang_version)
+        } else {
+          is_identifier_part(c, self.lang_version)
+        }
+      };
+      let c = self.cursor.first();
+      if c == '\\' {
+        let escape = &self.text()[1..];
+        match unicode_escape(escape) {
+          Some((c, len)) if is_valid(c) => {
+            self.token_flags |= if escape.starts_with("u{") {
+              TokenFlags::ExtendedUnicodeEscape
+            } else {
+              TokenFlags::UnicodeEscape
+            };
+            self.cursor.forword_by_usize(len + 1);
+            name.push(c);
+          }
+          _ => break,
+        }
+      } else if is_valid(c) && !self.cursor.is_eof() {
+        self.cursor.bump();
+        name.push(c);
+      } else {
+        break;
+      }
+    }
+    TokenKind::Ident(Symbol::intern(&name))
+  }
+
+  /// Scans a numeric literal, `first` is the already eaten first character,
+  /// i.e. a digit or the `.` of e.g. `.5`.
+  fn scan_number(&mut self, first: char, start: BytePos) -> TokenKind {
+    let mut is_integer = true;
+
+    if first == '0' {
+      let radix = match self.cursor.first() {
+        'x' | 'X' => Some((16, TokenFlags::HexSpecifier, "Hexadecimal digit expected.")),
+        'b' | 'B' => Some((2, TokenFlags::BinarySpecifier, "Binary digit expected.")),
+        'o' | 'O' => Some((8, TokenFlags::OctalSpecifier, "Octal digit expected.")),
+        _ => None,
+      };
+      if let Some((radix, flag, msg)) = radix {
+        self.cursor.bump();
+        self.token_flags |= flag;
+        if self.scan_digits(false, |c| c.is_digit(radix)) == 0 {
+          let pos = self.cur_pos();
+          self.error(pos, pos, msg);
+        }
+        return self.scan_bigint_suffix(start);
+      }
+
+      if self.cursor.first().is_ascii_digit() {
+        // Legacy octal literal (`0777`) or a decimal one with a leading zero (`089`),
+        // neither of them may contain separators.
+        self.cursor.eat_while(|c| c.is_ascii_digit());
+        if self.cursor.str_from(start).bytes().all(|b| matches!(b, b'0'..=b'7')) {
+          self.token_flags |= TokenFlags::Octal;
+          let symbol = Symbol::intern(self.cursor.str_from(start));
+          self.check_ident_after_number();
+          return token::Literal(Lit::new(LitKind::Num, symbol, None));
+        }
+        is_integer = false;
+      }
+    }
+
+    if first == '.' {
+      is_integer = false;
+      self.scan_digits(false, |c| c.is_ascii_digit());
+    } else {
+      self.scan_digits(true, |c| c.is_ascii_digit());
+      if self.cursor.first() == '.' {
+        self.cursor.bump();
+        is_integer = false;
+        self.scan_digits(false, |c| c.is_ascii_digit());
+      }
+    }
+
+    if matches!(self.cursor.first(), 'e' | 'E') {
+      self.cursor.bump();
+      self.token_flags |= TokenFlags::Scientific;
+      is_integer = false;
+      if matches!(self.cursor.first(), '+' | '-') {
+        self.cursor.bump();
+      }
+      if self.scan_digits(false, |c| c.is_ascii_digit()) == 0 {
+        let pos = self.cur_pos();
+        self.error(pos, pos, "Digit expected.");
+      }
+    }
+
+    if is_integer {
+      return self.scan_bigint_suffix(start);
+    }
+
+    let symbol = Symbol::intern(self.cursor.str_from(start));
+    if self.cursor.first() == 'n' && !is_identifier_part(self.cursor.second(), self.lang_version) {
+      // Keep the suffix of e.g. `1.5n` in the literal, so it is reported only once.
+      self.cursor.bump();
+      let msg = if self.token_flags & TokenFlags::Scientific != 0 {
+        "A bigint literal cannot use exponential notation."
+      } else {
+        "A bigint literal must be an integer."
+      };
+      self.error(start, self.cur_pos(), msg);
+    } else {
+      self.check_ident_after_number();
+    }
+    token::Literal(Lit::new(LitKind::Num, symbol, None))
+  }
+
+  /// Eats digits and numeric separators, returns the number of digits eaten.
+  /// `after_digit` tells whether the digits continue an already eaten one.
+  fn scan_digits(&mut self, after_digit: bool, is_digit: impl Fn(char) -> bool) -> usize {
+    let mut digits = 0;
+    let mut separator_allowed = after_digit;
+    let mut is_prev_separator = false;
+    loop {
+      let c = self.cursor.first();
+      if c == '_' {
+        self.token_flags |= TokenFlags::ContainsSeparator;
+        let pos = self.cur_pos();
+        self.cursor.bump();
+        if separator_allowed {
+          separator_allowed = false;
+          is_prev_separator = true;
+        } else if is_prev_separator {
+          self.error(
+            pos,
+            self.cur_pos(),
+            "Multiple consecutive numeric separators are not permitted.",
           );
+        } else {
+          self.error(pos, self.cur_pos(), "Numeric separators are not allowed here.");
+        }
+      } else if is_digit(c) && !self.cursor.is_eof() {
+        self.cursor.bump();
+        digits += 1;
+        separator_allowed = true;
+        is_prev_separator = false;
+      } else {
+        break;
+      }
+    }
+    if is_prev_separator {
+      let pos = self.cur_pos();
+      self.error(pos - BytePos(1), pos, "Numeric separators are not allowed here.");
+    }
+    digits
+  }
+
+  /// Scans the `n` suffix of an integer literal, making it a BigInt literal.
+  fn scan_bigint_suffix(&mut self, start: BytePos) -> TokenKind {
+    let symbol = Symbol::intern(self.cursor.str_from(start));
+    if self.cursor.first() == 'n' {
+      self.cursor.bump();
+      self.check_ident_after_number();
+      token::Literal(Lit::new(LitKind::BigInt, symbol, Some(Symbol::intern("n"))))
+    } else {
+      self.check_ident_after_number();
+      token::Literal(Lit::new(LitKind::Num, symbol, None))
+    }
+  }
+
+  /// Reports an identifier immediately following a numeric literal, e.g. `3in x`.
+  /// The identifier itself is left to be scanned as the next token.
+  fn check_ident_after_number(&mut self) {
+    if !is_identifier_start(self.cursor.first(), self.lang_version) || self.cursor.is_eof() {
+      return;
+    }
+    let text = self.text();
+    let len = text.find(|c| !is_identifier_part(c, self.lang_version)).unwrap_or(text.len());
+    let pos = self.cur_pos();
+    self.error(
+      pos,
+      pos + BytePos::from_usize(len),
+      "An identifier or keyword cannot immediately follow a numeric literal.",
+    );
+  }
+
+  /// Scans the rest of a template token, the opening `` ` `` or `}` is already eaten.
+  /// Invalid escapes are always flagged with `ContainsInvalidEscape`, but only reported
+  /// when `report_invalid_escape` is set, as tagged templates allow them.
+  fn scan_template(
+    &mut self,
+    started_with_backtick: bool,
+    report_invalid_escape: bool,
+  ) -> TokenKind {
+    let start = self.cur_pos();
+    let (template_kind, content_end) = loop {
+      let pos = self.cur_pos();
+      let kind = match self.cursor.bump() {
+        Some('`') => {
+          if started_with_backtick {
+            token::NoSubstitution
+          } else {
+            token::Tail
+          }
         }
-        '\\' if self.cursor.first() == '\\' || self.cursor.first() == quote => {
+        Some('$') if self.cursor.first() == '{' => {
           self.cursor.bump();
+          if started_with_backtick {
+            token::Head
+          } else {
+            token::Middle
+          }
+        }
+        Some('\\') => {
+          self.skip_escaped_char();
+          continue;
+        }
+        Some(_) => continue,
+        None => {
+          self.token_flags |= TokenFlags::Unterminated;
+          self.error(pos, pos, "Unterminated template literal.");
+          if started_with_backtick {
+            token::NoSubstitution
+          } else {
+            token::Tail
+          }
+        }
+      };
+      break (kind, pos);
+    };
+
+    let mut errors = Vec::new();
+    let (cooked, raw) =
+      js_template_value(self.cursor.str_from_to(start, content_end), &mut |range, err| {
+        errors.push((range, err))
+      });
+    self.report_escape_errors(start, errors, report_invalid_escape);
+    self.token_value = cooked.map(|cooked| utf16_symbol(&cooked));
+    token::Literal(Lit::new(LitKind::Template(template_kind), utf16_symbol(&raw), None))
+  }
+
+  /// Skips the character after a backslash in a string or template, which can't end it,
+  /// and flags unicode escapes. The escape is decoded along with the whole literal.
+  fn skip_escaped_char(&mut self) {
+    match self.cursor.bump() {
+      Some('u') => {
+        self.token_flags |= if self.cursor.first() == '{' {
+          TokenFlags::ExtendedUnicodeEscape
+        } else {
+          TokenFlags::UnicodeEscape
+        };
+      }
+      // A line continuation, `<CR><LF>` being a single line terminator.
+      Some('\r') => {
+        self.cursor.eat('\n');
+      }
+      _ => (),
+    }
+  }
+
+  /// Flags and, if `report`, reports the invalid escapes found by [tscore_lexer::unescape]
+  /// in the contents of a string or template starting at `start`.
+  fn report_escape_errors(
+    &mut self,
+    start: BytePos,
+    errors: Vec<(Range<usize>, EscapeError)>,
+    report: bool,
+  ) {
+    for (range, err) in errors {
+      let msg = match err {
+        // A backslash at the end of the file, reported as an unterminated literal.
+        EscapeError::LoneSlash => continue,
+        EscapeError::LegacyOctalEscape => {
+          "Octal escape sequences are not allowed in template strings."
+        }
+        EscapeError::NonOctalDecimalEscape => "Escape sequence is not allowed.",
+        EscapeError::OutOfRangeUnicodeEscape => {
+          "An extended Unicode escape value must be between 0x0 and 0x10FFFF inclusive."
+        }
+        // Digits after `\u{`, but no closing brace.
+        EscapeError::InvalidCharInUnicodeEscape | EscapeError::UnclosedUnicodeEscape
+          if range.len() > 3 =>
+        {
+          "Unterminated Unicode escape sequence."
+        }
+        _ => "Hexadecimal digit expected.",
+      };
+      self.token_flags |= TokenFlags::ContainsInvalidEscape;
+      if report {
+        let lo = start + BytePos::from_usize(range.start);
+        let hi = start + BytePos::from_usize(range.end);
+        self.error(lo, hi, msg);
+      }
+    }
+  }
+
+  /// Whether a merge conflict marker starts at `pos`, at the start of a line.
+  fn is_conflict_marker_at(&self, pos: BytePos) -> bool {
+    self.cursor.char_before(pos).map_or(true, is_line_break)
+      && is_conflict_marker(self.cursor.text_from(pos))
+  }
+
+  /// Skips a merge conflict marker starting at `start`, its first char `ch` is already eaten.
+  /// `<<<<<<<` and `>>>>>>>` markers only span their line. After `|||||||` or `=======`,
+  /// everything up to the next `=======` or `>>>>>>>` marker is skipped as well, so only
+  /// one side of the conflict is scanned.
+  fn scan_conflict_marker(&mut self, ch: char, start: BytePos) {
+    self.error(
+      start,
+      start + BytePos::from_usize(CONFLICT_MARKER_LEN),
+      "Merge conflict marker encountered.",
+    );
+    if ch == '<' || ch == '>' {
+      self.cursor.eat_while(|c| !is_line_break(c));
+      return;
+    }
+    debug_assert!(ch == '|' || ch == '=');
+    while !self.cursor.is_eof() {
+      let c = self.cursor.first();
+      if (c == '=' || c == '>') && c != ch && self.is_conflict_marker_at(self.cur_pos()) {
+        break;
+      }
+      self.cursor.bump();
+    }
+  }
+
+  /// Scans the rest of a `/* ... */` comment, the opening `/*` is already eaten.
+  fn scan_multi_line_comment(&mut self) {
+    while let Some(c) = self.cursor.bump() {
+      match c {
+        '*' if self.cursor.first() == '/' => {
+          self.cursor.bump();
+          return;
+        }
+        c if is_line_break(c) => {
+          self.token_flags |= TokenFlags::PrecedingLineBreak;
         }
         _ => (),
       }
     }
+    self.token_flags |= TokenFlags::Unterminated;
+    let pos = self.cur_pos();
+    self.error(pos, pos, "'*/' expected.");
+  }
+
+  /// Scans the rest of a string literal, the opening quote is already eaten.
+  /// The symbol is the raw text between the quotes, the value with escapes
+  /// processed is kept as [Scanner::token_value].
+  fn scan_string(&mut self, quote: char) -> Token {
+    debug_assert!(quote == '"' || quote == '\'');
+    let start = self.cur_pos() - BytePos(1);
+    let content_end = loop {
+      let c = self.cursor.first();
+      if self.cursor.is_eof() || is_line_break(c) {
+        self.token_flags |= TokenFlags::Unterminated;
+        self.error(start, self.cur_pos(), "Unterminated string literal.");
+        break self.cur_pos();
+      }
+      let pos = self.cur_pos();
+      self.cursor.bump();
+      match c {
+        c if c == quote => break pos,
+        '\\' => self.skip_escaped_char(),
+        _ => (),
+      }
+    };
 
-    todo!("raise fatal error")
+    // Invalid escapes are left out of the value.
+    let content = self.cursor.str_from_to(start + BytePos(1), content_end);
+    let mut value = Vec::with_capacity(content.len());
+    let mut errors = Vec::new();
+    unescape_js_literal(content, JsMode::Str, &mut |range, result| match result {
+      Ok(code) => encode_utf16(code, &mut value),
+      Err(err) => errors.push((range, err)),
+    });
+    self.report_escape_errors(start + BytePos(1), errors, true);
+    self.token_value = Some(utf16_symbol(&value));
+    let quote_kind = if quote == '"' { token::QuoteKind::Double } else { token::QuoteKind::Single };
+    Token::new(
+      token::Literal(Lit::new(LitKind::Str(quote_kind), Symbol::intern(content), None)),
+      self.sp_from(start),
+    )
   }
 }
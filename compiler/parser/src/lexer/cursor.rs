@@ -5,6 +5,7 @@ use tscore_span::{BytePos, Pos};
 ///
 /// Next characters can be peeked via `nth_char` method,
 /// and position can be shifted forward via `bump` method.
+#[derive(Clone)]
 pub(crate) struct Cursor<'a> {
   src: &'a str,
   chars: Chars<'a>,
@@ -86,6 +87,7 @@ impl<'a> Cursor<'a> {
   /// Moves to the next character.
   pub(crate) fn bump(&mut self) -> Option<char> {
     let c = self.chars.next()?;
+    self.pos = self.pos + BytePos::from_usize(c.len_utf8());
 
     #[cfg(debug_assertions)]
     {
@@ -95,6 +97,16 @@ impl<'a> Cursor<'a> {
     Some(c)
   }
 
+  /// Moves to the next character if it is `c`.
+  pub(crate) fn eat(&mut self, c: char) -> bool {
+    if self.first() == c && !self.is_eof() {
+      self.bump();
+      true
+    } else {
+      false
+    }
+  }
+
   /// Eats symbols while predicate returns true or until the end of file is reached.
   pub(crate) fn eat_while(&mut self, mut predicate: impl FnMut(char) -> bool) {
     while predicate(self.first()) && !self.is_eof() {
@@ -111,22 +123,40 @@ impl<'a> Cursor<'a> {
   }
 
   pub(crate) fn forword_by_usize(&mut self, size: usize) -> BytePos {
-    self.pos + BytePos::from_usize(size);
+    self.pos = self.pos + BytePos::from_usize(size);
+    self.chars = self.src[self.src_index(self.pos)..].chars();
 
     self.pos
   }
+
+  /// Moves the cursor back (or forward) to `pos`, e.g. to rescan a token.
+  pub(crate) fn reset_to(&mut self, pos: BytePos) {
+    self.pos = pos;
+    self.chars = self.src[self.src_index(pos)..].chars();
+  }
+
+  /// The source text from `pos` to the end.
+  pub(crate) fn text_from(&self, pos: BytePos) -> &'a str {
+    &self.src[self.src_index(pos)..]
+  }
+
+  /// The character right before `pos`, `None` at the start of the source.
+  pub(crate) fn char_before(&self, pos: BytePos) -> Option<char> {
+    self.src[..self.src_index(pos)].chars().next_back()
+  }
+
   pub(crate) fn src_index(&self, pos: BytePos) -> usize {
     (pos - self.start_pos).to_usize()
   }
 
   /// Slice of the source text from `start` up to but excluding `self.pos`,
   /// meaning the slice does not include the character `self.ch`.
-  pub(crate) fn str_from(&self, start: BytePos) -> &str {
+  pub(crate) fn str_from(&self, start: BytePos) -> &'a str {
     self.str_from_to(start, self.pos)
   }
 
   /// Slice of the source text spanning from `start` up to but excluding `end`.
-  pub(crate) fn str_from_to(&self, start: BytePos, end: BytePos) -> &str {
+  pub(crate) fn str_from_to(&self, start: BytePos, end: BytePos) -> &'a str {
     &self.src[self.src_index(start)..self.src_index(end)]
   }
 }
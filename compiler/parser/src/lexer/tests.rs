@@ -0,0 +1,1204 @@
+use super::*;
+
+use expect_test::{expect, Expect};
+use tscore_span::with_default_session_globals;
+
+fn scan_all(src: &str, lang_variant: LanguageVariant) -> String {
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new(src, ScriptTarget::ESNext, BytePos(0));
+    scanner.set_lang_variant(lang_variant);
+    let mut actual = String::new();
+    loop {
+      let token = scanner.scan();
+      if token == token::Eof {
+        break;
+      }
+      actual.push_str(&format!(
+        "{:?} {}..{}\n",
+        token.kind,
+        token.span.lo().to_usize(),
+        token.span.hi().to_usize()
+      ));
+    }
+    actual
+  })
+}
+
+fn check_scanning(src: &str, expect: Expect) {
+  expect.assert_eq(&scan_all(src, LanguageVariant::Standard))
+}
+
+fn check_scanning_jsx(src: &str, expect: Expect) {
+  expect.assert_eq(&scan_all(src, LanguageVariant::JSX))
+}
+
+#[test]
+fn smoke_test() {
+  check_scanning(
+    "#!/usr/bin/env node\nfoo(bar, $baz); // done",
+    expect![[r#"
+        Trivia(Shebang) 0..19
+        Trivia(NewLine) 19..20
+        Ident("foo") 20..23
+        OpenDelim(Paren) 23..24
+        Ident("bar") 24..27
+        Comma 27..28
+        Trivia(Whitespace) 28..29
+        Ident("$baz") 29..33
+        CloseDelim(Paren) 33..34
+        Semi 34..35
+        Trivia(Whitespace) 35..36
+        Trivia(SingleLineComment) 36..43
+    "#]],
+  )
+}
+
+#[test]
+fn punctuators() {
+  check_scanning(
+    "{ } [ ] ... . ; , ?. ? : @ ~ => = == === != !== !",
+    expect![[r#"
+        OpenDelim(Brace) 0..1
+        Trivia(Whitespace) 1..2
+        CloseDelim(Brace) 2..3
+        Trivia(Whitespace) 3..4
+        OpenDelim(Bracket) 4..5
+        Trivia(Whitespace) 5..6
+        CloseDelim(Bracket) 6..7
+        Trivia(Whitespace) 7..8
+        DotDotDot 8..11
+        Trivia(Whitespace) 11..12
+        Dot 12..13
+        Trivia(Whitespace) 13..14
+        Semi 14..15
+        Trivia(Whitespace) 15..16
+        Comma 16..17
+        Trivia(Whitespace) 17..18
+        QuestionDot 18..20
+        Trivia(Whitespace) 20..21
+        Question 21..22
+        Trivia(Whitespace) 22..23
+        Colon 23..24
+        Trivia(Whitespace) 24..25
+        At 25..26
+        Trivia(Whitespace) 26..27
+        Tilde 27..28
+        Trivia(Whitespace) 28..29
+        Arrow 29..31
+        Trivia(Whitespace) 31..32
+        Eq 32..33
+        Trivia(Whitespace) 33..34
+        EqEq 34..36
+        Trivia(Whitespace) 36..37
+        EqEqEq 37..40
+        Trivia(Whitespace) 40..41
+        NotEq 41..43
+        Trivia(Whitespace) 43..44
+        NotEqEq 44..47
+        Trivia(Whitespace) 47..48
+        Not 48..49
+    "#]],
+  )
+}
+
+#[test]
+fn operators() {
+  let mut scanner_src = String::new();
+  for op in &[
+    "+", "+=", "++", "-", "-=", "--", "*", "*=", "**", "**=", "/", "/=", "%", "%=", "<", "<=",
+    "<<", "<<=", ">", ">=", ">>", ">>=", ">>>", ">>>=", "&", "&=", "&&", "&&=", "|", "|=", "||",
+    "||=", "^", "^=", "??", "??=",
+  ] {
+    scanner_src.push_str(op);
+    scanner_src.push(' ');
+  }
+  let actual = scan_all(&scanner_src, LanguageVariant::Standard);
+  let kinds: Vec<&str> = actual
+    .lines()
+    .filter(|line| !line.starts_with("Trivia"))
+    .map(|line| line.split(' ').next().unwrap())
+    .collect();
+  expect![[r#"
+      BinOp(Plus)
+      BinOpEq(Plus)
+      PlusPlus
+      BinOp(Minus)
+      BinOpEq(Minus)
+      MinusMinus
+      BinOp(Star)
+      BinOpEq(Star)
+      BinOp(StarStar)
+      BinOpEq(StarStar)
+      BinOp(Slash)
+      BinOpEq(Slash)
+      BinOp(Percent)
+      BinOpEq(Percent)
+      Lt
+      Le
+      BinOp(Shl)
+      BinOpEq(Shl)
+      Gt
+      Ge
+      BinOp(Shr)
+      BinOpEq(Shr)
+      BinOp(Ushr)
+      BinOpEq(Ushr)
+      BinOp(And)
+      BinOpEq(And)
+      BinOp(AndAnd)
+      BinOpEq(AndAnd)
+      BinOp(Or)
+      BinOpEq(Or)
+      BinOp(OrOr)
+      BinOpEq(OrOr)
+      BinOp(Caret)
+      BinOpEq(Caret)
+      BinOp(QuestionQuestion)
+      BinOpEq(QuestionQuestion)
+  "#]]
+  .assert_eq(&(kinds.join("\n") + "\n"));
+}
+
+#[test]
+fn question_dot_before_digit() {
+  check_scanning(
+    "a?.5:b",
+    expect![[r#"
+        Ident("a") 0..1
+        Question 1..2
+        Literal(Lit { kind: Num, symbol: ".5", suffix: None }) 2..4
+        Colon 4..5
+        Ident("b") 5..6
+    "#]],
+  )
+}
+
+#[test]
+fn comments() {
+  check_scanning(
+    "/* block\n comment */a// line\r\nb",
+    expect![[r#"
+        Trivia(MultiLineComment) 0..20
+        Ident("a") 20..21
+        Trivia(SingleLineComment) 21..28
+        Trivia(NewLine) 28..30
+        Ident("b") 30..31
+    "#]],
+  )
+}
+
+#[test]
+fn skip_trivia() {
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new("a /* c */\n  b", ScriptTarget::ESNext, BytePos(0));
+    scanner.set_skip_trivia();
+    assert_eq!(scanner.scan(), TokenKind::Ident(Symbol::intern("a")));
+    assert!(!scanner.has_preceding_line_break());
+    assert_eq!(scanner.scan(), TokenKind::Ident(Symbol::intern("b")));
+    assert!(scanner.has_preceding_line_break());
+    assert_eq!(scanner.scan(), token::Eof);
+  })
+}
+
+#[test]
+fn identifiers() {
+  check_scanning(
+    "_private $ café ಠ_ಠ",
+    expect![[r#"
+        Ident("_private") 0..8
+        Trivia(Whitespace) 8..9
+        Ident("$") 9..10
+        Trivia(Whitespace) 10..11
+        Ident("café") 11..16
+        Trivia(Whitespace) 16..17
+        Ident("ಠ_ಠ") 17..24
+    "#]],
+  )
+}
+
+#[test]
+fn private_names() {
+  check_errors(
+    "#a #\\u0062 # #1",
+    expect![[r##"
+        PrivateIdent("#a") 0..2 flags=0b0
+        Trivia(Whitespace) 2..3 flags=0b0
+        PrivateIdent("#b") 3..10 flags=0b10000000000
+        Trivia(Whitespace) 10..11 flags=0b0
+        Unknown 11..12 flags=0b0
+        Trivia(Whitespace) 12..13 flags=0b0
+        Unknown 13..14 flags=0b0
+        Literal(Lit { kind: Num, symbol: "1", suffix: None }) 14..15 flags=0b0
+        error 11..12: Invalid character.
+        error 13..14: Invalid character.
+    "##]],
+  )
+}
+
+#[test]
+fn less_than_slash_in_jsx() {
+  check_scanning(
+    "a</b>",
+    expect![[r#"
+        Ident("a") 0..1
+        Lt 1..2
+        BinOp(Slash) 2..3
+        Ident("b") 3..4
+        Gt 4..5
+    "#]],
+  );
+  check_scanning_jsx(
+    "a</b></*c*/",
+    expect![[r#"
+        Ident("a") 0..1
+        LtSlash 1..3
+        Ident("b") 3..4
+        Gt 4..5
+        Lt 5..6
+        Trivia(MultiLineComment) 6..11
+    "#]],
+  );
+}
+
+fn check_number(src: &str, expect: Expect) {
+  let actual = with_default_session_globals(|| {
+    let mut scanner = Scanner::new(src, ScriptTarget::ESNext, BytePos(0));
+    let token = scanner.scan();
+    let mut actual = match token.kind {
+      token::Literal(lit) => format!("{:?} {:?} {:?}", lit.kind, lit.symbol, lit.suffix),
+      kind => format!("{:?}", kind),
+    };
+    actual.push_str(&format!(
+      " {}..{} flags={:#b}\n",
+      token.span.lo().to_usize(),
+      token.span.hi().to_usize(),
+      scanner.token_flags()
+    ));
+    for err in scanner.take_errors() {
+      actual.push_str(&format!(
+        "error {}..{}: {}\n",
+        err.span.lo().to_usize(),
+        err.span.hi().to_usize(),
+        err.msg
+      ));
+    }
+    actual
+  });
+  expect.assert_eq(&actual)
+}
+
+#[test]
+fn numeric_literals() {
+  check_number(
+    "123",
+    expect![[r#"
+        Num "123" None 0..3 flags=0b0
+    "#]],
+  );
+  check_number(
+    "1.5e+10",
+    expect![[r#"
+        Num "1.5e+10" None 0..7 flags=0b10000
+    "#]],
+  );
+  check_number(
+    ".5",
+    expect![[r#"
+        Num ".5" None 0..2 flags=0b0
+    "#]],
+  );
+  check_number(
+    "1.",
+    expect![[r#"
+        Num "1." None 0..2 flags=0b0
+    "#]],
+  );
+  check_number(
+    "0xFF_ff",
+    expect![[r#"
+        Num "0xFF_ff" None 0..7 flags=0b1001000000
+    "#]],
+  );
+  check_number(
+    "0B1010",
+    expect![[r#"
+        Num "0B1010" None 0..6 flags=0b10000000
+    "#]],
+  );
+  check_number(
+    "0o777",
+    expect![[r#"
+        Num "0o777" None 0..5 flags=0b100000000
+    "#]],
+  );
+  check_number(
+    "0777",
+    expect![[r#"
+        Num "0777" None 0..4 flags=0b100000
+    "#]],
+  );
+  check_number(
+    "089.5",
+    expect![[r#"
+        Num "089.5" None 0..5 flags=0b0
+    "#]],
+  );
+  check_number(
+    "1_000_000",
+    expect![[r#"
+        Num "1_000_000" None 0..9 flags=0b1000000000
+    "#]],
+  );
+}
+
+#[test]
+fn bigint_literals() {
+  check_number(
+    "123n",
+    expect![[r#"
+        BigInt "123" Some("n") 0..4 flags=0b0
+    "#]],
+  );
+  check_number(
+    "0x1fn",
+    expect![[r#"
+        BigInt "0x1f" Some("n") 0..5 flags=0b1000000
+    "#]],
+  );
+  check_number(
+    "1.5n",
+    expect![[r#"
+        Num "1.5" None 0..4 flags=0b0
+        error 0..4: A bigint literal must be an integer.
+    "#]],
+  );
+  check_number(
+    "1e3n",
+    expect![[r#"
+        Num "1e3" None 0..4 flags=0b10000
+        error 0..4: A bigint literal cannot use exponential notation.
+    "#]],
+  );
+  check_number(
+    "07n",
+    expect![[r#"
+        Num "07" None 0..2 flags=0b100000
+        error 2..3: An identifier or keyword cannot immediately follow a numeric literal.
+    "#]],
+  );
+}
+
+#[test]
+fn malformed_numeric_literals() {
+  check_number(
+    "1__0",
+    expect![[r#"
+        Num "1__0" None 0..4 flags=0b1000000000
+        error 2..3: Multiple consecutive numeric separators are not permitted.
+    "#]],
+  );
+  check_number(
+    "1_",
+    expect![[r#"
+        Num "1_" None 0..2 flags=0b1000000000
+        error 1..2: Numeric separators are not allowed here.
+    "#]],
+  );
+  check_number(
+    "0x_1",
+    expect![[r#"
+        Num "0x_1" None 0..4 flags=0b1001000000
+        error 2..3: Numeric separators are not allowed here.
+    "#]],
+  );
+  check_number(
+    "0x",
+    expect![[r#"
+        Num "0x" None 0..2 flags=0b1000000
+        error 2..2: Hexadecimal digit expected.
+    "#]],
+  );
+  check_number(
+    "0b12",
+    expect![[r#"
+        Num "0b1" None 0..3 flags=0b10000000
+    "#]],
+  );
+  check_number(
+    "1e",
+    expect![[r#"
+        Num "1e" None 0..2 flags=0b10000
+        error 2..2: Digit expected.
+    "#]],
+  );
+  check_number(
+    "3in",
+    expect![[r#"
+        Num "3" None 0..1 flags=0b0
+        error 1..3: An identifier or keyword cannot immediately follow a numeric literal.
+    "#]],
+  );
+}
+
+#[test]
+fn numeric_literal_values() {
+  assert_eq!(num_lit_value("123"), 123.0);
+  assert_eq!(num_lit_value("1_000.5e-1"), 100.05);
+  assert_eq!(num_lit_value(".5"), 0.5);
+  assert_eq!(num_lit_value("1."), 1.0);
+  assert_eq!(num_lit_value("0xff"), 255.0);
+  assert_eq!(num_lit_value("0b1010"), 10.0);
+  assert_eq!(num_lit_value("0O17"), 15.0);
+  assert_eq!(num_lit_value("0777"), 511.0);
+  assert_eq!(num_lit_value("089"), 89.0);
+  assert_eq!(num_lit_value("0.1"), 0.1);
+  assert_eq!(num_lit_value("1e400"), f64::INFINITY);
+  // Integers beyond 2^53 round to nearest, ties to even.
+  assert_eq!(num_lit_value("9007199254740993"), 9007199254740992.0);
+  assert_eq!(num_lit_value("0x20000000000001"), 9007199254740992.0);
+  assert_eq!(num_lit_value("0x20000000000003"), 9007199254740996.0);
+  assert_eq!(num_lit_value("0x200000000000011"), 144115188075855904.0);
+  assert_eq!(num_lit_value("0x1fffffffffffff8000000000000000000000001"), 2f64.powi(153));
+  assert_eq!(num_lit_value("0x1fffffffffffff80000000000000000"), 2f64.powi(121));
+  assert_eq!(num_lit_value(&format!("0x{}", "f".repeat(256))), f64::INFINITY);
+}
+
+fn template_to_string(scanner: &Scanner<'_>, token: &Token) -> String {
+  let mut actual = match token.kind {
+    token::Literal(lit) => format!("{:?} raw={:?}", lit.kind, lit.symbol),
+    ref kind => format!("{:?}", kind),
+  };
+  actual.push_str(&format!(
+    " cooked={:?} {}..{} flags={:#b}\n",
+    scanner.token_value(),
+    token.span.lo().to_usize(),
+    token.span.hi().to_usize(),
+    scanner.token_flags()
+  ));
+  actual
+}
+
+/// Scans a template, rescanning every `}` as its continuation.
+fn check_template(src: &str, is_tagged: bool, expect: Expect) {
+  let actual = with_default_session_globals(|| {
+    let mut scanner = Scanner::new(src, ScriptTarget::ESNext, BytePos(0));
+    scanner.set_skip_trivia();
+    let mut actual = String::new();
+    loop {
+      let mut token = scanner.scan();
+      if token == token::Eof {
+        break;
+      }
+      if token == token::CloseDelim(token::Brace) {
+        token = scanner.rescan_template_token(is_tagged);
+      }
+      actual.push_str(&template_to_string(&scanner, &token));
+    }
+    for err in scanner.take_errors() {
+      actual.push_str(&format!(
+        "error {}..{}: {}\n",
+        err.span.lo().to_usize(),
+        err.span.hi().to_usize(),
+        err.msg
+      ));
+    }
+    actual
+  });
+  expect.assert_eq(&actual)
+}
+
+#[test]
+fn templates() {
+  check_template(
+    "`abc`",
+    false,
+    expect![[r#"
+        Template(NoSubstitution) raw="abc" cooked=Some("abc") 0..5 flags=0b0
+    "#]],
+  );
+  check_template(
+    "`a${ b }c${d}`",
+    false,
+    expect![[r#"
+        Template(Head) raw="a" cooked=Some("a") 0..4 flags=0b0
+        Ident("b") cooked=None 5..6 flags=0b0
+        Template(Middle) raw="c" cooked=Some("c") 7..11 flags=0b0
+        Ident("d") cooked=None 11..12 flags=0b0
+        Template(Tail) raw="" cooked=Some("") 12..14 flags=0b0
+    "#]],
+  );
+  check_template(
+    "`${`${a}`}`",
+    false,
+    expect![[r#"
+        Template(Head) raw="" cooked=Some("") 0..3 flags=0b0
+        Template(Head) raw="" cooked=Some("") 3..6 flags=0b0
+        Ident("a") cooked=None 6..7 flags=0b0
+        Template(Tail) raw="" cooked=Some("") 7..9 flags=0b0
+        Template(Tail) raw="" cooked=Some("") 9..11 flags=0b0
+    "#]],
+  );
+}
+
+#[test]
+fn template_escapes() {
+  check_template(
+    r"`\x41\u0042\u{43}\uD83D\uDE00\$\n`",
+    false,
+    expect![[r#"
+        Template(NoSubstitution) raw="\\x41\\u0042\\u{43}\\uD83D\\uDE00\\$\\n" cooked=Some("ABC😀$\n") 0..34 flags=0b10000001000
+    "#]],
+  );
+  check_template(
+    "`a\r\nb\\\r\nc`",
+    false,
+    expect![[r#"
+        Template(NoSubstitution) raw="a\nb\\\nc" cooked=Some("a\nbc") 0..10 flags=0b0
+    "#]],
+  );
+}
+
+#[test]
+fn template_invalid_escapes() {
+  // Allowed in tagged templates, which have no cooked value then.
+  check_template(
+    r"`\unicode${a}\01`",
+    true,
+    expect![[r#"
+        Template(Head) raw="\\unicode" cooked=None 0..11 flags=0b110000000000
+        Ident("a") cooked=None 11..12 flags=0b0
+        Template(Tail) raw="\\01" cooked=None 12..17 flags=0b100000000000
+    "#]],
+  );
+  check_template(
+    r"`${a}\xz\u{110000}`",
+    false,
+    expect![[r#"
+        Template(Head) raw="" cooked=Some("") 0..3 flags=0b0
+        Ident("a") cooked=None 3..4 flags=0b0
+        Template(Tail) raw="\\xz\\u{110000}" cooked=None 4..19 flags=0b100000001000
+        error 5..7: Hexadecimal digit expected.
+        error 8..17: An extended Unicode escape value must be between 0x0 and 0x10FFFF inclusive.
+    "#]],
+  );
+
+  // Invalid escapes of the first token are only reported by a rescan.
+  let actual = with_default_session_globals(|| {
+    let mut scanner = Scanner::new(r"`\u{`", ScriptTarget::ESNext, BytePos(0));
+    let token = scanner.scan();
+    let mut actual = template_to_string(&scanner, &token);
+    assert!(scanner.take_errors().is_empty());
+    let token = scanner.rescan_template_head_or_no_substitution_template();
+    actual.push_str(&template_to_string(&scanner, &token));
+    for err in scanner.take_errors() {
+      actual.push_str(&format!("error: {}\n", err.msg));
+    }
+    actual
+  });
+  expect![[r#"
+      Template(NoSubstitution) raw="\\u{" cooked=None 0..5 flags=0b100000001000
+      Template(NoSubstitution) raw="\\u{" cooked=None 0..5 flags=0b100000001000
+      error: Hexadecimal digit expected.
+  "#]]
+  .assert_eq(&actual);
+}
+
+#[test]
+fn unterminated_template() {
+  check_template(
+    "`abc${d",
+    false,
+    expect![[r#"
+        Template(Head) raw="abc" cooked=Some("abc") 0..6 flags=0b0
+        Ident("d") cooked=None 6..7 flags=0b0
+    "#]],
+  );
+  check_template(
+    "`abc",
+    false,
+    expect![[r#"
+        Template(NoSubstitution) raw="abc" cooked=Some("abc") 0..4 flags=0b100
+        error 4..4: Unterminated template literal.
+    "#]],
+  );
+}
+
+fn check_regexp(src: &str, lang_version: ScriptTarget, expect: Expect) {
+  let actual = with_default_session_globals(|| {
+    let mut scanner = Scanner::new(src, lang_version, BytePos(0));
+    let token = scanner.scan();
+    let token = scanner.rescan_slash_token(token);
+    let mut actual = format!(
+      "{:?} {}..{} flags={:#b}\n",
+      token.kind,
+      token.span.lo().to_usize(),
+      token.span.hi().to_usize(),
+      scanner.token_flags()
+    );
+    for err in scanner.take_errors() {
+      actual.push_str(&format!(
+        "error {}..{}: {}\n",
+        err.span.lo().to_usize(),
+        err.span.hi().to_usize(),
+        err.msg
+      ));
+    }
+    actual
+  });
+  expect.assert_eq(&actual)
+}
+
+#[test]
+fn regexp_literals() {
+  check_regexp(
+    r"/[/\]]+\/(a|b)/gimsuy.test(x)",
+    ScriptTarget::ESNext,
+    expect![[r#"
+        Literal(Lit { kind: RegExp, symbol: "[/\\]]+\\/(a|b)", suffix: Some("gimsuy") }) 0..21 flags=0b0
+    "#]],
+  );
+  check_regexp(
+    "/=a/",
+    ScriptTarget::ESNext,
+    expect![[r#"
+        Literal(Lit { kind: RegExp, symbol: "=a", suffix: None }) 0..4 flags=0b0
+    "#]],
+  );
+  check_regexp(
+    "/a\\/\nb/",
+    ScriptTarget::ESNext,
+    expect![[r#"
+        Literal(Lit { kind: RegExp, symbol: "a\\/", suffix: None }) 0..4 flags=0b100
+        error 0..4: Unterminated regular expression literal.
+    "#]],
+  );
+}
+
+#[test]
+fn regexp_validation() {
+  check_regexp(
+    "/(a(b)/gxg",
+    ScriptTarget::ESNext,
+    expect![[r#"
+        Literal(Lit { kind: RegExp, symbol: "(a(b)", suffix: Some("gxg") }) 0..10 flags=0b0
+        error 1..2: Unterminated group, ')' expected.
+        error 8..9: Unknown regular expression flag.
+        error 9..10: Duplicate regular expression flag.
+    "#]],
+  );
+  check_regexp(
+    r"/a)[)]\)/uv",
+    ScriptTarget::ESNext,
+    expect![[r#"
+        Literal(Lit { kind: RegExp, symbol: "a)[)]\\)", suffix: Some("uv") }) 0..11 flags=0b0
+        error 2..3: Unexpected ')'. Did you mean to escape it with backslash?
+        error 10..11: The Unicode (u) flag and the Unicode Sets (v) flag cannot be set simultaneously.
+    "#]],
+  );
+  check_regexp(
+    "/a/suyd",
+    ScriptTarget::ES5,
+    expect![[r#"
+        Literal(Lit { kind: RegExp, symbol: "a", suffix: Some("suyd") }) 0..7 flags=0b0
+        error 3..4: This regular expression flag is only available when targeting 'es2018' or later.
+        error 4..5: This regular expression flag is only available when targeting 'es2015' or later.
+        error 5..6: This regular expression flag is only available when targeting 'es2015' or later.
+        error 6..7: This regular expression flag is only available when targeting 'esnext' or later.
+    "#]],
+  );
+}
+
+#[test]
+fn rescan_non_slash_token() {
+  check_regexp(
+    "a/b/",
+    ScriptTarget::ESNext,
+    expect![[r#"
+        Ident("a") 0..1 flags=0b0
+    "#]],
+  );
+}
+
+#[test]
+fn jsx_tokens() {
+  let actual = with_default_session_globals(|| {
+    let mut scanner = Scanner::new("<a>x &amp; y</a>{}\n  \n< ", ScriptTarget::ESNext, BytePos(0));
+    scanner.set_lang_variant(LanguageVariant::JSX);
+    let mut actual = String::new();
+    loop {
+      let token = scanner.scan_jsx_token(true);
+      if token == token::Eof {
+        break;
+      }
+      actual.push_str(&format!(
+        "{:?} {}..{} value={:?}\n",
+        token.kind,
+        token.span.lo().to_usize(),
+        token.span.hi().to_usize(),
+        scanner.token_value()
+      ));
+      match token.kind {
+        token::Lt | token::LtSlash => {
+          scanner.scan();
+          scanner.scan();
+        }
+        token::OpenDelim(token::Brace) => {
+          scanner.scan();
+        }
+        _ => (),
+      }
+    }
+    actual
+  });
+  expect![[r#"
+      Lt 0..1 value=None
+      JsxText("x &amp; y") 3..12 value=Some("x & y")
+      LtSlash 12..14 value=None
+      OpenDelim(Brace) 16..17 value=None
+      JsxTextAllWhiteSpaces("\n  \n") 18..22 value=Some("\n  \n")
+      Lt 22..23 value=None
+  "#]]
+  .assert_eq(&actual);
+}
+
+#[test]
+fn rescan_jsx_token() {
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new("> a = b</c>", ScriptTarget::ESNext, BytePos(0));
+    scanner.set_skip_trivia();
+    scanner.set_lang_variant(LanguageVariant::JSX);
+    assert_eq!(scanner.scan(), token::Gt);
+    assert_eq!(scanner.scan(), token::Ident(Symbol::intern("a")));
+    let token = scanner.rescan_jsx_token(BytePos(1), true);
+    assert_eq!(token.kind, token::JsxText(Symbol::intern(" a = b")));
+    assert_eq!(token.span, Span::new(BytePos(1), BytePos(7)));
+    assert_eq!(scanner.scan_jsx_token(true), token::LtSlash);
+  })
+}
+
+#[test]
+fn jsx_text_errors() {
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new("a > b } c\nd", ScriptTarget::ESNext, BytePos(0));
+    let token = scanner.scan_jsx_token(false);
+    assert_eq!(token.kind, token::JsxText(Symbol::intern("a > b } c")));
+    let errors: Vec<_> = scanner.take_errors().into_iter().map(|err| err.msg).collect();
+    assert_eq!(
+      errors,
+      [
+        "Unexpected token. Did you mean `{'>'}` or `&gt;`?",
+        "Unexpected token. Did you mean `{'}'}` or `&rbrace;`?"
+      ]
+    );
+  })
+}
+
+#[test]
+fn jsx_identifiers() {
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new("data-foo-=svg:rect:x a: ", ScriptTarget::ESNext, BytePos(0));
+    let mut names = Vec::new();
+    loop {
+      let token = scanner.scan();
+      if token == token::Eof {
+        break;
+      }
+      let token = scanner.scan_jsx_identifier(token);
+      names.push(format!("{:?}", token.kind));
+    }
+    expect![[r#"
+        Ident("data-foo-") Eq Ident("svg:rect") Colon Ident("x") Trivia(Whitespace) Ident("a") Colon Trivia(Whitespace)"#]]
+    .assert_eq(&names.join(" "));
+  })
+}
+
+#[test]
+fn jsx_attribute_values() {
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new("\"a\\&lt;\nb\" {x}", ScriptTarget::ESNext, BytePos(0));
+    scanner.set_skip_trivia();
+    let token = scanner.scan_jsx_attribute_value();
+    let lit = Lit::new(LitKind::Str(token::QuoteKind::Double), Symbol::intern("a\\&lt;\nb"), None);
+    assert_eq!(token, token::Literal(lit));
+    assert_eq!(scanner.token_value(), Some(Symbol::intern("a\\<\nb")));
+    assert_eq!(scanner.scan_jsx_attribute_value(), token::OpenDelim(token::Brace));
+
+    let mut scanner = Scanner::new("'abc", ScriptTarget::ESNext, BytePos(0));
+    let token = scanner.scan_jsx_attribute_value();
+    assert_eq!(token.span.hi().to_usize(), 4);
+    assert_eq!(scanner.token_flags(), TokenFlags::Unterminated);
+    assert_eq!(scanner.take_errors()[0].msg, "Unterminated string literal.");
+  })
+}
+
+#[test]
+fn html_entities() {
+  assert_eq!(decode_entities("&lt;&gt;&amp;&apos;&quot;&nbsp;&hearts;"), "<>&'\"\u{a0}♥");
+  assert_eq!(decode_entities("&#65;&#x42;&#x1F600;"), "AB😀");
+  assert_eq!(decode_entities("&unknown; & &; &#xZZ; &amp"), "&unknown; & &; &#xZZ; &amp");
+}
+
+#[test]
+fn jsdoc_tokens() {
+  let actual = with_default_session_globals(|| {
+    let src = "@param {Array<string>} foo-bar - the `x`\r\n * [a=1].";
+    let mut scanner = Scanner::new(src, ScriptTarget::ESNext, BytePos(0));
+    let mut actual = String::new();
+    loop {
+      let token = scanner.scan_jsdoc_token();
+      if token == token::Eof {
+        break;
+      }
+      actual.push_str(&format!("{:?} ", token.kind));
+    }
+    actual
+  });
+  expect![[r#"At Ident("param") Trivia(Whitespace) OpenDelim(Brace) Ident("Array") Lt Ident("string") Gt CloseDelim(Brace) Trivia(Whitespace) Ident("foo-bar") Trivia(Whitespace) Unknown Trivia(Whitespace) Ident("the") Trivia(Whitespace) Backtick Ident("x") Backtick Trivia(NewLine) Trivia(Whitespace) BinOp(Star) Trivia(Whitespace) OpenDelim(Bracket) Ident("a") Eq Unknown CloseDelim(Bracket) Dot "#]]
+    .assert_eq(&actual);
+}
+
+#[test]
+fn preceding_jsdoc_comment() {
+  with_default_session_globals(|| {
+    let mut scanner =
+      Scanner::new("/** doc */ a /**/ b /* c */ c", ScriptTarget::ESNext, BytePos(0));
+    scanner.set_skip_trivia();
+    for _ in 0..3 {
+      scanner.scan();
+      let is_jsdoc = scanner.token_flags() & TokenFlags::PrecedingJSDocComment != 0;
+      assert_eq!(is_jsdoc, scanner.token_start == BytePos(11));
+    }
+  })
+}
+
+#[test]
+fn escaped_identifiers() {
+  with_default_session_globals(|| {
+    let mut scanner =
+      Scanner::new(r"\u0061bc a\u{62}c \u0030 a\u", ScriptTarget::ESNext, BytePos(0));
+    scanner.set_skip_trivia();
+    assert_eq!(scanner.scan(), TokenKind::Ident(Symbol::intern("abc")));
+    assert_eq!(scanner.token_flags(), TokenFlags::UnicodeEscape);
+    assert_eq!(scanner.scan(), TokenKind::Ident(Symbol::intern("abc")));
+    assert_eq!(scanner.token_flags(), TokenFlags::ExtendedUnicodeEscape);
+    // A digit can't start an identifier, escaped or not.
+    assert_eq!(scanner.scan(), token::Unknown);
+    assert_eq!(scanner.scan(), TokenKind::Ident(Symbol::intern("u0030")));
+    assert_eq!(scanner.scan(), TokenKind::Ident(Symbol::intern("a")));
+    assert_eq!(scanner.scan(), token::Unknown);
+  })
+}
+
+#[test]
+fn identifier_tables_by_target() {
+  // U+0370 is a letter since Unicode 5.1, U+13F8 since Unicode 8.0.
+  assert!(!is_identifier_start('\u{370}', ScriptTarget::ES3));
+  assert!(is_identifier_start('\u{370}', ScriptTarget::ES5));
+  assert!(!is_identifier_start('\u{13F8}', ScriptTarget::ES5));
+  assert!(is_identifier_start('\u{13F8}', ScriptTarget::ES2015));
+  // Astral characters are surrogate pairs before ES2015.
+  assert!(!is_identifier_start('\u{10000}', ScriptTarget::ES5));
+  assert!(is_identifier_start('\u{10000}', ScriptTarget::ESNext));
+  assert!(is_identifier_part('\u{300}', ScriptTarget::ES3));
+  assert!(!is_identifier_start('\u{300}', ScriptTarget::ES3));
+
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new("a\u{13F8}", ScriptTarget::ES5, BytePos(0));
+    assert_eq!(scanner.scan(), TokenKind::Ident(Symbol::intern("a")));
+    assert_eq!(scanner.scan(), token::Unknown);
+  })
+}
+
+#[test]
+fn look_ahead_and_try_scan() {
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new("(a) => 1 ", ScriptTarget::ESNext, BytePos(0));
+    scanner.set_skip_trivia();
+    assert_eq!(scanner.scan(), token::OpenDelim(token::Paren));
+
+    let is_arrow = scanner.look_ahead(|scanner| {
+      scanner.scan().is_ident()
+        && scanner.scan() == token::CloseDelim(token::Paren)
+        && scanner.scan() == token::Arrow
+    });
+    assert!(is_arrow);
+    assert_eq!(scanner.scan().ident().map(|ident| ident.name), Some(Symbol::intern("a")));
+
+    let failed = scanner.try_scan(|scanner| {
+      scanner.set_lang_variant(LanguageVariant::JSX);
+      if scanner.scan() == token::Comma {
+        Some(())
+      } else {
+        None
+      }
+    });
+    assert_eq!(failed, None);
+    assert_eq!(scanner.lang_variant, LanguageVariant::Standard);
+
+    let close = scanner.try_scan(|scanner| Some(scanner.scan()));
+    assert_eq!(close.map(|token| token.kind), Some(token::CloseDelim(token::Paren)));
+    assert_eq!(scanner.scan(), token::Arrow);
+
+    let snapshot = scanner.snapshot();
+    let _ = scanner.scan();
+    let _ = scanner.scan();
+    scanner.restore(snapshot);
+    assert!(scanner.scan().is_lit());
+    assert_eq!(scanner.scan(), token::Eof);
+  })
+}
+
+#[test]
+fn rescan_first_char_token() {
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new("a<b<c>>= 1", ScriptTarget::ESNext, BytePos(0));
+    scanner.set_skip_trivia();
+    for _ in 0..5 {
+      let _ = scanner.scan();
+    }
+    let span = |token: &Token| (token.span.lo().to_usize(), token.span.hi().to_usize());
+
+    let token = scanner.scan();
+    assert_eq!(token, token::BinOpEq(token::Shr));
+    let token = scanner.rescan_first_char_token(token);
+    assert_eq!((token.kind.clone(), span(&token)), (token::Gt, (5, 6)));
+
+    let token = scanner.scan();
+    assert_eq!((token.kind.clone(), span(&token)), (token::Ge, (6, 8)));
+    let token = scanner.rescan_first_char_token(token);
+    assert_eq!((token.kind.clone(), span(&token)), (token::Gt, (6, 7)));
+
+    let token = scanner.scan();
+    assert_eq!((token.kind.clone(), span(&token)), (token::Eq, (7, 8)));
+    let token = scanner.rescan_first_char_token(token);
+    assert_eq!((token.kind.clone(), span(&token)), (token::Eq, (7, 8)));
+    assert!(scanner.scan().is_lit());
+    assert_eq!(scanner.scan(), token::Eof);
+  })
+}
+
+fn check_errors(src: &str, expect: Expect) {
+  let actual = with_default_session_globals(|| {
+    let mut errors = Vec::new();
+    let mut actual = String::new();
+    {
+      let mut scanner = Scanner::new(src, ScriptTarget::ESNext, BytePos(0));
+      scanner.set_on_error(|err| errors.push(err));
+      loop {
+        let token = scanner.scan();
+        if token == token::Eof {
+          break;
+        }
+        actual.push_str(&format!(
+          "{:?} {}..{} flags={:#b}\n",
+          token.kind,
+          token.span.lo().to_usize(),
+          token.span.hi().to_usize(),
+          scanner.token_flags()
+        ));
+      }
+    }
+    for err in errors {
+      actual.push_str(&format!(
+        "error {}..{}: {}\n",
+        err.span.lo().to_usize(),
+        err.span.hi().to_usize(),
+        err.msg
+      ));
+    }
+    actual
+  });
+  expect.assert_eq(&actual)
+}
+
+#[test]
+fn unterminated_tokens() {
+  check_errors(
+    "'abc\n\"",
+    expect![[r#"
+        Literal(Lit { kind: Str(Single), symbol: "abc", suffix: None }) 0..4 flags=0b100
+        Trivia(NewLine) 4..5 flags=0b1
+        Literal(Lit { kind: Str(Double), symbol: "", suffix: None }) 5..6 flags=0b100
+        error 0..4: Unterminated string literal.
+        error 5..6: Unterminated string literal.
+    "#]],
+  );
+  check_errors(
+    "/* abc",
+    expect![[r#"
+        Trivia(MultiLineComment) 0..6 flags=0b100
+        error 6..6: '*/' expected.
+    "#]],
+  );
+  check_errors(
+    "`abc",
+    expect![[r#"
+        Literal(Lit { kind: Template(NoSubstitution), symbol: "abc", suffix: None }) 0..4 flags=0b100
+        error 4..4: Unterminated template literal.
+    "#]],
+  );
+}
+
+#[test]
+fn invalid_characters_and_escapes() {
+  check_errors(
+    "a # \\u0030 \u{7}",
+    expect![[r#"
+        Ident("a") 0..1 flags=0b0
+        Trivia(Whitespace) 1..2 flags=0b0
+        Unknown 2..3 flags=0b0
+        Trivia(Whitespace) 3..4 flags=0b0
+        Unknown 4..5 flags=0b0
+        Ident("u0030") 5..10 flags=0b0
+        Trivia(Whitespace) 10..11 flags=0b0
+        Unknown 11..12 flags=0b0
+        error 2..3: Invalid character.
+        error 4..5: Invalid character.
+        error 11..12: Invalid character.
+    "#]],
+  );
+  check_errors(
+    r"'\x4' '\u{110000}' '\u12'",
+    expect![[r#"
+        Literal(Lit { kind: Str(Single), symbol: "\\x4", suffix: None }) 0..5 flags=0b100000000000
+        Trivia(Whitespace) 5..6 flags=0b0
+        Literal(Lit { kind: Str(Single), symbol: "\\u{110000}", suffix: None }) 6..18 flags=0b100000001000
+        Trivia(Whitespace) 18..19 flags=0b0
+        Literal(Lit { kind: Str(Single), symbol: "\\u12", suffix: None }) 19..25 flags=0b110000000000
+        error 1..4: Hexadecimal digit expected.
+        error 7..16: An extended Unicode escape value must be between 0x0 and 0x10FFFF inclusive.
+        error 20..24: Hexadecimal digit expected.
+    "#]],
+  );
+}
+
+#[test]
+fn unicode_whitespace() {
+  // U+2001 is a space separator, U+200B is neither whitespace nor part of an identifier.
+  check_errors(
+    "a\u{2001}b\u{200B}c",
+    expect![[r#"
+      Ident("a") 0..1 flags=0b0
+      Trivia(Whitespace) 1..4 flags=0b0
+      Ident("b") 4..5 flags=0b0
+      Unknown 5..8 flags=0b0
+      Ident("c") 8..9 flags=0b0
+      error 5..8: Invalid character.
+  "#]],
+  );
+}
+
+#[test]
+fn string_values() {
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new(
+      r"'a\n\x41\u{1F600}\101\08\9\
+b'",
+      ScriptTarget::ESNext,
+      BytePos(0),
+    );
+    let token = scanner.scan();
+    assert_eq!(token.span.hi().to_usize(), 30);
+    assert_eq!(scanner.token_value(), Some(Symbol::intern("a\nA\u{1F600}A\u{0}89b")));
+    assert!(scanner.take_errors().is_empty());
+  })
+}
+
+#[test]
+fn string_crlf_line_continuation() {
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new("'a\\\r\nb'", ScriptTarget::ESNext, BytePos(0));
+    let token = scanner.scan();
+    assert_eq!(token.span.hi().to_usize(), 7);
+    assert_eq!(scanner.token_value(), Some(Symbol::intern("ab")));
+    assert!(scanner.take_errors().is_empty());
+  })
+}
+
+#[test]
+fn errors_are_held_back_while_speculating() {
+  with_default_session_globals(|| {
+    let mut errors = Vec::new();
+    {
+      let mut scanner = Scanner::new("# 'a", ScriptTarget::ESNext, BytePos(0));
+      scanner.set_skip_trivia();
+      scanner.set_on_error(|err| errors.push(err.msg));
+      scanner.look_ahead(|scanner| scanner.scan());
+      scanner.try_scan(|scanner| Some(scanner.scan()));
+      scanner.try_scan(|scanner| {
+        scanner.scan();
+        None::<()>
+      });
+    }
+    assert_eq!(errors, ["Invalid character."]);
+  })
+}
+
+#[test]
+fn conflict_markers() {
+  check_errors(
+    "<<<<<<< HEAD\na;\n=======\nb;\n>>>>>>> branch\nc",
+    expect![[r#"
+        Trivia(ConflictMarker) 0..12 flags=0b0
+        Trivia(NewLine) 12..13 flags=0b1
+        Ident("a") 13..14 flags=0b0
+        Semi 14..15 flags=0b0
+        Trivia(NewLine) 15..16 flags=0b1
+        Trivia(ConflictMarker) 16..27 flags=0b0
+        Trivia(ConflictMarker) 27..41 flags=0b0
+        Trivia(NewLine) 41..42 flags=0b1
+        Ident("c") 42..43 flags=0b0
+        error 0..7: Merge conflict marker encountered.
+        error 16..23: Merge conflict marker encountered.
+        error 27..34: Merge conflict marker encountered.
+    "#]],
+  );
+  check_errors(
+    "<<<<<<< HEAD\na;\n||||||| base\nb;\n=======\nc;\n>>>>>>> branch\n",
+    expect![[r#"
+        Trivia(ConflictMarker) 0..12 flags=0b0
+        Trivia(NewLine) 12..13 flags=0b1
+        Ident("a") 13..14 flags=0b0
+        Semi 14..15 flags=0b0
+        Trivia(NewLine) 15..16 flags=0b1
+        Trivia(ConflictMarker) 16..32 flags=0b0
+        Trivia(ConflictMarker) 32..43 flags=0b0
+        Trivia(ConflictMarker) 43..57 flags=0b0
+        Trivia(NewLine) 57..58 flags=0b1
+        error 0..7: Merge conflict marker encountered.
+        error 16..23: Merge conflict marker encountered.
+        error 32..39: Merge conflict marker encountered.
+        error 43..50: Merge conflict marker encountered.
+    "#]],
+  );
+  // Not at the start of a line, or without the space.
+  check_errors(
+    "a <<<<<<< b\n>>>>>>>c",
+    expect![[r#"
+        Ident("a") 0..1 flags=0b0
+        Trivia(Whitespace) 1..2 flags=0b0
+        BinOp(Shl) 2..4 flags=0b0
+        BinOp(Shl) 4..6 flags=0b0
+        BinOp(Shl) 6..8 flags=0b0
+        Lt 8..9 flags=0b0
+        Trivia(Whitespace) 9..10 flags=0b0
+        Ident("b") 10..11 flags=0b0
+        Trivia(NewLine) 11..12 flags=0b1
+        BinOp(Ushr) 12..15 flags=0b0
+        BinOp(Ushr) 15..18 flags=0b0
+        Gt 18..19 flags=0b0
+        Ident("c") 19..20 flags=0b0
+    "#]],
+  );
+}
+
+#[test]
+fn conflict_marker_in_jsx_text() {
+  with_default_session_globals(|| {
+    let mut scanner = Scanner::new("<<<<<<< HEAD\n<a>", ScriptTarget::ESNext, BytePos(0));
+    let token = scanner.scan_jsx_token(true);
+    assert_eq!(token, Trivia(token::ConflictMarker));
+    assert_eq!(token.span.hi().to_usize(), 12);
+    assert_eq!(scanner.take_errors()[0].msg, "Merge conflict marker encountered.");
+  })
+}
@@ -1,8 +1,6 @@
-use tscore_common::ScriptKind;
-use tscore_common::ScriptTarget;
-
 mod context;
 pub mod lexer;
 pub mod parser;
 
+pub use context::{ContextFlags, TokenFlags};
 pub use parser::*;
@@ -1 +1,360 @@
+//! A recursive-descent parser building a [Module] from the tokens of the [Scanner].
+//!
+//! Like TypeScript's parser it never gives up: errors are reported and parsing
+//! goes on with what is there, so a full AST is produced for any input.
 
+mod class;
+mod decl;
+mod expr;
+mod func;
+mod jsx;
+mod list;
+mod pat;
+mod stmt;
+mod ty;
+
+#[cfg(test)]
+mod tests;
+
+use crate::context::{ContextFlags, ParsingContext};
+use crate::lexer::{ScanError, Scanner, ScannerSnapshot};
+
+use tscore_ast::token::{self, Token, TokenKind};
+use tscore_ast::Module;
+use tscore_common::path::is_declaration_file;
+use tscore_common::{LanguageVariant, ScriptKind, ScriptTarget};
+use tscore_span::symbol::{kw, Ident, Symbol};
+use tscore_span::{BytePos, Pos, Span};
+
+use std::mem;
+
+/// An error found while parsing, e.g. a missing `)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+  pub msg: String,
+  pub span: Span,
+}
+
+impl From<ScanError> for ParseError {
+  fn from(err: ScanError) -> Self {
+    ParseError { msg: err.msg, span: err.span }
+  }
+}
+
+/// Parses the text of a source file. With `ScriptKind::Unknown` the kind is taken
+/// from the extension of `file_name`, it decides whether JSX is allowed.
+///
+/// Returns the module along with the errors of both the scanner and the parser,
+/// ordered by position.
+pub fn parse_source_file(
+  file_name: &str,
+  text: &str,
+  lang_version: ScriptTarget,
+  script_kind: ScriptKind,
+) -> (Module, Vec<ParseError>) {
+  let script_kind = match script_kind {
+    ScriptKind::Unknown => ScriptKind::ensure_script_kind(file_name, None),
+    kind => kind,
+  };
+  let mut parser = Parser::new(text, lang_version, script_kind);
+  // Everything in a declaration file is ambient.
+  let context =
+    if is_declaration_file(file_name) { ContextFlags::Ambient } else { ContextFlags::None };
+  let module = parser.with_context(context, ContextFlags::None, |p| p.parse_module());
+  (module, parser.into_errors())
+}
+
+pub struct Parser<'a> {
+  src: &'a str,
+  scanner: Scanner<'a>,
+  lang_variant: LanguageVariant,
+  /// The [ContextFlags] in effect, see [Parser::with_context].
+  context: u8,
+  /// The lists being parsed, innermost last, see [Parser::parse_list].
+  parsing_contexts: Vec<ParsingContext>,
+  /// The current token.
+  pub token: Token,
+  /// The previous token.
+  pub prev_token: Token,
+  errors: Vec<ParseError>,
+  /// The `=` of shorthand properties with an initializer, `{ a = 1 }`, reported unless
+  /// the object literal turns out to be a destructuring pattern, see [Parser::expr_to_pat].
+  shorthand_inits: Vec<Span>,
+}
+
+/// A saved [Parser] state, see [Parser::look_ahead] and [Parser::try_parse].
+struct ParserSnapshot<'a> {
+  scanner: ScannerSnapshot<'a>,
+  token: Token,
+  prev_token: Token,
+  errors_len: usize,
+  shorthand_inits: Vec<Span>,
+}
+
+impl<'a> Parser<'a> {
+  pub fn new(src: &'a str, lang_version: ScriptTarget, script_kind: ScriptKind) -> Self {
+    let lang_variant = LanguageVariant::from(script_kind);
+    let mut scanner = Scanner::new(src, lang_version, BytePos(0));
+    scanner.set_skip_trivia();
+    scanner.set_lang_variant(lang_variant);
+    let token = scanner.scan();
+    Parser {
+      src,
+      scanner,
+      lang_variant,
+      context: ContextFlags::None,
+      parsing_contexts: Vec::new(),
+      token,
+      prev_token: Token::dummy(),
+      errors: Vec::new(),
+      shorthand_inits: Vec::new(),
+    }
+  }
+
+  /// Consumes the parser, returning the errors of the scanner and the parser by position.
+  pub fn into_errors(mut self) -> Vec<ParseError> {
+    for span in mem::take(&mut self.shorthand_inits) {
+      self.error(
+        span,
+        "An '=' can only follow a property name when the containing object literal is part of a destructuring pattern.",
+      );
+    }
+    let mut errors = self.errors;
+    errors.extend(self.scanner.take_errors().into_iter().map(ParseError::from));
+    errors.sort_by_key(|err| err.span.lo());
+    // Rescanning, e.g. the text between JSX tags, may report the same error again.
+    errors.dedup_by(|a, b| a.span == b.span && a.msg == b.msg);
+    errors
+  }
+
+  /// Advances to the next token.
+  pub fn bump(&mut self) {
+    let next = self.scanner.scan();
+    self.prev_token = mem::replace(&mut self.token, next);
+  }
+
+  /// Checks if the current token is `kind`.
+  pub fn check(&self, kind: &TokenKind) -> bool {
+    self.token == *kind
+  }
+
+  /// Consumes the current token if it is `kind`.
+  pub fn eat(&mut self, kind: &TokenKind) -> bool {
+    let present = self.check(kind);
+    if present {
+      self.bump();
+    }
+    present
+  }
+
+  /// Consumes the current token if it is `kind`, otherwise reports it as missing.
+  pub fn expect(&mut self, kind: &TokenKind) -> bool {
+    if self.eat(kind) {
+      return true;
+    }
+    self.error_at_token(&format!("'{}' expected.", kind));
+    false
+  }
+
+  /// Like [Parser::expect] for a `>`, which may be the first character of a token like
+  /// `>>` or `>=` when it closes type arguments, e.g. in `a as A<B<C>>`.
+  fn expect_gt(&mut self) -> bool {
+    self.check_gt();
+    self.expect(&token::Gt)
+  }
+
+  /// Checks if the current token is a `>`, splitting a token starting with one,
+  /// see [Parser::expect_gt].
+  fn check_gt(&mut self) -> bool {
+    if self.token.break_two_token_op().map_or(false, |(first, _)| first == token::Gt) {
+      self.token = self.scanner.rescan_first_char_token(self.token.clone());
+    }
+    self.check(&token::Gt)
+  }
+
+  /// Advances to the next token, scanned as between JSX tags, e.g. after the `>` of an
+  /// opening tag.
+  fn bump_jsx_text(&mut self) {
+    let next = self.scanner.scan_jsx_token(true);
+    self.prev_token = mem::replace(&mut self.token, next);
+  }
+
+  /// Checks if the current token is the keyword `kw`. Keywords are scanned as
+  /// identifiers, so contextual ones like `of` are checked the same way.
+  pub fn check_keyword(&self, kw: Symbol) -> bool {
+    self.token.is_ident_named(kw)
+  }
+
+  /// Consumes the current token if it is the keyword `kw`.
+  pub fn eat_keyword(&mut self, kw: Symbol) -> bool {
+    let present = self.check_keyword(kw);
+    if present {
+      self.bump();
+    }
+    present
+  }
+
+  /// Consumes the current token if it is the keyword `kw`, otherwise reports it as missing.
+  pub fn expect_keyword(&mut self, kw: Symbol) -> bool {
+    if self.eat_keyword(kw) {
+      return true;
+    }
+    self.error_at_token(&format!("'{}' expected.", kw));
+    false
+  }
+
+  /// Whether there is a line break between the previous and the current token.
+  pub fn has_preceding_line_break(&self) -> bool {
+    self.scanner.has_preceding_line_break()
+  }
+
+  /// Whether a statement may end here, either with a `;` or by automatic semicolon
+  /// insertion, i.e. before a `}`, at the end of the file or after a line break.
+  fn can_parse_semicolon(&self) -> bool {
+    self.check(&token::Semi)
+      || self.check(&token::CloseDelim(token::Brace))
+      || self.check(&token::Eof)
+      || self.has_preceding_line_break()
+  }
+
+  /// Ends a statement, see [Parser::can_parse_semicolon].
+  fn expect_semicolon(&mut self) -> bool {
+    if self.can_parse_semicolon() {
+      self.eat(&token::Semi);
+      return true;
+    }
+    self.expect(&token::Semi)
+  }
+
+  /// Whether the current token is an identifier that can name a binding, i.e. any
+  /// identifier but the reserved words, and `yield` and `await` where they are operators.
+  fn is_binding_ident(&self) -> bool {
+    match self.token.ident() {
+      Some(ident) if ident.name == kw::Yield => !self.in_context(ContextFlags::Yield),
+      Some(ident) if ident.name == kw::Await => !self.in_context(ContextFlags::Await),
+      Some(ident) => !ident.is_reserved(),
+      None => false,
+    }
+  }
+
+  /// Parses an identifier name, where reserved words are allowed too, e.g. after a `.`.
+  fn parse_ident_name(&mut self) -> Ident {
+    match self.token.ident() {
+      Some(ident) => {
+        self.bump();
+        ident
+      }
+      None => {
+        self.error_at_token("Identifier expected.");
+        Ident::new(kw::Empty, self.token.span.shrink_to_lo())
+      }
+    }
+  }
+
+  /// Parses an identifier, see [Parser::parse_ident_or].
+  pub fn parse_ident(&mut self) -> Ident {
+    self.parse_ident_or("Identifier expected.")
+  }
+
+  /// Parses an identifier. Otherwise reports `msg` and returns an empty identifier at
+  /// the current position without consuming anything, like TypeScript's missing nodes.
+  fn parse_ident_or(&mut self, msg: &str) -> Ident {
+    if self.is_binding_ident() {
+      let ident = self.token.ident().unwrap();
+      self.bump();
+      return ident;
+    }
+    match self.token.ident() {
+      Some(ident) => self.error_at_token(&format!(
+        "Identifier expected. '{}' is a reserved word that cannot be used here.",
+        ident.name
+      )),
+      None => self.error_at_token(msg),
+    }
+    Ident::new(kw::Empty, self.token.span.shrink_to_lo())
+  }
+
+  /// Whether any of the [ContextFlags] `flags` is in effect.
+  fn in_context(&self, flags: u8) -> bool {
+    self.context & flags != 0
+  }
+
+  /// Runs `f` with the [ContextFlags] `unset` turned off and then `set` turned on,
+  /// restoring the context afterwards.
+  fn with_context<T>(&mut self, set: u8, unset: u8, f: impl FnOnce(&mut Self) -> T) -> T {
+    let context = self.context;
+    self.context = (context & !unset) | set;
+    let result = f(self);
+    self.context = context;
+    result
+  }
+
+  /// The source text of `span`.
+  fn span_text(&self, span: Span) -> &'a str {
+    &self.src[span.lo().to_usize()..span.hi().to_usize()]
+  }
+
+  /// The span from `lo` to the end of the previous token, empty if nothing was consumed.
+  fn span_from(&self, lo: BytePos) -> Span {
+    Span::new(lo, self.prev_token.span.hi().max(lo))
+  }
+
+  /// Reports an error, unless one was already reported at the same position:
+  /// a single mistake often makes several parsing steps fail at once.
+  pub fn error(&mut self, span: Span, msg: &str) {
+    if self.errors.last().map_or(false, |err| err.span.lo() == span.lo()) {
+      return;
+    }
+    self.errors.push(ParseError { msg: msg.to_owned(), span });
+  }
+
+  fn error_at_token(&mut self, msg: &str) {
+    self.error(self.token.span, msg)
+  }
+
+  fn snapshot(&self) -> ParserSnapshot<'a> {
+    ParserSnapshot {
+      scanner: self.scanner.snapshot(),
+      token: self.token.clone(),
+      prev_token: self.prev_token.clone(),
+      errors_len: self.errors.len(),
+      shorthand_inits: self.shorthand_inits.clone(),
+    }
+  }
+
+  fn restore(&mut self, snapshot: ParserSnapshot<'a>) {
+    self.scanner.restore(snapshot.scanner);
+    self.token = snapshot.token;
+    self.prev_token = snapshot.prev_token;
+    self.errors.truncate(snapshot.errors_len);
+    self.shorthand_inits = snapshot.shorthand_inits;
+  }
+
+  /// Runs `f` and always rolls the parser back afterwards, e.g. to peek at the tokens
+  /// after the current one.
+  pub fn look_ahead<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+    let snapshot = self.snapshot();
+    let result = f(self);
+    self.restore(snapshot);
+    result
+  }
+
+  /// Runs `f` and rolls the parser back if it returns `None`, along with the errors
+  /// reported meanwhile.
+  pub fn try_parse<T>(&mut self, f: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+    let snapshot = self.snapshot();
+    let result = f(self);
+    if result.is_none() {
+      self.restore(snapshot);
+    }
+    result
+  }
+
+  /// Checks the token after the current one.
+  fn look_ahead_next(&mut self, f: impl FnOnce(&Self) -> bool) -> bool {
+    self.look_ahead(|p| {
+      p.bump();
+      f(p)
+    })
+  }
+}
@@ -0,0 +1,620 @@
+use super::Parser;
+use crate::context::{ContextFlags, ParsingContext};
+
+use tscore_ast::ptr::P;
+use tscore_ast::token;
+use tscore_ast::{
+  Class, ClassElement, ClassElementKind, Decorator, Expr, ExprKind, ExprWithTypeArgs, Fn, FnParam,
+  FnRet, FnSig, Heritage, Prop, PropName, Visibility,
+};
+use tscore_span::symbol::{kw, Ident, Symbol};
+use tscore_span::{BytePos, Span};
+
+/// A modifier keyword of a class member or a parameter.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) enum Modifier {
+  Public,
+  Protected,
+  Private,
+  Static,
+  Readonly,
+  Abstract,
+  Override,
+  Declare,
+  Async,
+}
+
+impl Modifier {
+  pub(super) fn from_symbol(name: Symbol) -> Option<Modifier> {
+    use Modifier::*;
+    Some(match name {
+      kw::Public => Public,
+      kw::Protected => Protected,
+      kw::Private => Private,
+      kw::Static => Static,
+      kw::Readonly => Readonly,
+      kw::Abstract => Abstract,
+      kw::Override => Override,
+      kw::Declare => Declare,
+      kw::Async => Async,
+      _ => return None,
+    })
+  }
+
+  fn as_str(self) -> &'static str {
+    use Modifier::*;
+    match self {
+      Public => "public",
+      Protected => "protected",
+      Private => "private",
+      Static => "static",
+      Readonly => "readonly",
+      Abstract => "abstract",
+      Override => "override",
+      Declare => "declare",
+      Async => "async",
+    }
+  }
+
+  fn is_accessibility(self) -> bool {
+    matches!(self, Modifier::Public | Modifier::Protected | Modifier::Private)
+  }
+}
+
+/// What modifiers apply to, which decides the ones allowed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) enum ModifierTarget {
+  Property,
+  Method,
+  Accessor,
+  Constructor,
+  IndexSig,
+  Param,
+}
+
+/// The modifiers of a class member or a parameter, in source order.
+pub(super) struct Modifiers(Vec<(Modifier, Span)>);
+
+impl Modifiers {
+  pub(super) fn has(&self, modifier: Modifier) -> bool {
+    self.span_of(modifier).is_some()
+  }
+
+  fn span_of(&self, modifier: Modifier) -> Option<Span> {
+    self.0.iter().find(|(m, _)| *m == modifier).map(|(_, span)| *span)
+  }
+
+  pub(super) fn vis(&self) -> Option<Visibility> {
+    self.0.iter().find_map(|(modifier, _)| match modifier {
+      Modifier::Public => Some(Visibility::Public),
+      Modifier::Protected => Some(Visibility::Protected),
+      Modifier::Private => Some(Visibility::Private),
+      _ => None,
+    })
+  }
+}
+
+impl<'a> Parser<'a> {
+  /// Parses a class declaration following its `decorators`, along with `abstract`.
+  /// Decorators followed by anything but a class are reported, the declaration is then
+  /// missing.
+  pub(super) fn parse_class_decl(&mut self, decorators: Vec<Decorator>) -> Option<Class> {
+    let lo = decorators.first().map_or(self.token.span.lo(), |decorator| decorator.span.lo());
+    let is_abstract = self.is_abstract_class();
+    if is_abstract {
+      self.bump();
+    }
+    if !self.check_keyword(kw::Class) {
+      self.error_at_token("Declaration expected.");
+      return None;
+    }
+    Some(self.parse_class(lo, decorators, is_abstract))
+  }
+
+  /// `abstract` is a modifier only before `class` on the same line.
+  pub(super) fn is_abstract_class(&mut self) -> bool {
+    self.check_keyword(kw::Abstract)
+      && self.look_ahead_next(|p| p.check_keyword(kw::Class) && !p.has_preceding_line_break())
+  }
+
+  /// Parses `class A<T> extends B implements C { .. }`, starting at the `class` keyword.
+  /// The name is optional, the checker reports a declaration without one.
+  pub(super) fn parse_class(
+    &mut self,
+    lo: BytePos,
+    decorators: Vec<Decorator>,
+    is_abstract: bool,
+  ) -> Class {
+    self.expect_keyword(kw::Class);
+    // `class implements A {}` has no name.
+    let is_implements =
+      self.check_keyword(kw::Implements) && self.look_ahead_next(|p| p.token.is_ident());
+    let name =
+      if self.is_binding_ident() && !is_implements { Some(self.parse_ident()) } else { None };
+    let generics = self.parse_generics();
+    let heritages = self.parse_heritages();
+    let elements = if self.expect(&token::OpenDelim(token::Brace)) {
+      let elements =
+        self.parse_list(ParsingContext::ClassMembers, |p| p.parse_class_element(is_abstract));
+      self.expect(&token::CloseDelim(token::Brace));
+      elements
+    } else {
+      Vec::new()
+    };
+    Class { name, generics, heritages, span: self.span_from(lo), elements, is_abstract, decorators }
+  }
+
+  /// Parses the `extends` and `implements` clauses, each with a list of types.
+  pub(super) fn parse_heritages(&mut self) -> Vec<Heritage> {
+    if !self.is_heritage_clause() {
+      return Vec::new();
+    }
+    let clauses = self.parse_list(ParsingContext::HeritageClauses, |p| p.parse_heritage_clause());
+    clauses.into_iter().flatten().collect()
+  }
+
+  fn parse_heritage_clause(&mut self) -> Vec<Heritage> {
+    let is_extends = self.check_keyword(kw::Extends);
+    let keyword = if is_extends { kw::Extends } else { kw::Implements };
+    self.bump();
+    let span = self.prev_token.span.shrink_to_hi();
+    let exprs = self.parse_delimited_list(ParsingContext::HeritageClauseElement, false, |p| {
+      p.parse_expr_with_type_args()
+    });
+    if exprs.is_empty() {
+      self.error(span, &format!("'{}' list cannot be empty.", keyword));
+    }
+    let heritage = if is_extends { Heritage::Extends } else { Heritage::Impl };
+    exprs.into_iter().map(heritage).collect()
+  }
+
+  /// Parses a type in a heritage clause, an expression like `A.B` with type arguments.
+  fn parse_expr_with_type_args(&mut self) -> ExprWithTypeArgs {
+    let lo = self.token.span.lo();
+    // `A<T>` followed by `,` is parsed as an instantiation expression.
+    let expr = match self.parse_lhs_expr().into_inner() {
+      Expr { kind: ExprKind::WithTypeArgs(expr), .. } => return expr.into_inner(),
+      expr => P(expr),
+    };
+    let type_args = if self.check(&token::Lt) { self.parse_ty_args() } else { Vec::new() };
+    ExprWithTypeArgs { expr, type_args, span: self.span_from(lo) }
+  }
+
+  /// Whether the current token can begin a class member, a `;` aside.
+  pub(super) fn is_start_of_class_element(&mut self) -> bool {
+    if self.check(&token::At) || self.modifier().is_some() {
+      return true;
+    }
+    if self.check(&token::BinOp(token::Star)) || self.check(&token::OpenDelim(token::Bracket)) {
+      return true;
+    }
+    let is_keyword = match self.token.kind {
+      token::Ident(name) => name.is_keyword() && name != kw::Get && name != kw::Set,
+      token::Literal(lit) if lit.kind == token::Num || matches!(lit.kind, token::Str(_)) => false,
+      token::PrivateIdent(_) => false,
+      _ => return false,
+    };
+    // A keyword is a name only if a member goes on, as in `delete() {}` or `if: T`.
+    !is_keyword
+      || self.look_ahead(|p| {
+        p.bump();
+        matches!(
+          p.token.kind,
+          token::OpenDelim(token::Paren)
+            | token::OpenDelim(token::Bracket)
+            | token::Lt
+            | token::Not
+            | token::Colon
+            | token::Eq
+            | token::Question
+        ) || p.can_parse_semicolon()
+      })
+  }
+
+  /// Parses a property, method, accessor, constructor or index signature along with its
+  /// decorators and modifiers, or a `;`.
+  fn parse_class_element(&mut self, in_abstract_class: bool) -> ClassElement {
+    let lo = self.token.span.lo();
+    let decorators = self.parse_decorators();
+    let modifiers = self.parse_modifiers();
+    let member_lo = self.token.span.lo();
+    let mut name = None;
+    let mut optional = false;
+    let mut definite = false;
+    let (kind, target) =
+      if decorators.is_empty() && modifiers.0.is_empty() && self.eat(&token::Semi) {
+        (ClassElementKind::Semi, ModifierTarget::Property)
+      } else if self.is_accessor() {
+        let is_getter = self.check_keyword(kw::Get);
+        self.bump();
+        name = Some(self.parse_class_element_name());
+        let f = self.parse_method(member_lo, None, None);
+        let kind =
+          if is_getter { ClassElementKind::Getter(P(f)) } else { ClassElementKind::Setter(P(f)) };
+        (kind, ModifierTarget::Accessor)
+      } else if self.is_constructor_name() {
+        self.bump();
+        (
+          ClassElementKind::Constructor(P(self.parse_constructor(member_lo))),
+          ModifierTarget::Constructor,
+        )
+      } else if self.check(&token::OpenDelim(token::Bracket))
+        && self.look_ahead(|p| p.is_unambiguously_index_sig())
+      {
+        let index = self.parse_index_sig(member_lo, modifiers.has(Modifier::Readonly));
+        (ClassElementKind::IndexSig(P(index)), ModifierTarget::IndexSig)
+      } else {
+        let generator = if self.check(&token::BinOp(token::Star)) {
+          let span = self.token.span;
+          self.bump();
+          Some(span)
+        } else {
+          None
+        };
+        name = Some(self.parse_class_element_name());
+        optional = self.eat(&token::Question);
+        if generator.is_some()
+          || self.check(&token::OpenDelim(token::Paren))
+          || self.check(&token::Lt)
+        {
+          let asyncness = modifiers.span_of(Modifier::Async);
+          let f = self.parse_method(member_lo, asyncness, generator);
+          (ClassElementKind::Method(P(f)), ModifierTarget::Method)
+        } else {
+          definite = !optional && !self.has_preceding_line_break() && self.eat(&token::Not);
+          let prop = self.parse_prop(member_lo);
+          (ClassElementKind::Property(P(prop)), ModifierTarget::Property)
+        }
+      };
+    let is_private_name = matches!(name, Some(PropName::PrivateIdent(_)));
+    self.check_modifiers(&modifiers, target, is_private_name, in_abstract_class);
+    ClassElement {
+      kind,
+      vis: modifiers.vis(),
+      name,
+      is_static: modifiers.has(Modifier::Static),
+      is_abstract: modifiers.has(Modifier::Abstract),
+      is_override: modifiers.has(Modifier::Override),
+      is_declare: modifiers.has(Modifier::Declare),
+      definite,
+      optional,
+      readonly: modifiers.has(Modifier::Readonly),
+      decorators,
+      span: self.span_from(lo),
+    }
+  }
+
+  /// `get` and `set` start an accessor when followed by its name, `get() {}` is a method.
+  pub(super) fn is_accessor(&mut self) -> bool {
+    (self.check_keyword(kw::Get) || self.check_keyword(kw::Set))
+      && self.look_ahead_next(|p| p.can_follow_modifier())
+  }
+
+  /// The `constructor` keyword, or the string `"constructor"` before a `(`.
+  fn is_constructor_name(&mut self) -> bool {
+    match self.token.kind {
+      token::Ident(name) => name == kw::Constructor,
+      token::Literal(lit) if matches!(lit.kind, token::Str(_)) => {
+        lit.symbol == kw::Constructor
+          && self.look_ahead_next(|p| p.check(&token::OpenDelim(token::Paren)))
+      }
+      _ => false,
+    }
+  }
+
+  /// Parses the name of a property or a method. After decorators or modifiers the
+  /// name may be missing, e.g. in `class A { @dec }`.
+  fn parse_class_element_name(&mut self) -> PropName {
+    let is_name = match self.token.kind {
+      token::Ident(_) | token::PrivateIdent(_) | token::OpenDelim(token::Bracket) => true,
+      token::Literal(lit) => lit.kind == token::Num || matches!(lit.kind, token::Str(_)),
+      _ => false,
+    };
+    if !is_name {
+      self.error_at_token("Declaration expected.");
+      return PropName::Ident(Ident::new(kw::Empty, self.token.span.shrink_to_lo()));
+    }
+    self.parse_prop_name()
+  }
+
+  /// Parses the signature and the body of a method or an accessor, starting after the
+  /// name. The body is missing for an overload or an abstract method.
+  fn parse_method(&mut self, lo: BytePos, asyncness: Option<Span>, generator: Option<Span>) -> Fn {
+    let mut context = ContextFlags::None;
+    if generator.is_some() {
+      context |= ContextFlags::Yield;
+    }
+    if asyncness.is_some() {
+      context |= ContextFlags::Await;
+    }
+    let sig = self.parse_fn_sig(context);
+    let body = self.parse_fn_body_or_semicolon(context);
+    Fn {
+      name: None,
+      sig,
+      body,
+      asyncness,
+      generator,
+      decorators: Vec::new(),
+      span: self.span_from(lo),
+      js_docs: Vec::new(),
+    }
+  }
+
+  /// Parses a constructor after the `constructor` keyword. Type parameters and a return
+  /// type are parsed as in a method and reported.
+  fn parse_constructor(&mut self, lo: BytePos) -> Fn {
+    let sig_lo = self.token.span.lo();
+    let generics = self.parse_generics();
+    if let (Some(first), Some(last)) = (generics.params.first(), generics.params.last()) {
+      let span = first.span.to(last.span);
+      self.error(span, "Type parameters cannot appear on a constructor declaration.");
+    }
+    let params = self.parse_fn_param_list(ContextFlags::None);
+    let ret = self.parse_fn_ret();
+    if let FnRet::Ty(ty) = &ret {
+      self.error(ty.span, "Type annotation cannot appear on a constructor declaration.");
+    }
+    let body = self.parse_fn_body_or_semicolon(ContextFlags::None);
+    if body.is_none() {
+      self.check_param_props(&params);
+    }
+    let sig = FnSig { generics, params, ret, span: self.span_from(sig_lo) };
+    Fn {
+      name: None,
+      sig,
+      body,
+      asyncness: None,
+      generator: None,
+      decorators: Vec::new(),
+      span: self.span_from(lo),
+      js_docs: Vec::new(),
+    }
+  }
+
+  /// Parses the type and the initializer of a property, `a: T = 1;`, after its name.
+  /// `yield`, `await` and `in` in the initializer are those of the class, not of the
+  /// enclosing function.
+  fn parse_prop(&mut self, lo: BytePos) -> Prop {
+    let ty = if self.eat(&token::Colon) { Some(self.parse_ty()) } else { None };
+    let init = if self.eat(&token::Eq) {
+      let unset = ContextFlags::DisallowIn | ContextFlags::Yield | ContextFlags::Await;
+      Some(self.with_context(0, unset, |p| p.parse_assign_expr()))
+    } else {
+      None
+    };
+    self.expect_semicolon();
+    Prop { ty, init, span: self.span_from(lo) }
+  }
+
+  /// Parses the decorators of a class, a class member or a parameter, `@a @b.c()`.
+  pub(super) fn parse_decorators(&mut self) -> Vec<Decorator> {
+    let mut decorators = Vec::new();
+    while self.check(&token::At) {
+      let lo = self.token.span.lo();
+      self.bump();
+      let expr = self.with_context(ContextFlags::Decorator, 0, |p| p.parse_lhs_expr());
+      decorators.push(Decorator { span: self.span_from(lo), expr });
+    }
+    decorators
+  }
+
+  /// The modifier the current token is, if it is followed by what a modifier can be,
+  /// e.g. `static a` but not `static() {}` or `readonly: T`. A line break is allowed
+  /// only after `static`.
+  pub(super) fn modifier(&mut self) -> Option<Modifier> {
+    let modifier = self.token.ident().and_then(|ident| Modifier::from_symbol(ident.name))?;
+    let is_modifier = self.look_ahead_next(|p| {
+      (modifier == Modifier::Static || !p.has_preceding_line_break()) && p.can_follow_modifier()
+    });
+    if is_modifier {
+      Some(modifier)
+    } else {
+      None
+    }
+  }
+
+  /// Parses the modifiers of a class member or a parameter, which are checked once what
+  /// they apply to is known, see [Parser::check_modifiers].
+  pub(super) fn parse_modifiers(&mut self) -> Modifiers {
+    let mut modifiers = Vec::new();
+    while let Some(modifier) = self.modifier() {
+      modifiers.push((modifier, self.token.span));
+      self.bump();
+    }
+    Modifiers(modifiers)
+  }
+
+  /// Reports the first modifier that is duplicated, misordered, conflicts with another
+  /// one or doesn't apply to `target`, returning whether the modifiers are valid.
+  pub(super) fn check_modifiers(
+    &mut self,
+    modifiers: &Modifiers,
+    target: ModifierTarget,
+    is_private_name: bool,
+    in_abstract_class: bool,
+  ) -> bool {
+    let error = modifiers_error(&modifiers.0, target, is_private_name, in_abstract_class);
+    match error {
+      Some((span, msg)) => {
+        self.error(span, &msg);
+        false
+      }
+      None => true,
+    }
+  }
+
+  /// Reports the parameter properties, like `private a`, outside a constructor
+  /// implementation.
+  pub(super) fn check_param_props(&mut self, params: &[FnParam]) {
+    for param in params {
+      if param.vis.is_some() || param.readonly || param.is_override {
+        let msg = "A parameter property is only allowed in a constructor implementation.";
+        self.error(param.span, msg);
+      }
+    }
+  }
+}
+
+/// The first error in `modifiers`, in the order TypeScript's grammar checks go.
+fn modifiers_error(
+  modifiers: &[(Modifier, Span)],
+  target: ModifierTarget,
+  is_private_name: bool,
+  in_abstract_class: bool,
+) -> Option<(Span, String)> {
+  use Modifier::*;
+  let must_precede = |a: Modifier, b: Modifier| {
+    format!("'{}' modifier must precede '{}' modifier.", a.as_str(), b.as_str())
+  };
+  let cannot_be_used_with = |a: Modifier, b: Modifier| {
+    format!("'{}' modifier cannot be used with '{}' modifier.", a.as_str(), b.as_str())
+  };
+  let cannot_appear_on = |modifier: Modifier, what: &str| {
+    format!("'{}' modifier cannot appear on {}.", modifier.as_str(), what)
+  };
+
+  let mut seen: Vec<Modifier> = Vec::new();
+  for &(modifier, span) in modifiers {
+    let has = |m: Modifier| seen.contains(&m);
+    let already_seen = format!("'{}' modifier already seen.", modifier.as_str());
+    let msg = if target == ModifierTarget::IndexSig && modifier != Readonly && modifier != Static {
+      Some(cannot_appear_on(modifier, "an index signature"))
+    } else {
+      match modifier {
+        Public | Protected | Private => {
+          let preceded = [Override, Static, Readonly, Async].iter().copied().find(|m| has(*m));
+          if seen.iter().any(|m| m.is_accessibility()) {
+            Some("Accessibility modifier already seen.".to_owned())
+          } else if let Some(other) = preceded {
+            Some(must_precede(modifier, other))
+          } else if has(Abstract) && modifier == Private {
+            Some(cannot_be_used_with(modifier, Abstract))
+          } else if has(Abstract) {
+            Some(must_precede(modifier, Abstract))
+          } else if is_private_name {
+            Some("An accessibility modifier cannot be used with a private identifier.".to_owned())
+          } else {
+            None
+          }
+        }
+        Static => {
+          if has(Static) {
+            Some(already_seen)
+          } else if has(Readonly) {
+            Some(must_precede(Static, Readonly))
+          } else if has(Async) {
+            Some(must_precede(Static, Async))
+          } else if target == ModifierTarget::Param {
+            Some(cannot_appear_on(Static, "a parameter"))
+          } else if has(Abstract) {
+            Some(cannot_be_used_with(Static, Abstract))
+          } else if has(Override) {
+            Some(must_precede(Static, Override))
+          } else {
+            None
+          }
+        }
+        Readonly => {
+          let allowed = [ModifierTarget::Property, ModifierTarget::IndexSig, ModifierTarget::Param];
+          if has(Readonly) {
+            Some(already_seen)
+          } else if !allowed.contains(&target) {
+            let msg =
+              "'readonly' modifier can only appear on a property declaration or index signature.";
+            Some(msg.to_owned())
+          } else {
+            None
+          }
+        }
+        Declare => {
+          if has(Declare) {
+            Some(already_seen)
+          } else if has(Async) {
+            Some("'async' modifier cannot be used in an ambient context.".to_owned())
+          } else if has(Override) {
+            Some(cannot_be_used_with(Declare, Override))
+          } else if target == ModifierTarget::Param {
+            Some(cannot_appear_on(Declare, "a parameter"))
+          } else if target != ModifierTarget::Property {
+            Some("'declare' modifier cannot appear on class elements of this kind.".to_owned())
+          } else {
+            None
+          }
+        }
+        Abstract => {
+          let allowed =
+            [ModifierTarget::Property, ModifierTarget::Method, ModifierTarget::Accessor];
+          if has(Abstract) {
+            Some(already_seen)
+          } else if !allowed.contains(&target) {
+            let msg =
+              "'abstract' modifier can only appear on a class, method, or property declaration.";
+            Some(msg.to_owned())
+          } else if !in_abstract_class {
+            Some("Abstract methods can only appear within an abstract class.".to_owned())
+          } else if has(Static) {
+            Some(cannot_be_used_with(Static, Abstract))
+          } else if has(Private) {
+            Some(cannot_be_used_with(Private, Abstract))
+          } else if has(Async) {
+            Some(cannot_be_used_with(Async, Abstract))
+          } else if has(Override) {
+            Some(must_precede(Abstract, Override))
+          } else if is_private_name {
+            Some("'abstract' modifier cannot be used with a private identifier.".to_owned())
+          } else {
+            None
+          }
+        }
+        Override => {
+          if has(Override) {
+            Some(already_seen)
+          } else if has(Declare) {
+            Some(cannot_be_used_with(Override, Declare))
+          } else if has(Readonly) {
+            Some(must_precede(Override, Readonly))
+          } else if has(Async) {
+            Some(must_precede(Override, Async))
+          } else {
+            None
+          }
+        }
+        Async => {
+          if has(Async) {
+            Some(already_seen)
+          } else if has(Declare) {
+            Some("'async' modifier cannot be used in an ambient context.".to_owned())
+          } else if target == ModifierTarget::Param {
+            Some(cannot_appear_on(Async, "a parameter"))
+          } else if has(Abstract) {
+            Some(cannot_be_used_with(Async, Abstract))
+          } else {
+            None
+          }
+        }
+      }
+    };
+    if let Some(msg) = msg {
+      return Some((span, msg));
+    }
+    seen.push(modifier);
+  }
+
+  let span_of = |modifier: Modifier| {
+    modifiers.iter().rev().find(|(m, _)| *m == modifier).map(|(_, span)| *span)
+  };
+  if target == ModifierTarget::Constructor {
+    let invalid = [Static, Abstract, Override, Async, Readonly];
+    return invalid.iter().find_map(|&modifier| {
+      let span = span_of(modifier)?;
+      Some((span, cannot_appear_on(modifier, "a constructor declaration")))
+    });
+  }
+  if target == ModifierTarget::Property || target == ModifierTarget::Accessor {
+    if let Some(span) = span_of(Async) {
+      return Some((span, "'async' modifier cannot be used here.".to_owned()));
+    }
+  }
+  None
+}
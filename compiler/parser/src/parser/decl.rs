@@ -0,0 +1,486 @@
+use super::Parser;
+use crate::context::{ContextFlags, ParsingContext};
+
+use tscore_ast::ptr::P;
+use tscore_ast::token;
+use tscore_ast::{
+  Decl, DeclKind, Decorator, Enum, EnumMember, Export, ExportAssign, Import, ImportAttribute,
+  ImportEquals, Interface, LitKind, ModuleBody, ModuleDecl, ModuleName, ModuleRef, NameBinding,
+  PropName, StmtKind, StrLit, TypeAlias,
+};
+use tscore_span::symbol::{kw, Ident};
+use tscore_span::BytePos;
+
+impl<'a> Parser<'a> {
+  /// Whether a declaration starts here, possibly after `declare`.
+  pub(super) fn is_start_of_decl(&mut self) -> bool {
+    if self.check_keyword(kw::Declare)
+      && self.look_ahead(|p| {
+        p.bump();
+        !p.has_preceding_line_break() && !p.check(&token::At) && p.is_start_of_decl_worker()
+      })
+    {
+      return true;
+    }
+    self.is_start_of_decl_worker()
+  }
+
+  fn is_start_of_decl_worker(&mut self) -> bool {
+    if self.check_keyword(kw::Var)
+      || self.check_keyword(kw::Const)
+      || self.check_keyword(kw::Let) && self.is_let_decl()
+      || self.check_keyword(kw::Function)
+      || self.is_async_fn()
+      || self.check(&token::At)
+      || self.check_keyword(kw::Class)
+      || self.is_abstract_class()
+      || self.check_keyword(kw::Enum)
+      || self.check_keyword(kw::Export)
+    {
+      return true;
+    }
+    if self.check_keyword(kw::Interface) || self.check_keyword(kw::Type) {
+      // Both are plain identifiers unless a name follows on the same line.
+      return self.look_ahead_next(|p| !p.has_preceding_line_break() && p.is_binding_ident());
+    }
+    if self.check_keyword(kw::Import) {
+      // `import(a)` and `import.meta` are expressions.
+      return self.look_ahead_next(|p| {
+        p.is_str_lit()
+          || p.check(&token::BinOp(token::Star))
+          || p.check(&token::OpenDelim(token::Brace))
+          || p.token.is_ident()
+      });
+    }
+    if self.check_keyword(kw::Namespace) || self.check_keyword(kw::Module) {
+      // `namespace` and `module` are plain identifiers unless a name follows on the same line.
+      return self.look_ahead_next(|p| {
+        !p.has_preceding_line_break() && (p.is_binding_ident() || p.is_str_lit())
+      });
+    }
+    self.check_keyword(kw::Global)
+      && self.look_ahead_next(|p| p.check(&token::OpenDelim(token::Brace)))
+  }
+
+  /// Parses a declaration, see [Parser::is_start_of_decl], along with its decorators and
+  /// `export`. Everything after `declare` is parsed in the ambient context.
+  pub(super) fn parse_decl(&mut self) -> StmtKind {
+    let lo = self.token.span.lo();
+    let mut decorators = self.parse_decorators();
+    let is_export = self.eat_keyword(kw::Export);
+    if is_export {
+      if let Some(kind) = self.parse_export(lo) {
+        if let Some(decorator) = decorators.first() {
+          self.error(decorator.span, "Decorators are not valid here.");
+        }
+        let ambient = self.in_context(ContextFlags::Ambient);
+        let span = self.span_from(lo);
+        return StmtKind::Decl(P(Decl {
+          kind,
+          ambient,
+          is_export: false,
+          is_default: false,
+          span,
+        }));
+      }
+    }
+    let is_default = is_export && self.eat_keyword(kw::Default);
+    if is_export && self.check(&token::At) {
+      let span = self.token.span;
+      let after_export = self.parse_decorators();
+      if !decorators.is_empty() {
+        self.error(
+          span,
+          "Decorators may not appear after 'export' or 'export default' if they also appear before 'export'.",
+        );
+      }
+      decorators.extend(after_export);
+    }
+    let is_ambient = self.check_keyword(kw::Declare) && self.is_start_of_decl();
+    if is_ambient {
+      self.bump();
+    }
+    let context = if is_ambient { ContextFlags::Ambient } else { ContextFlags::None };
+    self.with_context(context, 0, |p| match p.parse_decl_worker(lo, decorators, is_default) {
+      Some(kind) => {
+        let ambient = p.in_context(ContextFlags::Ambient);
+        let span = p.span_from(lo);
+        StmtKind::Decl(P(Decl { kind, ambient, is_export, is_default, span }))
+      }
+      None => StmtKind::Empty,
+    })
+  }
+
+  /// Parses the declaration after its modifiers. Reports and returns `None` if there is
+  /// none, e.g. after `export`.
+  fn parse_decl_worker(
+    &mut self,
+    lo: BytePos,
+    decorators: Vec<Decorator>,
+    is_default: bool,
+  ) -> Option<DeclKind> {
+    let kind =
+      if !decorators.is_empty() || self.check_keyword(kw::Class) || self.is_abstract_class() {
+        DeclKind::Class(P(self.parse_class_decl(decorators)?))
+      } else if self.check_keyword(kw::Enum)
+        || self.check_keyword(kw::Const) && self.look_ahead_next(|p| p.check_keyword(kw::Enum))
+      {
+        DeclKind::Enum(P(self.parse_enum()))
+      } else if self.check_keyword(kw::Var)
+        || self.check_keyword(kw::Const)
+        || self.check_keyword(kw::Let)
+      {
+        let list = self.parse_var_decl_list();
+        self.expect_semicolon();
+        DeclKind::Var(P(list))
+      } else if self.check_keyword(kw::Function) || self.is_async_fn() {
+        DeclKind::Fn(P(self.parse_fn(true, is_default)))
+      } else if self.check_keyword(kw::Interface) {
+        DeclKind::Interface(P(self.parse_interface()))
+      } else if self.check_keyword(kw::Type) {
+        DeclKind::Type(P(self.parse_type_alias()))
+      } else if self.check_keyword(kw::Import) {
+        self.parse_import(lo)
+      } else if self.check_keyword(kw::Namespace)
+        || self.check_keyword(kw::Module)
+        || self.check_keyword(kw::Global)
+      {
+        DeclKind::Module(P(self.parse_module_decl()))
+      } else {
+        self.error_at_token("Declaration expected.");
+        return None;
+      };
+    Some(kind)
+  }
+
+  /// Parses `interface A<T> extends B, C { .. }`.
+  fn parse_interface(&mut self) -> Interface {
+    let lo = self.token.span.lo();
+    self.expect_keyword(kw::Interface);
+    let name = self.parse_ident();
+    let generics = self.parse_generics();
+    let heritages = self.parse_heritages();
+    let mut elements = Vec::new();
+    if self.expect(&token::OpenDelim(token::Brace)) {
+      elements = self.parse_ty_members();
+      self.expect(&token::CloseDelim(token::Brace));
+    }
+    Interface { name, generics, heritages, elements, span: self.span_from(lo) }
+  }
+
+  /// Parses `type A<T> = B;`.
+  fn parse_type_alias(&mut self) -> TypeAlias {
+    let lo = self.token.span.lo();
+    self.expect_keyword(kw::Type);
+    let name = self.parse_ident();
+    let generics = self.parse_generics();
+    self.expect(&token::Eq);
+    let ty = self.parse_ty();
+    self.expect_semicolon();
+    TypeAlias { name, generics, ty, span: self.span_from(lo) }
+  }
+
+  /// Parses `enum A { .. }` or `const enum A { .. }`.
+  fn parse_enum(&mut self) -> Enum {
+    let lo = self.token.span.lo();
+    let is_const = self.eat_keyword(kw::Const);
+    self.expect_keyword(kw::Enum);
+    let name = self.parse_ident();
+    let mut members = Vec::new();
+    if self.expect(&token::OpenDelim(token::Brace)) {
+      members = self.with_context(0, ContextFlags::Yield | ContextFlags::Await, |p| {
+        p.parse_delimited_list(ParsingContext::EnumMembers, false, |p| p.parse_enum_member())
+      });
+      self.expect(&token::CloseDelim(token::Brace));
+    }
+    Enum { name, is_const, members, span: self.span_from(lo) }
+  }
+
+  fn parse_enum_member(&mut self) -> EnumMember {
+    let lo = self.token.span.lo();
+    let name = self.parse_prop_name();
+    let init = if self.eat(&token::Eq) {
+      Some(self.with_context(0, ContextFlags::DisallowIn, |p| p.parse_assign_expr()))
+    } else {
+      None
+    };
+    EnumMember { name, init, span: self.span_from(lo) }
+  }
+
+  /// Parses `namespace A.B { .. }`, `module A { .. }`, `module 'a' { .. }` or
+  /// `global { .. }`.
+  fn parse_module_decl(&mut self) -> ModuleDecl {
+    let lo = self.token.span.lo();
+    if self.check_keyword(kw::Global) {
+      let name = ModuleName::Global(self.parse_ident_name());
+      return self.parse_ambient_module(lo, name);
+    }
+    if self.eat_keyword(kw::Namespace) {
+      return self.parse_namespace(lo, true);
+    }
+    self.expect_keyword(kw::Module);
+    if self.is_str_lit() {
+      let name = ModuleName::Str(self.parse_module_specifier());
+      return self.parse_ambient_module(lo, name);
+    }
+    self.parse_namespace(lo, false)
+  }
+
+  /// Parses the name and body of a namespace, a dotted name nests a declaration per name.
+  fn parse_namespace(&mut self, lo: BytePos, is_namespace: bool) -> ModuleDecl {
+    let name = ModuleName::Ident(self.parse_ident());
+    let body = if self.eat(&token::Dot) {
+      let inner_lo = self.token.span.lo();
+      ModuleBody::Decl(P(self.parse_namespace(inner_lo, is_namespace)))
+    } else {
+      ModuleBody::Block(P(self.parse_block()))
+    };
+    ModuleDecl { name, is_namespace, body: Some(body), span: self.span_from(lo) }
+  }
+
+  /// Parses the body of `module 'a'` or `global`, which may be left out for a module.
+  fn parse_ambient_module(&mut self, lo: BytePos, name: ModuleName) -> ModuleDecl {
+    let body = if self.check(&token::OpenDelim(token::Brace)) {
+      Some(ModuleBody::Block(P(self.parse_block())))
+    } else {
+      self.expect_semicolon();
+      None
+    };
+    ModuleDecl { name, is_namespace: false, body, span: self.span_from(lo) }
+  }
+
+  /// Parses what follows `export` unless it is a declaration: `export default a`,
+  /// `export = a`, `export as namespace A`, `export * from 'a'` or `export { a }`.
+  fn parse_export(&mut self, lo: BytePos) -> Option<DeclKind> {
+    if self.check(&token::Eq) || self.check_keyword(kw::Default) && !self.is_default_modifier() {
+      let is_export_equals = self.check(&token::Eq);
+      self.bump();
+      let expr =
+        self.with_context(ContextFlags::Await, ContextFlags::DisallowIn, |p| p.parse_assign_expr());
+      self.expect_semicolon();
+      let span = self.span_from(lo);
+      return Some(DeclKind::ExportAssign(P(ExportAssign { expr, is_export_equals, span })));
+    }
+    if self.eat_keyword(kw::As) {
+      self.expect_keyword(kw::Namespace);
+      let name = self.parse_ident();
+      self.expect_semicolon();
+      return Some(DeclKind::ExportAsNamespace(name));
+    }
+    let type_only = self.check_keyword(kw::Type)
+      && self.look_ahead_next(|p| {
+        p.check(&token::BinOp(token::Star)) || p.check(&token::OpenDelim(token::Brace))
+      });
+    if type_only {
+      self.bump();
+    } else if !self.check(&token::BinOp(token::Star))
+      && !self.check(&token::OpenDelim(token::Brace))
+    {
+      return None;
+    }
+    let mut all = false;
+    let mut namespace = None;
+    let mut names = Vec::new();
+    let mut from = None;
+    if self.eat(&token::BinOp(token::Star)) {
+      all = true;
+      if self.eat_keyword(kw::As) {
+        namespace = Some(self.parse_ident_name());
+      }
+      self.expect_keyword(kw::From);
+      from = Some(self.parse_module_specifier());
+    } else {
+      names = self.parse_name_bindings(false);
+      // `export { a } 'b'` is a missing `from`.
+      if self.check_keyword(kw::From) || self.is_str_lit() && !self.has_preceding_line_break() {
+        self.expect_keyword(kw::From);
+        from = Some(self.parse_module_specifier());
+      }
+    }
+    let attributes = if from.is_some() { self.parse_import_attributes() } else { Vec::new() };
+    self.expect_semicolon();
+    let span = self.span_from(lo);
+    Some(DeclKind::Export(P(Export { type_only, all, namespace, names, from, attributes, span })))
+  }
+
+  /// `default` is a modifier when a class or a function declaration follows it,
+  /// otherwise it exports an expression.
+  fn is_default_modifier(&mut self) -> bool {
+    self.look_ahead(|p| {
+      p.bump();
+      p.check_keyword(kw::Class)
+        || p.check_keyword(kw::Function)
+        || p.check_keyword(kw::Interface)
+        || p.check(&token::At)
+        || p.is_abstract_class()
+        || p.is_async_fn()
+    })
+  }
+
+  /// Parses an import declaration or `import a = ..`, starting at `import`.
+  fn parse_import(&mut self, lo: BytePos) -> DeclKind {
+    self.expect_keyword(kw::Import);
+    let mut default = if self.is_binding_ident() { Some(self.parse_ident()) } else { None };
+    let mut type_only = false;
+    if default.map_or(false, |ident| ident.name == kw::Type)
+      && !self.check_keyword(kw::From)
+      && (self.is_binding_ident()
+        || self.check(&token::BinOp(token::Star))
+        || self.check(&token::OpenDelim(token::Brace)))
+    {
+      type_only = true;
+      default = if self.is_binding_ident() { Some(self.parse_ident()) } else { None };
+    }
+    if let Some(name) = default {
+      if !self.check(&token::Comma) && !self.check_keyword(kw::From) {
+        return DeclKind::ImportEquals(P(self.parse_import_equals(lo, name, type_only)));
+      }
+    }
+    let mut namespace = None;
+    let mut names = Vec::new();
+    if default.is_some()
+      || self.check(&token::BinOp(token::Star))
+      || self.check(&token::OpenDelim(token::Brace))
+    {
+      if default.is_none() || self.eat(&token::Comma) {
+        if self.eat(&token::BinOp(token::Star)) {
+          self.expect_keyword(kw::As);
+          namespace = Some(self.parse_ident());
+        } else {
+          names = self.parse_name_bindings(true);
+        }
+      }
+      self.expect_keyword(kw::From);
+    }
+    let from = self.parse_module_specifier();
+    let attributes = self.parse_import_attributes();
+    self.expect_semicolon();
+    let span = self.span_from(lo);
+    DeclKind::Import(P(Import { default, namespace, names, from, type_only, attributes, span }))
+  }
+
+  /// Parses `= require('a')` or `= A.B` after the name of an import.
+  fn parse_import_equals(&mut self, lo: BytePos, name: Ident, type_only: bool) -> ImportEquals {
+    self.expect(&token::Eq);
+    let module_ref = if self.check_keyword(kw::Require)
+      && self.look_ahead_next(|p| p.check(&token::OpenDelim(token::Paren)))
+    {
+      self.bump();
+      self.bump();
+      let specifier = self.parse_module_specifier();
+      self.expect(&token::CloseDelim(token::Paren));
+      ModuleRef::Require(specifier)
+    } else {
+      ModuleRef::Entity(self.parse_entity_name("Identifier expected."))
+    };
+    self.expect_semicolon();
+    ImportEquals { name, type_only, module_ref, span: self.span_from(lo) }
+  }
+
+  /// Parses `{ a, b as c, type d }` after `import` or `export`.
+  fn parse_name_bindings(&mut self, is_import: bool) -> Vec<NameBinding> {
+    self.parse_bracketed_list(
+      ParsingContext::ImportOrExportSpecifiers,
+      &token::OpenDelim(token::Brace),
+      &token::CloseDelim(token::Brace),
+      |p| p.parse_name_binding(is_import),
+    )
+  }
+
+  /// Parses `a`, `a as b` or `type a`, where `type` may be a name too, as in `{ type }`,
+  /// `{ type as a }` or `{ type as as }`.
+  fn parse_name_binding(&mut self, is_import: bool) -> NameBinding {
+    let lo = self.token.span.lo();
+    let mut name = self.parse_ident_name();
+    let mut alias = None;
+    let mut type_only = false;
+    let mut can_parse_as = true;
+    if name.name == kw::Type {
+      if self.check_keyword(kw::As) {
+        let first_as = self.parse_ident_name();
+        if self.check_keyword(kw::As) {
+          let second_as = self.parse_ident_name();
+          if self.token.is_ident() {
+            // `{ type as as a }`
+            type_only = true;
+            name = first_as;
+            alias = Some(self.parse_ident_name());
+          } else {
+            // `{ type as as }`
+            alias = Some(second_as);
+          }
+        } else if self.token.is_ident() {
+          // `{ type as a }`
+          alias = Some(self.parse_ident_name());
+          can_parse_as = false;
+        } else {
+          // `{ type as }`
+          type_only = true;
+          name = first_as;
+        }
+      } else if self.token.is_ident() {
+        // `{ type a }`
+        type_only = true;
+        name = self.parse_ident_name();
+      }
+    }
+    if can_parse_as && self.eat_keyword(kw::As) {
+      name = alias.unwrap_or(name);
+      alias = Some(self.parse_ident_name());
+    }
+    // The local name of an import is a binding, `{ default as a }` is fine but not
+    // `{ default }`.
+    let local = alias.unwrap_or(name);
+    if is_import && local.is_reserved() {
+      self.error(local.span, "Identifier expected.");
+    }
+    NameBinding { name, alias, type_only, span: self.span_from(lo) }
+  }
+
+  /// Parses `with { type: 'json' }` after a module specifier, or the older
+  /// `assert { .. }` on the same line.
+  fn parse_import_attributes(&mut self) -> Vec<ImportAttribute> {
+    let mut attributes = Vec::new();
+    if !self.check_keyword(kw::With)
+      && !(self.check_keyword(kw::Assert) && !self.has_preceding_line_break())
+    {
+      return attributes;
+    }
+    self.bump();
+    if self.expect(&token::OpenDelim(token::Brace)) {
+      while !self.check(&token::CloseDelim(token::Brace)) && !self.check(&token::Eof) {
+        let lo = self.token.span.lo();
+        let name = if self.is_str_lit() {
+          PropName::NumLit(self.parse_lit())
+        } else {
+          PropName::Ident(self.parse_ident_name())
+        };
+        self.expect(&token::Colon);
+        let value = self.with_context(0, ContextFlags::DisallowIn, |p| p.parse_assign_expr());
+        attributes.push(ImportAttribute { name, value, span: self.span_from(lo) });
+        if !self.eat(&token::Comma) {
+          break;
+        }
+      }
+      self.expect(&token::CloseDelim(token::Brace));
+    }
+    attributes
+  }
+
+  /// Parses the string naming a module, e.g. after `from`.
+  fn parse_module_specifier(&mut self) -> StrLit {
+    if !self.is_str_lit() {
+      self.error_at_token("String literal expected.");
+      return StrLit { span: self.token.span.shrink_to_lo(), symbol: kw::Empty };
+    }
+    let lit = self.parse_lit();
+    match lit.kind {
+      LitKind::Str(symbol) => StrLit { span: lit.span, symbol },
+      _ => unreachable!(),
+    }
+  }
+
+  pub(super) fn is_str_lit(&self) -> bool {
+    matches!(self.token.kind, token::Literal(lit) if matches!(lit.kind, token::Str(_)))
+  }
+}
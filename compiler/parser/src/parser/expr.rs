@@ -0,0 +1,856 @@
+use super::class::Modifier;
+use super::Parser;
+use crate::context::{ContextFlags, ParsingContext, TokenFlags};
+use crate::lexer::num_lit_value;
+
+use tscore_ast::ptr::P;
+use tscore_ast::token;
+use tscore_ast::{
+  ArrayLit, AssignExpr, AssignOpKind, BinOpKind, CallExpr, CondExpr, ElementAccessExpr, Expr,
+  ExprKind, ExprWithTypeArgs, Fn, FnBody, Lit, LitKind, NewExpr, ObjectLit, ObjectLitEl,
+  ObjectLitElKind, Pat, PatKind, PropAccessExpr, PropAssign, PropName, Spanned, StrLit,
+  TemplateLit, TemplateSpan, Ty, UnaryOpKind, UpdateOpKind,
+};
+use tscore_common::LanguageVariant;
+use tscore_span::symbol::{kw, sym, Ident, Symbol};
+use tscore_span::{BytePos, Span};
+
+/// What an assignment target is checked for, the start of the error messages.
+pub(super) const ASSIGNMENT: &str = "The left-hand side of an assignment expression";
+const UPDATE_OPERAND: &str = "The operand of an increment or decrement operator";
+pub(super) const FOR_IN_TARGET: &str = "The left-hand side of a 'for...in' statement";
+pub(super) const FOR_OF_TARGET: &str = "The left-hand side of a 'for...of' statement";
+
+/// The precedence of `as`, the same as the relational operators.
+const AS_PRECEDENCE: u8 = 11;
+
+impl<'a> Parser<'a> {
+  /// Parses an expression, including the comma operator.
+  pub fn parse_expr(&mut self) -> P<Expr> {
+    let lo = self.token.span.lo();
+    let mut expr = self.parse_assign_expr();
+    while self.check(&token::Comma) {
+      let op = Spanned { node: BinOpKind::Comma, span: self.token.span };
+      self.bump();
+      let right = self.parse_assign_expr();
+      expr = self.mk_expr(lo, ExprKind::Binary(op, expr, right));
+    }
+    expr
+  }
+
+  /// Parses an assignment or anything of higher precedence, i.e. an expression that
+  /// is not a comma list, like a variable initializer or an argument.
+  pub fn parse_assign_expr(&mut self) -> P<Expr> {
+    if self.is_yield_expr() {
+      return self.parse_yield_expr();
+    }
+    if let Some(arrow_fn) = self.try_parse_arrow_fn() {
+      return arrow_fn;
+    }
+    let lo = self.token.span.lo();
+    let expr = self.parse_binary_expr(0);
+    if let ExprKind::Ident(ident) = expr.kind {
+      if self.check(&token::Arrow) {
+        return self.parse_simple_arrow_fn(lo, ident, None);
+      }
+    }
+    if let Some(op) = AssignOpKind::from_token(&self.token) {
+      let left = P(self.expr_to_pat(expr, op == AssignOpKind::Assign, ASSIGNMENT));
+      let operator = Spanned { node: op, span: self.token.span };
+      self.bump();
+      let right = self.parse_assign_expr();
+      let span = self.span_from(lo);
+      return self.mk_expr(lo, ExprKind::Assign(P(AssignExpr { span, left, operator, right })));
+    }
+    self.parse_cond_rest(lo, expr)
+  }
+
+  /// `yield` is an identifier outside of generators, unless an operand follows it on the
+  /// same line, which is reported later.
+  fn is_yield_expr(&mut self) -> bool {
+    self.check_keyword(kw::Yield)
+      && (self.in_context(ContextFlags::Yield)
+        || self.look_ahead_next(|p| {
+          !p.has_preceding_line_break() && (p.token.is_ident() || p.token.is_lit())
+        }))
+  }
+
+  /// Parses `yield`, `yield a` or `yield* a`.
+  fn parse_yield_expr(&mut self) -> P<Expr> {
+    let lo = self.token.span.lo();
+    self.expect_keyword(kw::Yield);
+    let mut delegate = false;
+    let mut expr = None;
+    if !self.has_preceding_line_break()
+      && (self.check(&token::BinOp(token::Star)) || self.token.can_begin_expr())
+    {
+      delegate = self.eat(&token::BinOp(token::Star));
+      expr = Some(self.parse_assign_expr());
+    }
+    self.mk_expr(lo, ExprKind::Yield(expr, delegate))
+  }
+
+  /// Parses the `? a : b` of a conditional expression, if present.
+  fn parse_cond_rest(&mut self, lo: BytePos, test: P<Expr>) -> P<Expr> {
+    if !self.eat(&token::Question) {
+      return test;
+    }
+    let consequent = self.with_context(0, ContextFlags::DisallowIn, |p| p.parse_assign_expr());
+    self.expect(&token::Colon);
+    let alternate = self.parse_assign_expr();
+    let span = self.span_from(lo);
+    self.mk_expr(lo, ExprKind::Cond(P(CondExpr { span, test, consequent, alternate })))
+  }
+
+  /// Parses a binary expression whose operators bind tighter than `prec`.
+  fn parse_binary_expr(&mut self, prec: u8) -> P<Expr> {
+    let lo = self.token.span.lo();
+    let left = match self.token.kind.clone() {
+      // `#a in b` checks for a private field, a private name is no expression otherwise.
+      token::PrivateIdent(name) if self.look_ahead_next(|p| p.check_keyword(kw::In)) => {
+        let ident = Ident::new(name, self.token.span);
+        self.bump();
+        self.mk_expr(lo, ExprKind::PrivateIdent(ident))
+      }
+      _ => self.parse_unary_expr(),
+    };
+    self.parse_binary_rest(prec, lo, left)
+  }
+
+  fn parse_binary_rest(&mut self, prec: u8, lo: BytePos, mut left: P<Expr>) -> P<Expr> {
+    loop {
+      if self.check_keyword(kw::As) {
+        // `as` on a new line starts another statement.
+        if AS_PRECEDENCE <= prec || self.has_preceding_line_break() {
+          break;
+        }
+        self.bump();
+        let ty = self.parse_ty();
+        left = self.mk_expr(lo, ExprKind::As(left, ty));
+        continue;
+      }
+      let op = match BinOpKind::from_token(&self.token) {
+        Some(op) => op,
+        None => break,
+      };
+      // `**` is right-associative, all the others are left-associative.
+      let binds =
+        if op == BinOpKind::Exp { op.precedence() >= prec } else { op.precedence() > prec };
+      if !binds || op == BinOpKind::In && self.in_context(ContextFlags::DisallowIn) {
+        break;
+      }
+      let op = Spanned { node: op, span: self.token.span };
+      self.bump();
+      let right = self.parse_binary_expr(op.node.precedence());
+      if op.node == BinOpKind::Qq {
+        self.check_coalesce_operand(&left, BinOpKind::Qq, true);
+        self.check_coalesce_operand(&right, BinOpKind::Qq, false);
+      }
+      left = self.mk_expr(lo, ExprKind::Binary(op, left, right));
+    }
+    left
+  }
+
+  /// `??` can't be mixed with `||` and `&&` without parentheses, e.g. `a || b ?? c`.
+  fn check_coalesce_operand(&mut self, operand: &Expr, op: BinOpKind, is_left: bool) {
+    if let ExprKind::Binary(inner, ..) = &operand.kind {
+      if inner.node == BinOpKind::And || inner.node == BinOpKind::Or {
+        let (first, second) = if is_left { (inner.node, op) } else { (op, inner.node) };
+        self.error(
+          operand.span,
+          &format!(
+            "'{}' and '{}' operations cannot be mixed without parentheses.",
+            first.as_str(),
+            second.as_str()
+          ),
+        );
+      }
+    }
+  }
+
+  /// Parses a unary expression, which may not be the left operand of `**`.
+  fn parse_unary_expr(&mut self) -> P<Expr> {
+    if self.check(&token::PlusPlus) || self.check(&token::MinusMinus) {
+      return self.parse_update_expr();
+    }
+    let is_simple = self.is_start_of_simple_unary_expr();
+    let expr = self.parse_simple_unary_expr();
+    if is_simple && self.check(&token::BinOp(token::StarStar)) {
+      let what = match &expr.kind {
+        ExprKind::Unary(op, _) => {
+          format!("An unary expression with the '{}' operator", op.node.as_str())
+        }
+        _ => "A type assertion expression".to_owned(),
+      };
+      self.error(
+        expr.span,
+        &format!(
+          "{} is not allowed in the left-hand side of an exponentiation expression. {}",
+          what, "Consider enclosing the expression in parentheses."
+        ),
+      );
+    }
+    expr
+  }
+
+  /// Whether the current token starts a unary operator expression or a type assertion.
+  fn is_start_of_simple_unary_expr(&mut self) -> bool {
+    match self.token.kind {
+      token::BinOp(token::Plus) | token::BinOp(token::Minus) | token::Tilde | token::Not => true,
+      token::Lt => self.lang_variant != LanguageVariant::JSX,
+      token::Ident(name) => {
+        name == kw::Delete || name == kw::Void || name == kw::TypeOf || self.is_await_expr()
+      }
+      _ => false,
+    }
+  }
+
+  /// `await` is an identifier outside of async functions, unless an operand follows it
+  /// on the same line as in top-level `await`.
+  fn is_await_expr(&mut self) -> bool {
+    self.check_keyword(kw::Await)
+      && (self.in_context(ContextFlags::Await)
+        || self.look_ahead_next(|p| {
+          !p.has_preceding_line_break() && (p.token.is_ident() || p.token.is_lit())
+        }))
+  }
+
+  fn parse_simple_unary_expr(&mut self) -> P<Expr> {
+    let lo = self.token.span.lo();
+    let is_await = self.is_await_expr();
+    let op = match self.token.kind {
+      token::BinOp(token::Plus) => UnaryOpKind::Plus,
+      token::BinOp(token::Minus) => UnaryOpKind::Minus,
+      token::Tilde => UnaryOpKind::Tilde,
+      token::Not => UnaryOpKind::Not,
+      token::Lt if self.lang_variant != LanguageVariant::JSX => {
+        // `<T>x`, JSX files use the `as` form as it clashes with elements.
+        self.bump();
+        let ty = self.parse_ty();
+        self.expect_gt();
+        let expr = self.parse_simple_unary_expr();
+        return self.mk_expr(lo, ExprKind::Assert(ty, expr));
+      }
+      token::Ident(name) if name == kw::Delete => UnaryOpKind::Del,
+      token::Ident(name) if name == kw::Void => UnaryOpKind::Void,
+      token::Ident(name) if name == kw::TypeOf => UnaryOpKind::Typeof,
+      token::Ident(_) if is_await => UnaryOpKind::Await,
+      _ => return self.parse_update_expr(),
+    };
+    let op = Spanned { node: op, span: self.token.span };
+    self.bump();
+    let expr = self.parse_simple_unary_expr();
+    self.mk_expr(lo, ExprKind::Unary(op, expr))
+  }
+
+  /// Parses a prefix or postfix `++` or `--`, or a left-hand side expression.
+  fn parse_update_expr(&mut self) -> P<Expr> {
+    let lo = self.token.span.lo();
+    if let Some(op) = self.update_op() {
+      let op = Spanned { node: op, span: self.token.span };
+      self.bump();
+      let expr = self.parse_simple_unary_expr();
+      self.check_assign_target(&expr, UPDATE_OPERAND);
+      return self.mk_expr(lo, ExprKind::Update(op, expr, true));
+    }
+    if self.is_start_of_jsx_el() {
+      return self.parse_jsx_el_expr();
+    }
+    let expr = self.parse_lhs_expr();
+    match self.update_op() {
+      // A postfix operator must be on the same line, `a \n ++b` is `a; ++b`.
+      Some(op) if !self.has_preceding_line_break() => {
+        self.check_assign_target(&expr, UPDATE_OPERAND);
+        let op = Spanned { node: op, span: self.token.span };
+        self.bump();
+        self.mk_expr(lo, ExprKind::Update(op, expr, false))
+      }
+      _ => expr,
+    }
+  }
+
+  fn update_op(&self) -> Option<UpdateOpKind> {
+    match self.token.kind {
+      token::PlusPlus => Some(UpdateOpKind::PlusPlus),
+      token::MinusMinus => Some(UpdateOpKind::MinusMinus),
+      _ => None,
+    }
+  }
+
+  /// Reports `expr` if it can't be assigned to. Array and object literals are
+  /// converted to destructuring patterns instead, see [Parser::expr_to_pat].
+  pub(super) fn check_assign_target(&mut self, expr: &Expr, what: &str) {
+    if is_optional_chain(expr) {
+      self.error(expr.span, &format!("{} may not be an optional property access.", what));
+    } else if !is_assign_target(expr) {
+      self.error(expr.span, &format!("{} must be a variable or a property access.", what));
+    }
+  }
+
+  /// Parses a call, member access or `new` expression, along with `import(..)`,
+  /// `import.meta` and `super`.
+  pub(super) fn parse_lhs_expr(&mut self) -> P<Expr> {
+    let lo = self.token.span.lo();
+    let expr = if self.check_keyword(kw::Import)
+      && self.look_ahead_next(|p| p.check(&token::OpenDelim(token::Paren)) || p.check(&token::Lt))
+    {
+      // Dynamic `import(..)`, the call itself is parsed below.
+      self.bump();
+      self.mk_expr(lo, ExprKind::Import)
+    } else if self.check_keyword(kw::Import) && self.look_ahead_next(|p| p.check(&token::Dot)) {
+      self.bump();
+      self.bump();
+      self.parse_meta_prop(lo, kw::Import, sym::meta)
+    } else if self.eat_keyword(kw::Super) {
+      if !self.check(&token::OpenDelim(token::Paren))
+        && !self.check(&token::Dot)
+        && !self.check(&token::OpenDelim(token::Bracket))
+      {
+        self.error_at_token("'super' must be followed by an argument list or member access.");
+      }
+      self.mk_expr(lo, ExprKind::Super)
+    } else {
+      self.parse_member_expr()
+    };
+    self.parse_call_rest(lo, expr)
+  }
+
+  /// Parses the property name of `import.meta` or `new.target`.
+  fn parse_meta_prop(&mut self, lo: BytePos, keyword: Symbol, expected: Symbol) -> P<Expr> {
+    let name = self.parse_ident_name();
+    if name.name != expected && name.name != kw::Empty {
+      self.error(
+        name.span,
+        &format!(
+          "'{}' is not a valid meta-property for keyword '{}'. Did you mean '{}'?",
+          name.name, keyword, expected
+        ),
+      );
+    }
+    self.mk_expr(lo, ExprKind::Meta(name))
+  }
+
+  fn parse_member_expr(&mut self) -> P<Expr> {
+    let lo = self.token.span.lo();
+    let expr = self.parse_primary_expr();
+    self.parse_member_rest(lo, expr, true)
+  }
+
+  /// Parses the property and element accesses, tagged templates and non-null
+  /// assertions following `expr`.
+  fn parse_member_rest(
+    &mut self,
+    lo: BytePos,
+    mut expr: P<Expr>,
+    allow_optional_chain: bool,
+  ) -> P<Expr> {
+    loop {
+      let optional = allow_optional_chain && self.is_start_of_optional_access();
+      if optional {
+        self.bump();
+      }
+      if optional && self.token.is_ident() || !optional && self.eat(&token::Dot) {
+        let name = self.parse_member_name();
+        let span = self.span_from(lo);
+        expr =
+          self.mk_expr(lo, ExprKind::PropAccess(P(PropAccessExpr { expr, optional, name, span })));
+        continue;
+      }
+      // In a decorator, `@a [b]() {}`, the `[` starts the computed name of a member.
+      if (optional || !self.in_context(ContextFlags::Decorator))
+        && self.eat(&token::OpenDelim(token::Bracket))
+      {
+        let member = if self.check(&token::CloseDelim(token::Bracket)) {
+          self.error_at_token("An element access expression should take an argument.");
+          self.missing_expr()
+        } else {
+          self.with_context(0, ContextFlags::DisallowIn, |p| p.parse_expr())
+        };
+        self.expect(&token::CloseDelim(token::Bracket));
+        let span = self.span_from(lo);
+        let access = ElementAccessExpr { expr, optional, member, span };
+        expr = self.mk_expr(lo, ExprKind::ElementAccess(P(access)));
+        continue;
+      }
+      if self.is_template_start() {
+        expr = self.parse_tagged_template(lo, expr, Vec::new(), optional);
+        continue;
+      }
+      if !optional && self.check(&token::Not) && !self.has_preceding_line_break() {
+        self.bump();
+        expr = self.mk_expr(lo, ExprKind::NonNull(expr));
+        continue;
+      }
+      return expr;
+    }
+  }
+
+  /// Parses the template of a tagged template, `` tag<T>`a${b}` ``.
+  fn parse_tagged_template(
+    &mut self,
+    lo: BytePos,
+    tag: P<Expr>,
+    type_args: Vec<P<Ty>>,
+    optional: bool,
+  ) -> P<Expr> {
+    if optional {
+      self.error_at_token("Tagged template expressions are not permitted in an optional chain.");
+    }
+    let template = self.parse_template_lit(true);
+    self.mk_expr(lo, ExprKind::TaggedTemplate(tag, type_args, P(template)))
+  }
+
+  /// Parses the name after a `.`, which may be a private name as in `this.#a`.
+  fn parse_member_name(&mut self) -> Ident {
+    if let token::PrivateIdent(name) = self.token.kind {
+      let ident = Ident::new(name, self.token.span);
+      self.bump();
+      return ident;
+    }
+    self.parse_ident_name()
+  }
+
+  /// A `?.` followed by a property name, `[` or a template. `?.(` is an optional call.
+  fn is_start_of_optional_access(&mut self) -> bool {
+    self.check(&token::QuestionDot)
+      && self.look_ahead_next(|p| {
+        p.token.is_ident() || p.check(&token::OpenDelim(token::Bracket)) || p.is_template_start()
+      })
+  }
+
+  fn is_template_start(&self) -> bool {
+    match self.token.kind {
+      token::Literal(lit) => {
+        lit.kind == token::Template(token::NoSubstitution)
+          || lit.kind == token::Template(token::Head)
+      }
+      _ => false,
+    }
+  }
+
+  /// Parses the calls and member accesses following `expr`.
+  fn parse_call_rest(&mut self, lo: BytePos, mut expr: P<Expr>) -> P<Expr> {
+    loop {
+      expr = self.parse_member_rest(lo, expr, true);
+      let optional = self.eat(&token::QuestionDot);
+      let type_args = self.try_parse_ty_args_in_expr();
+      if type_args.is_some() && self.is_template_start() {
+        expr = self.parse_tagged_template(lo, expr, type_args.unwrap_or_default(), optional);
+        continue;
+      }
+      if type_args.is_some() && !optional && !self.check(&token::OpenDelim(token::Paren)) {
+        let type_args = type_args.unwrap_or_default();
+        let span = self.span_from(lo);
+        expr =
+          self.mk_expr(lo, ExprKind::WithTypeArgs(P(ExprWithTypeArgs { expr, type_args, span })));
+        continue;
+      }
+      if type_args.is_some() || self.check(&token::OpenDelim(token::Paren)) {
+        let args = self.parse_args();
+        let span = self.span_from(lo);
+        let type_args = type_args.unwrap_or_default();
+        let call = CallExpr { span, expr, args, type_args, optional };
+        expr = self.mk_expr(lo, ExprKind::Call(P(call)));
+        continue;
+      }
+      if optional {
+        // `?.` followed by nothing that can be accessed.
+        let name = self.parse_ident_name();
+        let span = self.span_from(lo);
+        expr =
+          self.mk_expr(lo, ExprKind::PropAccess(P(PropAccessExpr { expr, optional, name, span })));
+      }
+      return expr;
+    }
+  }
+
+  /// Speculatively parses the type arguments of a call or `new`, `f<T>(a)`. They are only
+  /// kept when followed by `(`, a template or a token that can't start an expression, so
+  /// `a < b > c` stays a comparison.
+  fn try_parse_ty_args_in_expr(&mut self) -> Option<Vec<P<Ty>>> {
+    if !self.check(&token::Lt) {
+      return None;
+    }
+    self.try_parse(|p| {
+      p.bump();
+      if p.check_ty_args_close_in_expr() {
+        p.error_at_token("Type argument list cannot be empty.");
+      }
+      let type_args =
+        p.parse_delimited_list(ParsingContext::TypeArguments, false, |p| p.parse_ty());
+      if !p.check_ty_args_close_in_expr() {
+        return None;
+      }
+      p.bump();
+      let can_follow =
+        p.check(&token::OpenDelim(token::Paren)) || p.is_template_start() || !p.is_start_of_expr();
+      if can_follow {
+        Some(type_args)
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Checks for the `>` closing type arguments in an expression. Only a `>`, `>>` or `>>>` is
+  /// split for it, `a < b >= c` is a comparison and not `a<b> = c`.
+  fn check_ty_args_close_in_expr(&mut self) -> bool {
+    match self.token.kind {
+      token::Ge | token::BinOpEq(token::Shr) | token::BinOpEq(token::Ushr) => false,
+      _ => self.check_gt(),
+    }
+  }
+
+  /// Parses the arguments of a call or `new`, `(a, ...b)`.
+  fn parse_args(&mut self) -> Vec<P<Expr>> {
+    self.expect(&token::OpenDelim(token::Paren));
+    let args = self.parse_delimited_list(ParsingContext::ArgumentExpressions, false, |p| {
+      p.parse_arg_or_array_el()
+    });
+    self.expect(&token::CloseDelim(token::Paren));
+    args
+  }
+
+  /// Parses an argument or an element of an array literal, which may be spread.
+  fn parse_arg_or_array_el(&mut self) -> P<Expr> {
+    self.with_context(0, ContextFlags::DisallowIn | ContextFlags::Decorator, |p| {
+      let lo = p.token.span.lo();
+      if p.eat(&token::DotDotDot) {
+        let expr = p.parse_assign_expr();
+        return p.mk_expr(lo, ExprKind::Spread(expr));
+      }
+      p.parse_assign_expr()
+    })
+  }
+
+  fn parse_primary_expr(&mut self) -> P<Expr> {
+    let lo = self.token.span.lo();
+    if matches!(self.token.kind, token::BinOp(token::Slash) | token::BinOpEq(token::Slash)) {
+      // A regular expression, which only the parser can tell apart from a division.
+      self.token = self.scanner.rescan_slash_token(self.token.clone());
+    }
+    let is_fn = self.check_keyword(kw::Function) || self.is_async_fn();
+    let kind = match self.token.kind {
+      token::Literal(lit) if matches!(lit.kind, token::Template(_)) => {
+        let template = self.parse_template_lit(false);
+        let span = template.span;
+        ExprKind::Lit(P(Lit { token: lit, kind: LitKind::Template(P(template)), span }))
+      }
+      token::Literal(_) => ExprKind::Lit(P(self.parse_lit())),
+      token::OpenDelim(token::Paren) => {
+        self.bump();
+        let expr = self.with_context(0, ContextFlags::DisallowIn, |p| p.parse_expr());
+        self.expect(&token::CloseDelim(token::Paren));
+        ExprKind::Paren(expr)
+      }
+      token::OpenDelim(token::Bracket) => ExprKind::Array(P(self.parse_array_lit())),
+      token::OpenDelim(token::Brace) => ExprKind::Object(P(self.parse_object_lit())),
+      token::Ident(name) if name == kw::True || name == kw::False || name == kw::Null => {
+        ExprKind::Lit(P(self.parse_lit()))
+      }
+      token::Ident(name) if name == kw::This => {
+        self.bump();
+        ExprKind::This
+      }
+      token::Ident(name) if name == kw::Super => {
+        self.bump();
+        ExprKind::Super
+      }
+      token::Ident(name) if name == kw::New => return self.parse_new_expr(),
+      token::Ident(_) if is_fn => ExprKind::Fn(P(self.parse_fn(false, false))),
+      token::Ident(name) if name == kw::Class => {
+        ExprKind::Class(P(self.parse_class(lo, Vec::new(), false)))
+      }
+      token::At => {
+        // Decorators of a class expression.
+        let decorators = self.parse_decorators();
+        if self.check_keyword(kw::Class) {
+          ExprKind::Class(P(self.parse_class(lo, decorators, false)))
+        } else {
+          self.error_at_token("Expression expected.");
+          ExprKind::Ident(Ident::new(kw::Empty, self.token.span.shrink_to_lo()))
+        }
+      }
+      _ => ExprKind::Ident(self.parse_ident_or("Expression expected.")),
+    };
+    self.mk_expr(lo, kind)
+  }
+
+  /// Parses `new A`, `new A(..)` or `new.target`.
+  fn parse_new_expr(&mut self) -> P<Expr> {
+    let lo = self.token.span.lo();
+    self.expect_keyword(kw::New);
+    if self.eat(&token::Dot) {
+      return self.parse_meta_prop(lo, kw::New, sym::target);
+    }
+    let expr_lo = self.token.span.lo();
+    let expr = self.parse_primary_expr();
+    let mut expr = self.parse_member_rest(expr_lo, expr, false);
+    let mut type_args = self.try_parse_ty_args_in_expr().unwrap_or_default();
+    if self.is_template_start() {
+      // `new a<T>`b`()`, the tagged template is what is constructed.
+      expr = self.parse_tagged_template(expr_lo, expr, type_args, false);
+      type_args = Vec::new();
+    }
+    if self.check(&token::QuestionDot) {
+      self.error_at_token(&format!(
+        "Invalid optional chain from new expression. Did you mean to call '{}()'?",
+        self.span_text(expr.span)
+      ));
+    }
+    let args =
+      if self.check(&token::OpenDelim(token::Paren)) { Some(self.parse_args()) } else { None };
+    let span = self.span_from(lo);
+    self.mk_expr(lo, ExprKind::New(P(NewExpr { span, expr, args, type_args })))
+  }
+
+  /// Parses an array literal, `[a, , ...b]`.
+  fn parse_array_lit(&mut self) -> ArrayLit {
+    self.expect(&token::OpenDelim(token::Bracket));
+    let multi_line = self.has_preceding_line_break();
+    let elements = self.parse_delimited_list(ParsingContext::ArrayLiteralMembers, false, |p| {
+      if p.check(&token::Comma) {
+        // A hole, `[a, , b]`.
+        let span = p.token.span.shrink_to_lo();
+        P(Expr { kind: ExprKind::Omitted, span })
+      } else {
+        p.parse_arg_or_array_el()
+      }
+    });
+    self.expect(&token::CloseDelim(token::Bracket));
+    ArrayLit { elements, multi_line }
+  }
+
+  /// Parses an object literal, `{ a, b: 1, [c]: 2, ...d }`.
+  fn parse_object_lit(&mut self) -> ObjectLit {
+    self.expect(&token::OpenDelim(token::Brace));
+    let multi_line = self.has_preceding_line_break();
+    let props = self.parse_delimited_list(ParsingContext::ObjectLiteralMembers, true, |p| {
+      P(p.parse_object_lit_el())
+    });
+    self.expect(&token::CloseDelim(token::Brace));
+    ObjectLit { multi_line, props }
+  }
+
+  fn parse_object_lit_el(&mut self) -> ObjectLitEl {
+    let lo = self.token.span.lo();
+    if self.eat(&token::DotDotDot) {
+      let expr = self.with_context(0, ContextFlags::DisallowIn, |p| p.parse_assign_expr());
+      let kind = ObjectLitElKind::SpreadAssign(expr);
+      return ObjectLitEl { kind, name: None, span: self.span_from(lo) };
+    }
+    if self.is_accessor() {
+      let is_getter = self.check_keyword(kw::Get);
+      self.bump();
+      let name = Some(self.parse_prop_name());
+      let f = P(self.parse_object_method(lo, None, None));
+      let kind = if is_getter { ObjectLitElKind::Getter(f) } else { ObjectLitElKind::Setter(f) };
+      return ObjectLitEl { kind, name, span: self.span_from(lo) };
+    }
+    let asyncness = if self.modifier() == Some(Modifier::Async) {
+      let span = self.token.span;
+      self.bump();
+      Some(span)
+    } else {
+      None
+    };
+    let generator = if self.check(&token::BinOp(token::Star)) {
+      let span = self.token.span;
+      self.bump();
+      Some(span)
+    } else {
+      None
+    };
+    // Only identifiers that can be referenced can be shorthands, `{ if }` is an error.
+    let is_shorthand_name = self.is_binding_ident();
+    let name = self.parse_prop_name();
+    let optional = self.eat(&token::Question);
+    if asyncness.is_some()
+      || generator.is_some()
+      || self.check(&token::OpenDelim(token::Paren))
+      || self.check(&token::Lt)
+    {
+      let kind = ObjectLitElKind::MethodDecl(P(self.parse_object_method(lo, asyncness, generator)));
+      return ObjectLitEl { kind, name: Some(name), span: self.span_from(lo) };
+    }
+    let definite = self.eat(&token::Not);
+    let kind = match name {
+      PropName::Ident(ident) if is_shorthand_name && !self.check(&token::Colon) => {
+        // `{ a }`, or `{ a = 1 }` which is only valid as a destructuring pattern.
+        let mut init = self.mk_expr(lo, ExprKind::Ident(ident));
+        if self.check(&token::Eq) {
+          let left = P(Pat { kind: PatKind::Ident(ident), span: ident.span });
+          let operator = Spanned { node: AssignOpKind::Assign, span: self.token.span };
+          self.shorthand_inits.push(operator.span);
+          self.bump();
+          let right = self.with_context(0, ContextFlags::DisallowIn, |p| p.parse_assign_expr());
+          let span = self.span_from(lo);
+          init = self.mk_expr(lo, ExprKind::Assign(P(AssignExpr { span, left, operator, right })));
+        }
+        let span = self.span_from(lo);
+        ObjectLitElKind::ShortPropAssign(P(PropAssign { span, name, optional, definite, init }))
+      }
+      _ => {
+        self.expect(&token::Colon);
+        let init = self.with_context(0, ContextFlags::DisallowIn, |p| p.parse_assign_expr());
+        let span = self.span_from(lo);
+        ObjectLitElKind::PropAssign(P(PropAssign { span, name, optional, definite, init }))
+      }
+    };
+    ObjectLitEl { kind, name: None, span: self.span_from(lo) }
+  }
+
+  /// Parses the signature and the body of a method or an accessor in an object literal,
+  /// after its name. Unlike in a class, the body can't be left out.
+  fn parse_object_method(
+    &mut self,
+    lo: BytePos,
+    asyncness: Option<Span>,
+    generator: Option<Span>,
+  ) -> Fn {
+    let mut context = ContextFlags::None;
+    if generator.is_some() {
+      context |= ContextFlags::Yield;
+    }
+    if asyncness.is_some() {
+      context |= ContextFlags::Await;
+    }
+    let sig = self.parse_fn_sig(context);
+    let body = Some(P(FnBody::Block(P(self.parse_fn_block(context)))));
+    Fn {
+      name: None,
+      sig,
+      body,
+      asyncness,
+      generator,
+      decorators: Vec::new(),
+      span: self.span_from(lo),
+      js_docs: Vec::new(),
+    }
+  }
+
+  /// Parses the name of a property, an identifier, a string or numeric literal, or a
+  /// computed name, `[expr]`.
+  pub(super) fn parse_prop_name(&mut self) -> PropName {
+    match self.token.kind {
+      token::Literal(lit) if lit.kind == token::Num || matches!(lit.kind, token::Str(_)) => {
+        PropName::NumLit(self.parse_lit())
+      }
+      token::OpenDelim(token::Bracket) => {
+        self.bump();
+        let expr = self.with_context(0, ContextFlags::DisallowIn, |p| p.parse_expr());
+        self.expect(&token::CloseDelim(token::Bracket));
+        PropName::Expr(expr)
+      }
+      token::PrivateIdent(name) => {
+        let ident = Ident::new(name, self.token.span);
+        self.bump();
+        PropName::PrivateIdent(ident)
+      }
+      _ => PropName::Ident(self.parse_ident_name()),
+    }
+  }
+
+  /// Parses a template literal, with a `NoSubstitution` or `Head` token as the current
+  /// token. The `}` ending each substitution is rescanned as the rest of the template.
+  fn parse_template_lit(&mut self, is_tagged: bool) -> TemplateLit {
+    let lo = self.token.span.lo();
+    if !is_tagged && self.scanner.token_flags() & TokenFlags::ContainsInvalidEscape != 0 {
+      // Invalid escapes are only allowed in tagged templates.
+      self.token = self.scanner.rescan_template_head_or_no_substitution_template();
+    }
+    let (head, mut kind) = self.parse_template_part();
+    let mut spans = Vec::new();
+    while kind == token::Head || kind == token::Middle {
+      let span_lo = self.token.span.lo();
+      let expr = self.with_context(0, ContextFlags::DisallowIn, |p| p.parse_expr());
+      let lit = if self.check(&token::CloseDelim(token::Brace)) {
+        self.token = self.scanner.rescan_template_token(is_tagged);
+        let (lit, next) = self.parse_template_part();
+        kind = next;
+        Some(lit)
+      } else {
+        self.error_at_token("'}' expected.");
+        kind = token::Tail;
+        None
+      };
+      spans.push(P(TemplateSpan { span: self.span_from(span_lo), expr, lit }));
+    }
+    TemplateLit { span: self.span_from(lo), head: Some(head), spans }
+  }
+
+  /// Consumes a template token, returning its cooked text and which part it is.
+  pub(super) fn parse_template_part(&mut self) -> (StrLit, token::TemplateKind) {
+    let (symbol, kind) = match self.token.kind {
+      token::Literal(token::Lit { kind: token::Template(kind), symbol, .. }) => {
+        (self.scanner.token_value().unwrap_or(symbol), kind)
+      }
+      _ => unreachable!("`parse_template_part` called on {:?}", self.token),
+    };
+    let lit = StrLit { span: self.token.span, symbol };
+    self.bump();
+    (lit, kind)
+  }
+
+  /// Parses a literal token other than a template, or `true`, `false` and `null`.
+  pub(super) fn parse_lit(&mut self) -> Lit {
+    let span = self.token.span;
+    let (token, kind) = match self.token.kind {
+      token::Ident(name) if name == kw::Null => {
+        (token::Lit::new(token::Null, name, None), LitKind::Null)
+      }
+      token::Ident(name) => {
+        (token::Lit::new(token::Bool, name, None), LitKind::Bool(name == kw::True))
+      }
+      token::Literal(lit) => {
+        let kind = match lit.kind {
+          token::Num => LitKind::Num(num_lit_value(&lit.symbol.as_str())),
+          token::Str(_) => LitKind::Str(self.scanner.token_value().unwrap_or(lit.symbol)),
+          token::BigInt => LitKind::BitInt,
+          token::RegExp => LitKind::RegExp,
+          token::Template(_) | token::Bool | token::Null => {
+            unreachable!("`parse_lit` called on {:?}", lit.kind)
+          }
+        };
+        (lit, kind)
+      }
+      _ => unreachable!("`parse_lit` called on {:?}", self.token),
+    };
+    self.bump();
+    Lit { token, kind, span }
+  }
+
+  pub(super) fn mk_expr(&self, lo: BytePos, kind: ExprKind) -> P<Expr> {
+    P(Expr { kind, span: self.span_from(lo) })
+  }
+
+  /// An empty identifier standing for an expression that is missing, the error
+  /// is reported by the caller.
+  pub(super) fn missing_expr(&self) -> P<Expr> {
+    let span = self.token.span.shrink_to_lo();
+    P(Expr { kind: ExprKind::Ident(Ident::new(kw::Empty, span)), span })
+  }
+}
+
+/// Whether `expr` is part of an optional chain, e.g. `a?.b.c`.
+fn is_optional_chain(expr: &Expr) -> bool {
+  match &expr.kind {
+    ExprKind::PropAccess(access) => access.optional || is_optional_chain(&access.expr),
+    ExprKind::ElementAccess(access) => access.optional || is_optional_chain(&access.expr),
+    ExprKind::Call(call) => call.optional || is_optional_chain(&call.expr),
+    ExprKind::NonNull(expr) => is_optional_chain(expr),
+    _ => false,
+  }
+}
+
+fn is_assign_target(expr: &Expr) -> bool {
+  match &expr.kind {
+    // A missing expression was reported already.
+    ExprKind::Ident(_) | ExprKind::PropAccess(_) | ExprKind::ElementAccess(_) => true,
+    ExprKind::Paren(expr) | ExprKind::NonNull(expr) | ExprKind::As(expr, _) => {
+      is_assign_target(expr)
+    }
+    ExprKind::Assert(_, expr) => is_assign_target(expr),
+    _ => false,
+  }
+}
@@ -0,0 +1,428 @@
+use super::class::{Modifier, ModifierTarget};
+use super::ty::ty_has_arrow_fn_blocking_parse_error;
+use super::Parser;
+use crate::context::{ContextFlags, ParsingContext};
+
+use tscore_ast::ptr::P;
+use tscore_ast::token;
+use tscore_ast::{Block, Expr, ExprKind, Fn, FnBody, FnParam, FnRet, FnSig, Pat, PatKind};
+use tscore_common::LanguageVariant;
+use tscore_span::symbol::{kw, Ident};
+use tscore_span::{BytePos, Span};
+
+/// The answer of a look-ahead that can't always decide, see [Parser::is_paren_arrow_fn].
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Tristate {
+  True,
+  False,
+  Unknown,
+}
+
+impl<'a> Parser<'a> {
+  /// Parses a parenthesized, generic or `async` arrow function if one starts here.
+  /// Whether `(` starts an arrow function is decided by looking ahead when possible,
+  /// otherwise by trying to parse its signature, as in `(a, b) => a` versus `(a, b)`.
+  pub(super) fn try_parse_arrow_fn(&mut self) -> Option<P<Expr>> {
+    let arrow = match self.is_paren_arrow_fn() {
+      Tristate::True => self.parse_paren_arrow_fn(true),
+      Tristate::Unknown => self.try_parse(|p| p.parse_paren_arrow_fn(false)),
+      Tristate::False => None,
+    };
+    arrow.or_else(|| self.try_parse_async_simple_arrow_fn())
+  }
+
+  fn is_paren_arrow_fn(&mut self) -> Tristate {
+    if self.check(&token::OpenDelim(token::Paren))
+      || self.check(&token::Lt)
+      || self.check_keyword(kw::Async)
+    {
+      return self.look_ahead(|p| p.is_paren_arrow_fn_worker());
+    }
+    if self.check(&token::Arrow) {
+      // `=> a` with the parameters missing, parsed as an arrow function to recover.
+      return Tristate::True;
+    }
+    Tristate::False
+  }
+
+  fn is_paren_arrow_fn_worker(&mut self) -> Tristate {
+    if self.eat_keyword(kw::Async) {
+      if self.has_preceding_line_break() {
+        return Tristate::False;
+      }
+      if !self.check(&token::OpenDelim(token::Paren)) && !self.check(&token::Lt) {
+        return Tristate::False;
+      }
+    }
+    let is_paren = self.check(&token::OpenDelim(token::Paren));
+    self.bump();
+    if !is_paren {
+      // `<T>(..) =>`, a type assertion like `<T>(a)` looks the same up to the `=>`.
+      if !self.is_binding_ident() {
+        return Tristate::False;
+      }
+      if self.lang_variant == LanguageVariant::JSX {
+        // `<T>` starts an element, only `<T,>` and `<T extends U>` start an arrow function.
+        self.bump();
+        if self.eat_keyword(kw::Extends) {
+          let is_attribute = self.check(&token::Eq) || self.check(&token::Gt);
+          return if is_attribute { Tristate::False } else { Tristate::True };
+        }
+        return if self.check(&token::Comma) { Tristate::True } else { Tristate::False };
+      }
+      return Tristate::Unknown;
+    }
+
+    if self.eat(&token::CloseDelim(token::Paren)) {
+      // `() =>`, `(): T =>` or `() {` with a missing `=>`.
+      return if self.check(&token::Arrow)
+        || self.check(&token::Colon)
+        || self.check(&token::OpenDelim(token::Brace))
+      {
+        Tristate::True
+      } else {
+        Tristate::False
+      };
+    }
+    if self.check(&token::OpenDelim(token::Bracket)) || self.check(&token::OpenDelim(token::Brace))
+    {
+      // A destructuring parameter or an array or object literal.
+      return Tristate::Unknown;
+    }
+    if self.check(&token::DotDotDot) {
+      return Tristate::True;
+    }
+    // `(private a` can only start a parameter, one with a modifier.
+    let is_modifier = self.token.ident().and_then(|ident| Modifier::from_symbol(ident.name));
+    if is_modifier.map_or(false, |modifier| modifier != Modifier::Async)
+      && self.look_ahead_next(|p| p.token.is_ident())
+    {
+      return Tristate::True;
+    }
+    if !self.is_binding_ident() && !self.check_keyword(kw::This) {
+      return Tristate::False;
+    }
+    self.bump();
+    match self.token.kind {
+      // `(a: T`
+      token::Colon => Tristate::True,
+      // `(a?: T`, `(a?,`, `(a? =` or `(a?)`, otherwise a conditional, `(a ? b : c)`.
+      token::Question => {
+        self.bump();
+        match self.token.kind {
+          token::Colon | token::Comma | token::Eq | token::CloseDelim(token::Paren) => {
+            Tristate::True
+          }
+          _ => Tristate::False,
+        }
+      }
+      // `(a,`, `(a =` or `(a)`, an arrow function only if `=>` follows the `)`.
+      token::Comma | token::Eq | token::CloseDelim(token::Paren) => Tristate::Unknown,
+      _ => Tristate::False,
+    }
+  }
+
+  /// Parses `async? <T>? (params): R => body`. With `allow_ambiguity` the arrow function
+  /// is known to start here and errors are reported, otherwise `None` is returned as
+  /// soon as it turns out not to be one.
+  fn parse_paren_arrow_fn(&mut self, allow_ambiguity: bool) -> Option<P<Expr>> {
+    let lo = self.token.span.lo();
+    let asyncness = self.parse_asyncness();
+    let context = if asyncness.is_some() { ContextFlags::Await } else { ContextFlags::None };
+    let generics = self.parse_generics();
+    let params = if self.eat(&token::OpenDelim(token::Paren)) {
+      let params = self.parse_fn_params(context);
+      if !self.expect(&token::CloseDelim(token::Paren)) && !allow_ambiguity {
+        return None;
+      }
+      self.check_param_props(&params);
+      params
+    } else {
+      if !allow_ambiguity {
+        return None;
+      }
+      self.expect(&token::OpenDelim(token::Paren));
+      Vec::new()
+    };
+    let ret = self.parse_fn_ret();
+    // A signature alone is not enough, `(a): b` may be the consequent of a conditional.
+    if !allow_ambiguity
+      && !self.check(&token::Arrow)
+      && !self.check(&token::OpenDelim(token::Brace))
+    {
+      return None;
+    }
+    if let FnRet::Ty(ty) = &ret {
+      if !allow_ambiguity && ty_has_arrow_fn_blocking_parse_error(ty) {
+        return None;
+      }
+    }
+    let sig = FnSig { generics, params, ret, span: self.span_from(lo) };
+    let body = if self.check(&token::Arrow) || self.check(&token::OpenDelim(token::Brace)) {
+      self.expect_arrow();
+      self.parse_arrow_fn_body(asyncness.is_some())
+    } else {
+      self.expect_arrow();
+      FnBody::Expr(self.missing_expr())
+    };
+    Some(self.mk_arrow_fn(lo, sig, body, asyncness))
+  }
+
+  /// Parses `async a => body`, where `async` is not followed by a parenthesis.
+  fn try_parse_async_simple_arrow_fn(&mut self) -> Option<P<Expr>> {
+    let is_arrow_fn = self.check_keyword(kw::Async)
+      && self.look_ahead(|p| {
+        p.bump();
+        if p.has_preceding_line_break() || !p.is_binding_ident() {
+          return false;
+        }
+        p.bump();
+        p.check(&token::Arrow) && !p.has_preceding_line_break()
+      });
+    if !is_arrow_fn {
+      return None;
+    }
+    let lo = self.token.span.lo();
+    let asyncness = self.parse_asyncness();
+    let param = self.with_context(ContextFlags::Await, 0, |p| p.parse_ident());
+    Some(self.parse_simple_arrow_fn(lo, param, asyncness))
+  }
+
+  /// Parses the `=> body` of `a => body`, `a` being the single parameter.
+  pub(super) fn parse_simple_arrow_fn(
+    &mut self,
+    lo: BytePos,
+    param: Ident,
+    asyncness: Option<Span>,
+  ) -> P<Expr> {
+    let pat = P(Pat { kind: PatKind::Ident(param), span: param.span });
+    let param = FnParam {
+      name: pat,
+      optional: false,
+      rest: false,
+      ty: None,
+      init: None,
+      vis: None,
+      readonly: false,
+      is_override: false,
+      decorators: Vec::new(),
+      span: param.span,
+    };
+    let generics = self.empty_generics();
+    let sig = FnSig { generics, params: vec![param], ret: FnRet::Void, span: self.span_from(lo) };
+    self.expect_arrow();
+    let body = self.parse_arrow_fn_body(asyncness.is_some());
+    self.mk_arrow_fn(lo, sig, body, asyncness)
+  }
+
+  fn expect_arrow(&mut self) {
+    if self.check(&token::Arrow) && self.has_preceding_line_break() {
+      self.error_at_token("Line terminator not permitted before arrow.");
+    }
+    self.expect(&token::Arrow);
+  }
+
+  /// Parses a block body, or a concise body which keeps the `in` context of the
+  /// arrow function, e.g. in a `for` initializer.
+  fn parse_arrow_fn_body(&mut self, is_async: bool) -> FnBody {
+    if self.check(&token::OpenDelim(token::Brace)) {
+      let context = if is_async { ContextFlags::Await } else { ContextFlags::None };
+      return FnBody::Block(P(self.parse_fn_block(context)));
+    }
+    let expr = if is_async {
+      self.with_context(ContextFlags::Await, 0, |p| p.parse_assign_expr())
+    } else {
+      self.with_context(0, ContextFlags::Await, |p| p.parse_assign_expr())
+    };
+    FnBody::Expr(expr)
+  }
+
+  fn mk_arrow_fn(
+    &mut self,
+    lo: BytePos,
+    sig: FnSig,
+    body: FnBody,
+    asyncness: Option<Span>,
+  ) -> P<Expr> {
+    let f = Fn {
+      name: None,
+      sig,
+      body: Some(P(body)),
+      asyncness,
+      generator: None,
+      decorators: Vec::new(),
+      span: self.span_from(lo),
+      js_docs: Vec::new(),
+    };
+    self.mk_expr(lo, ExprKind::ArrowFn(P(f)))
+  }
+
+  /// Parses a function declaration or expression, `async function* f<T>(a): R { .. }`.
+  /// The name is optional for expressions, and only declarations may omit the body,
+  /// as an overload signature.
+  pub(super) fn parse_fn(&mut self, is_decl: bool, is_default: bool) -> Fn {
+    let lo = self.token.span.lo();
+    let asyncness = self.parse_asyncness();
+    self.expect_keyword(kw::Function);
+    let generator = if self.check(&token::BinOp(token::Star)) {
+      let span = self.token.span;
+      self.bump();
+      Some(span)
+    } else {
+      None
+    };
+    let mut context = ContextFlags::None;
+    if generator.is_some() {
+      context |= ContextFlags::Yield;
+    }
+    if asyncness.is_some() {
+      context |= ContextFlags::Await;
+    }
+    // The name of a declaration belongs to the enclosing scope, that of an expression
+    // to the function itself, e.g. `function* yield() {}` is an error.
+    let name = if is_decl {
+      // Only `export default function () {}` may leave the name out.
+      if is_default && !self.is_binding_ident() {
+        None
+      } else {
+        Some(self.parse_ident())
+      }
+    } else {
+      let unset = ContextFlags::Yield | ContextFlags::Await;
+      self.with_context(context, unset, |p| {
+        if p.is_binding_ident() {
+          Some(p.parse_ident())
+        } else {
+          None
+        }
+      })
+    };
+    let sig = self.parse_fn_sig(context);
+    let body = if is_decl {
+      self.parse_fn_body_or_semicolon(context)
+    } else {
+      Some(P(FnBody::Block(P(self.parse_fn_block(context)))))
+    };
+    Fn {
+      name,
+      sig,
+      body,
+      asyncness,
+      generator,
+      decorators: Vec::new(),
+      span: self.span_from(lo),
+      js_docs: Vec::new(),
+    }
+  }
+
+  /// Whether `async function` starts here, with no line break in between.
+  pub(super) fn is_async_fn(&mut self) -> bool {
+    self.check_keyword(kw::Async)
+      && self.look_ahead_next(|p| p.check_keyword(kw::Function) && !p.has_preceding_line_break())
+  }
+
+  fn parse_asyncness(&mut self) -> Option<Span> {
+    if self.check_keyword(kw::Async) {
+      let span = self.token.span;
+      self.bump();
+      return Some(span);
+    }
+    None
+  }
+
+  /// Parses the type parameters, parameters and return type of a function.
+  pub(super) fn parse_fn_sig(&mut self, context: u8) -> FnSig {
+    let lo = self.token.span.lo();
+    let generics = self.parse_generics();
+    let params = self.parse_fn_param_list(context);
+    self.check_param_props(&params);
+    let ret = self.parse_fn_ret();
+    FnSig { generics, params, ret, span: self.span_from(lo) }
+  }
+
+  /// Parses the parameters with their parentheses, `(a, b)`.
+  pub(super) fn parse_fn_param_list(&mut self, context: u8) -> Vec<FnParam> {
+    if !self.expect(&token::OpenDelim(token::Paren)) {
+      return Vec::new();
+    }
+    let params = self.parse_fn_params(context);
+    self.expect(&token::CloseDelim(token::Paren));
+    params
+  }
+
+  /// Parses the parameters inside the parentheses, `a, b?: T, c = 1, ...d`. `yield` and
+  /// `await` are operators in the parameters as in the body, depending on `context`.
+  fn parse_fn_params(&mut self, context: u8) -> Vec<FnParam> {
+    let unset = ContextFlags::DisallowIn | ContextFlags::Yield | ContextFlags::Await;
+    self.with_context(context, unset, |p| {
+      p.parse_delimited_list(ParsingContext::Parameters, false, |p| p.parse_fn_param())
+    })
+  }
+
+  /// Parses a parameter with its decorators and modifiers, the modifiers of a parameter
+  /// property, `constructor(private readonly a) {}`, being the only valid ones.
+  pub(super) fn parse_fn_param(&mut self) -> FnParam {
+    let lo = self.token.span.lo();
+    let decorators = self.parse_decorators();
+    let modifiers = self.parse_modifiers();
+    let rest = self.eat(&token::DotDotDot);
+    let name = if self.check_keyword(kw::This) {
+      // `this: T` declares the type of `this` in the function.
+      let ident = self.token.ident().unwrap();
+      self.bump();
+      Pat { kind: PatKind::Ident(ident), span: ident.span }
+    } else {
+      self.parse_binding_pat()
+    };
+    let optional = self.eat(&token::Question);
+    let ty = if self.eat(&token::Colon) { Some(self.parse_ty()) } else { None };
+    let init = if self.eat(&token::Eq) { Some(self.parse_assign_expr()) } else { None };
+    let span = self.span_from(lo);
+    let readonly = modifiers.has(Modifier::Readonly);
+    let is_override = modifiers.has(Modifier::Override);
+    let is_prop = modifiers.vis().is_some() || readonly || is_override;
+    if self.check_modifiers(&modifiers, ModifierTarget::Param, false, false) && is_prop {
+      if !matches!(name.kind, PatKind::Ident(_)) {
+        self.error(span, "A parameter property may not be declared using a binding pattern.");
+      } else if rest {
+        self.error(span, "A parameter property cannot be declared using a rest parameter.");
+      }
+    }
+    FnParam {
+      name: P(name),
+      optional,
+      rest,
+      ty,
+      init,
+      vis: modifiers.vis(),
+      readonly,
+      is_override,
+      decorators,
+      span,
+    }
+  }
+
+  pub(super) fn parse_fn_ret(&mut self) -> FnRet {
+    if self.eat(&token::Colon) {
+      return FnRet::Ty(self.parse_ret_ty());
+    }
+    FnRet::Void
+  }
+
+  /// Parses the block body of a function, or a `;` instead for an overload signature
+  /// or a method without an implementation.
+  pub(super) fn parse_fn_body_or_semicolon(&mut self, context: u8) -> Option<P<FnBody>> {
+    if !self.check(&token::OpenDelim(token::Brace)) && self.can_parse_semicolon() {
+      self.expect_semicolon();
+      return None;
+    }
+    Some(P(FnBody::Block(P(self.parse_fn_block(context)))))
+  }
+
+  /// Parses the block body of a function. `in` is an operator again, and `yield` and
+  /// `await` are operators only as given by `context`.
+  pub(super) fn parse_fn_block(&mut self, context: u8) -> Block {
+    let unset = ContextFlags::DisallowIn | ContextFlags::Yield | ContextFlags::Await;
+    self.with_context(context, unset, |p| p.parse_block())
+  }
+}
@@ -0,0 +1,337 @@
+use super::Parser;
+use crate::context::ParsingContext;
+
+use tscore_ast::ptr::P;
+use tscore_ast::token;
+use tscore_ast::{
+  BinOpKind, Expr, ExprKind, JsxChild, JsxChildKind, JsxClosingEl, JsxEl, JsxExpr, JsxOpeningEl,
+  JsxProp, JsxPropKind, JsxTagNameKind, PropAccessExpr, Spanned,
+};
+use tscore_common::LanguageVariant;
+use tscore_span::symbol::{kw, Ident};
+use tscore_span::Span;
+
+impl<'a> Parser<'a> {
+  /// Whether the current token starts a JSX element, a `<` followed by a tag name or
+  /// the `>` of a fragment.
+  pub(super) fn is_start_of_jsx_el(&mut self) -> bool {
+    self.lang_variant == LanguageVariant::JSX
+      && self.check(&token::Lt)
+      && self.look_ahead_next(|p| p.token.is_ident() || p.check(&token::Gt))
+  }
+
+  /// Parses a JSX element in an expression. Adjacent elements, which need a parent
+  /// element, are reported and joined with a comma operator.
+  pub(super) fn parse_jsx_el_expr(&mut self) -> P<Expr> {
+    let lo = self.token.span.lo();
+    let el = self.parse_jsx_el(true, None);
+    let mut expr = self.mk_expr(lo, ExprKind::JsxEl(el));
+    if !self.check(&token::Lt) {
+      return expr;
+    }
+    while self.check(&token::Lt) {
+      let op = Spanned { node: BinOpKind::Comma, span: self.token.span.shrink_to_lo() };
+      let el_lo = self.token.span.lo();
+      let el = self.parse_jsx_el(true, None);
+      let right = self.mk_expr(el_lo, ExprKind::JsxEl(el));
+      expr = self.mk_expr(lo, ExprKind::Binary(op, expr, right));
+    }
+    self.error(expr.span, "JSX expressions must have one parent element.");
+    expr
+  }
+
+  /// Parses an element, a self-closing element or a fragment. In an expression the
+  /// token after it is scanned as usual, otherwise as JSX text. `parent` is the tag of
+  /// the enclosing element, whose closing tag may have been taken by this element.
+  fn parse_jsx_el(&mut self, in_expr: bool, parent: Option<&JsxTagNameKind>) -> P<JsxEl> {
+    let lo = self.token.span.lo();
+    self.expect(&token::Lt);
+    if self.check_gt() {
+      self.bump_jsx_text();
+      let children = self.parse_jsx_children(None, self.span_from(lo));
+      self.parse_jsx_closing_fragment(in_expr);
+      let span = self.span_from(lo);
+      return P(JsxEl { span, opening_el: None, closing_el: None, children });
+    }
+
+    let tag = self.parse_jsx_tag_name();
+    let type_args = if self.check(&token::Lt) { self.parse_ty_args() } else { Vec::new() };
+    let props = self.parse_jsx_props();
+    if !self.check_gt() {
+      self.expect(&token::BinOp(token::Slash));
+      self.expect_jsx_gt(in_expr);
+      let span = self.span_from(lo);
+      let opening_el = Some(JsxOpeningEl { span, tag, type_args, props });
+      return P(JsxEl { span, opening_el, closing_el: None, children: Vec::new() });
+    }
+    self.bump_jsx_text();
+    let opening_el = JsxOpeningEl { span: self.span_from(lo), tag, type_args, props };
+
+    let mut children = self.parse_jsx_children(Some(&opening_el.tag), opening_el.span);
+    let stolen_closing = match children.last_mut().map(|last| &mut **last) {
+      Some(JsxChild { kind: JsxChildKind::El(child), span })
+        if closes_parent(child, &opening_el.tag) =>
+      {
+        // `<a><b></a>`, the unclosed `<b>` took the closing tag of `<a>`, which gets it
+        // back while `<b>` ends with a missing closing tag.
+        let closing = child.closing_el.take().unwrap();
+        let end = closing.span.shrink_to_lo();
+        let tag = JsxTagNameKind::Ident(Ident::new(kw::Empty, end));
+        child.closing_el = Some(JsxClosingEl { span: end, tag });
+        child.span = child.span.with_hi(end.lo());
+        *span = child.span;
+        Some(closing)
+      }
+      _ => None,
+    };
+    let closing_el = match stolen_closing {
+      Some(closing) => closing,
+      None => {
+        let closing = self.parse_jsx_closing_el(&opening_el.tag, in_expr);
+        if !tag_names_eq(&opening_el.tag, &closing.tag) {
+          let opening_tag = self.span_text(tag_span(&opening_el.tag));
+          if parent.map_or(false, |parent| tag_names_eq(parent, &closing.tag)) {
+            self.error(
+              tag_span(&opening_el.tag),
+              &format!("JSX element '{}' has no corresponding closing tag.", opening_tag),
+            );
+          } else {
+            self.error(
+              tag_span(&closing.tag),
+              &format!("Expected corresponding JSX closing tag for '{}'.", opening_tag),
+            );
+          }
+        }
+        closing
+      }
+    };
+    let span = self.span_from(lo);
+    P(JsxEl { span, opening_el: Some(opening_el), closing_el: Some(closing_el), children })
+  }
+
+  /// Parses the children up to a closing tag. `parent` is the tag of the element, `None`
+  /// for a fragment, and `opening` the span reported if the closing tag is missing.
+  fn parse_jsx_children(
+    &mut self,
+    parent: Option<&JsxTagNameKind>,
+    opening: Span,
+  ) -> Vec<P<JsxChild>> {
+    self.with_parsing_context(ParsingContext::JsxChildren, |p| {
+      let mut children = Vec::new();
+      loop {
+        // The token after a child may have been scanned as usual, e.g. after `<a></b>`.
+        p.token = p.scanner.rescan_jsx_token(p.prev_token.span.hi(), true);
+        let lo = p.token.span.lo();
+        let kind = match p.token.kind {
+          token::Eof => {
+            match parent {
+              Some(tag) => {
+                let span = tag_span(tag);
+                let msg =
+                  format!("JSX element '{}' has no corresponding closing tag.", p.span_text(span));
+                p.error(span, &msg);
+              }
+              None => p.error(opening, "JSX fragment has no corresponding closing tag."),
+            }
+            break;
+          }
+          token::LtSlash | token::Trivia(_) => break,
+          token::JsxText(text) | token::JsxTextAllWhiteSpaces(text) => {
+            let only_whitespace = matches!(p.token.kind, token::JsxTextAllWhiteSpaces(_));
+            let text = p.scanner.token_value().unwrap_or(text);
+            p.bump_jsx_text();
+            JsxChildKind::Text(text, only_whitespace)
+          }
+          token::OpenDelim(token::Brace) => JsxChildKind::Expr(P(p.parse_jsx_expr(false))),
+          // A `<`, the only other token between tags.
+          _ => JsxChildKind::El(p.parse_jsx_el(false, parent)),
+        };
+        let child = P(JsxChild { kind, span: p.span_from(lo) });
+        let is_mismatched = match (&child.kind, parent) {
+          (JsxChildKind::El(child), Some(parent)) => closes_parent(child, parent),
+          _ => false,
+        };
+        children.push(child);
+        // Stop after `<b>` in `<a><b></a>`, the caller gives `</a>` back to `<a>`.
+        if is_mismatched {
+          break;
+        }
+      }
+      children
+    })
+  }
+
+  /// Parses `{a}` or `{...a}`, which may be empty between tags. The token after the `}`
+  /// is scanned as usual in an attribute, otherwise as JSX text.
+  fn parse_jsx_expr(&mut self, in_attr: bool) -> JsxExpr {
+    let lo = self.token.span.lo();
+    self.expect(&token::OpenDelim(token::Brace));
+    let mut spread = false;
+    let mut expr = None;
+    if !self.check(&token::CloseDelim(token::Brace)) {
+      spread = self.eat(&token::DotDotDot);
+      expr = Some(self.parse_expr());
+    }
+    if in_attr {
+      self.expect(&token::CloseDelim(token::Brace));
+    } else if self.check(&token::CloseDelim(token::Brace)) {
+      self.bump_jsx_text();
+    } else {
+      self.expect(&token::CloseDelim(token::Brace));
+    }
+    JsxExpr { span: self.span_from(lo), spread, expr }
+  }
+
+  /// Parses the attributes of an opening tag, up to its `>` or `/>`.
+  fn parse_jsx_props(&mut self) -> Vec<P<JsxProp>> {
+    self.parse_list(ParsingContext::JsxAttributes, |p| p.parse_jsx_prop())
+  }
+
+  /// Parses `a`, `a="b"`, `a={b}` or `{...a}`.
+  fn parse_jsx_prop(&mut self) -> P<JsxProp> {
+    let lo = self.token.span.lo();
+    let kind = if self.eat(&token::OpenDelim(token::Brace)) {
+      self.expect(&token::DotDotDot);
+      let expr = self.parse_expr();
+      self.expect(&token::CloseDelim(token::Brace));
+      JsxPropKind::Spread(expr)
+    } else {
+      self.token = self.scanner.scan_jsx_identifier(self.token.clone());
+      let name = self.parse_ident_name();
+      JsxPropKind::Attr(name, self.parse_jsx_attr_value())
+    };
+    P(JsxProp { kind, span: self.span_from(lo) })
+  }
+
+  /// Parses the value after the `=` of an attribute, if any.
+  fn parse_jsx_attr_value(&mut self) -> Option<P<Expr>> {
+    if !self.check(&token::Eq) {
+      return None;
+    }
+    let next = self.scanner.scan_jsx_attribute_value();
+    self.prev_token = std::mem::replace(&mut self.token, next);
+    let lo = self.token.span.lo();
+    let kind = match self.token.kind {
+      token::Literal(lit) if matches!(lit.kind, token::Str(_)) => {
+        ExprKind::Lit(P(self.parse_lit()))
+      }
+      token::OpenDelim(token::Brace) => {
+        let expr = self.parse_jsx_expr(true);
+        match expr.expr {
+          Some(expr) => return Some(expr),
+          None => {
+            self.error(expr.span, "JSX attributes must only be assigned a non-empty 'expression'.");
+            return None;
+          }
+        }
+      }
+      token::Lt => ExprKind::JsxEl(self.parse_jsx_el(true, None)),
+      _ => {
+        self.error_at_token("'{' or JSX element expected.");
+        return None;
+      }
+    };
+    Some(self.mk_expr(lo, kind))
+  }
+
+  /// Parses `</a>` after the children of `opening`. The token after it is scanned as JSX
+  /// text if it continues the children of the parent, i.e. unless in an expression or
+  /// the tags don't match.
+  fn parse_jsx_closing_el(&mut self, opening: &JsxTagNameKind, in_expr: bool) -> JsxClosingEl {
+    let lo = self.token.span.lo();
+    self.expect(&token::LtSlash);
+    let tag = self.parse_jsx_tag_name();
+    self.expect_jsx_gt(in_expr || !tag_names_eq(opening, &tag));
+    JsxClosingEl { span: self.span_from(lo), tag }
+  }
+
+  /// Parses `</>`.
+  fn parse_jsx_closing_fragment(&mut self, in_expr: bool) {
+    self.expect(&token::LtSlash);
+    if self.token.is_ident() {
+      let tag = self.parse_jsx_tag_name();
+      self.error(tag_span(&tag), "Expected corresponding closing tag for JSX fragment.");
+    }
+    self.expect_jsx_gt(in_expr);
+  }
+
+  /// Parses a tag name, an identifier which may contain dashes or a namespace, `this`,
+  /// or a property access like `a.b`.
+  fn parse_jsx_tag_name(&mut self) -> JsxTagNameKind {
+    let lo = self.token.span.lo();
+    self.token = self.scanner.scan_jsx_identifier(self.token.clone());
+    let mut tag = if self.check_keyword(kw::This) {
+      self.bump();
+      JsxTagNameKind::This(self.prev_token.span)
+    } else {
+      JsxTagNameKind::Ident(self.parse_ident_name())
+    };
+    while self.eat(&token::Dot) {
+      let expr = match tag {
+        JsxTagNameKind::Ident(ident) => P(Expr { kind: ExprKind::Ident(ident), span: ident.span }),
+        JsxTagNameKind::This(span) => P(Expr { kind: ExprKind::This, span }),
+        JsxTagNameKind::PropAccess(access) => {
+          P(Expr { span: access.span, kind: ExprKind::PropAccess(access) })
+        }
+      };
+      let name = self.parse_ident_name();
+      let span = self.span_from(lo);
+      tag = JsxTagNameKind::PropAccess(P(PropAccessExpr { expr, optional: false, name, span }));
+    }
+    tag
+  }
+
+  /// Consumes the `>` ending a tag, scanning the next token as usual if `in_expr`,
+  /// otherwise as JSX text.
+  fn expect_jsx_gt(&mut self, in_expr: bool) {
+    if !self.check_gt() {
+      self.expect(&token::Gt);
+    } else if in_expr {
+      self.bump();
+    } else {
+      self.bump_jsx_text();
+    }
+  }
+}
+
+/// Whether `el` is an element whose closing tag doesn't match its opening tag but
+/// `parent`, as `<b></a>` in `<a><b></a>`.
+fn closes_parent(el: &JsxEl, parent: &JsxTagNameKind) -> bool {
+  match (&el.opening_el, &el.closing_el) {
+    (Some(opening), Some(closing)) => {
+      !tag_names_eq(&opening.tag, &closing.tag) && tag_names_eq(parent, &closing.tag)
+    }
+    _ => false,
+  }
+}
+
+fn tag_names_eq(a: &JsxTagNameKind, b: &JsxTagNameKind) -> bool {
+  match (a, b) {
+    (JsxTagNameKind::Ident(a), JsxTagNameKind::Ident(b)) => a.name == b.name,
+    (JsxTagNameKind::This(_), JsxTagNameKind::This(_)) => true,
+    (JsxTagNameKind::PropAccess(a), JsxTagNameKind::PropAccess(b)) => {
+      a.name.name == b.name.name && tag_exprs_eq(&a.expr, &b.expr)
+    }
+    _ => false,
+  }
+}
+
+/// [tag_names_eq] for the object of a property access in a tag name.
+fn tag_exprs_eq(a: &Expr, b: &Expr) -> bool {
+  match (&a.kind, &b.kind) {
+    (ExprKind::Ident(a), ExprKind::Ident(b)) => a.name == b.name,
+    (ExprKind::This, ExprKind::This) => true,
+    (ExprKind::PropAccess(a), ExprKind::PropAccess(b)) => {
+      a.name.name == b.name.name && tag_exprs_eq(&a.expr, &b.expr)
+    }
+    _ => false,
+  }
+}
+
+fn tag_span(tag: &JsxTagNameKind) -> Span {
+  match tag {
+    JsxTagNameKind::Ident(ident) => ident.span,
+    JsxTagNameKind::This(span) => *span,
+    JsxTagNameKind::PropAccess(access) => access.span,
+  }
+}
@@ -0,0 +1,427 @@
+use super::class::Modifier;
+use super::Parser;
+use crate::context::{ContextFlags, ParsingContext};
+
+use tscore_ast::token::{self, TokenKind};
+use tscore_ast::BinOpKind;
+use tscore_span::symbol::kw;
+
+impl<'a> Parser<'a> {
+  /// Parses a list of elements with no separator, like the statements of a block or the
+  /// members of a class, until a terminator of `kind`.
+  ///
+  /// A token that can't start an element is reported. If an enclosing list can go on
+  /// with it, this list ends so that the enclosing one resumes, otherwise the token is
+  /// skipped. So an error in a class body doesn't swallow the rest of the file.
+  pub(super) fn parse_list<T>(
+    &mut self,
+    kind: ParsingContext,
+    mut parse_el: impl FnMut(&mut Self) -> T,
+  ) -> Vec<T> {
+    self.with_parsing_context(kind, |p| {
+      let mut list = Vec::new();
+      while !p.is_list_terminator(kind) {
+        if p.is_list_element(kind, false) {
+          let lo = p.token.span.lo();
+          list.push(parse_el(p));
+          if p.token.span.lo() == lo {
+            // Nothing could be parsed, skip the token so we are sure to make progress.
+            p.bump();
+          }
+          continue;
+        }
+        if p.abort_parsing_list_or_move_to_next_token(kind) {
+          break;
+        }
+      }
+      list
+    })
+  }
+
+  /// Parses a list of elements separated by commas, recovering like [Parser::parse_list].
+  /// A missing comma is reported and the list goes on, with `allow_semicolon` a `;`
+  /// takes its place, e.g. `{ a: 1; b: 2 }`.
+  pub(super) fn parse_delimited_list<T>(
+    &mut self,
+    kind: ParsingContext,
+    allow_semicolon: bool,
+    mut parse_el: impl FnMut(&mut Self) -> T,
+  ) -> Vec<T> {
+    self.with_parsing_context(kind, |p| {
+      let mut list = Vec::new();
+      loop {
+        if p.is_list_element(kind, false) {
+          let lo = p.token.span.lo();
+          list.push(parse_el(p));
+          if p.eat(&token::Comma) {
+            continue;
+          }
+          if p.is_list_terminator(kind) {
+            break;
+          }
+          // Report the missing comma and go on with the next element.
+          if kind == ParsingContext::EnumMembers {
+            p.error_at_token("An enum member name must be followed by a ',', '=', or '}'.");
+          } else {
+            p.expect(&token::Comma);
+          }
+          if allow_semicolon && p.check(&token::Semi) && !p.has_preceding_line_break() {
+            p.bump();
+          }
+          if p.token.span.lo() == lo {
+            // Nothing could be parsed, skip the token so we are sure to make progress.
+            p.bump();
+          }
+          continue;
+        }
+        if p.is_list_terminator(kind) || p.abort_parsing_list_or_move_to_next_token(kind) {
+          break;
+        }
+      }
+      list
+    })
+  }
+
+  /// Parses a delimited list between `open` and `close`, e.g. `[A, B]`, empty if `open`
+  /// is missing.
+  pub(super) fn parse_bracketed_list<T>(
+    &mut self,
+    kind: ParsingContext,
+    open: &TokenKind,
+    close: &TokenKind,
+    parse_el: impl FnMut(&mut Self) -> T,
+  ) -> Vec<T> {
+    if !self.expect(open) {
+      return Vec::new();
+    }
+    let list = self.parse_delimited_list(kind, false, parse_el);
+    self.expect(close);
+    list
+  }
+
+  /// Runs `f` with the list `kind` being parsed, see [Parser::is_in_some_parsing_context].
+  pub(super) fn with_parsing_context<T>(
+    &mut self,
+    kind: ParsingContext,
+    f: impl FnOnce(&mut Self) -> T,
+  ) -> T {
+    self.parsing_contexts.push(kind);
+    let result = f(self);
+    self.parsing_contexts.pop();
+    result
+  }
+
+  /// Reports the current token, which isn't an element of the list `kind`. Returns `true`
+  /// if the list should end, otherwise skips the token.
+  fn abort_parsing_list_or_move_to_next_token(&mut self, kind: ParsingContext) -> bool {
+    self.parsing_context_error(kind);
+    if self.is_in_some_parsing_context() {
+      return true;
+    }
+    self.bump();
+    false
+  }
+
+  /// Whether the current token could start an element or end any of the lists being
+  /// parsed, in which case the innermost list gives up rather than skip it.
+  fn is_in_some_parsing_context(&mut self) -> bool {
+    for i in 0..self.parsing_contexts.len() {
+      let kind = self.parsing_contexts[i];
+      if self.is_list_element(kind, true) || self.is_list_terminator(kind) {
+        return true;
+      }
+    }
+    false
+  }
+
+  /// Whether the current token ends the list `kind`. The end of the file ends all.
+  fn is_list_terminator(&mut self, kind: ParsingContext) -> bool {
+    use ParsingContext::*;
+    if self.check(&token::Eof) {
+      return true;
+    }
+    let close_brace = self.check(&token::CloseDelim(token::Brace));
+    match kind {
+      BlockStatements
+      | SwitchClauses
+      | TypeMembers
+      | ClassMembers
+      | EnumMembers
+      | ObjectLiteralMembers
+      | ObjectBindingElements
+      | ImportOrExportSpecifiers => close_brace,
+      SwitchClauseStatements => {
+        close_brace || self.check_keyword(kw::Case) || self.check_keyword(kw::Default)
+      }
+      HeritageClauseElement => {
+        self.check(&token::OpenDelim(token::Brace)) || self.is_heritage_clause()
+      }
+      VariableDeclarations => {
+        // Stop at an `=>` too, another declaration is unlikely to follow a broken arrow
+        // function.
+        self.can_parse_semicolon()
+          || self.check_keyword(kw::In)
+          || self.check_keyword(kw::Of)
+          || self.check(&token::Arrow)
+      }
+      TypeParameters => {
+        self.check_gt()
+          || self.check(&token::OpenDelim(token::Paren))
+          || self.check(&token::OpenDelim(token::Brace))
+          || self.is_heritage_clause()
+      }
+      ArgumentExpressions => {
+        self.check(&token::CloseDelim(token::Paren)) || self.check(&token::Semi)
+      }
+      ArrayLiteralMembers | TupleElementTypes | ArrayBindingElements => {
+        self.check(&token::CloseDelim(token::Bracket))
+      }
+      Parameters | JSDocParameters | RestProperties => {
+        self.check(&token::CloseDelim(token::Paren))
+          || self.check(&token::CloseDelim(token::Bracket))
+      }
+      // Type arguments are only separated by commas, `A<B C>` is not an error to go on
+      // with.
+      TypeArguments => !self.check(&token::Comma),
+      HeritageClauses => self.check(&token::OpenDelim(token::Brace)) || close_brace,
+      JsxAttributes => self.check_gt() || self.check(&token::BinOp(token::Slash)),
+      JsxChildren => self.check(&token::LtSlash),
+      SourceElements | Count => false,
+    }
+  }
+
+  /// Whether the current token can start an element of the list `kind`. While
+  /// `in_error_recovery`, i.e. when checking if an enclosing list can go on with a token,
+  /// tokens that show up in too many places, like `;`, are not taken as elements.
+  fn is_list_element(&mut self, kind: ParsingContext, in_error_recovery: bool) -> bool {
+    use ParsingContext::*;
+    match kind {
+      SourceElements | BlockStatements | SwitchClauseStatements => {
+        !(in_error_recovery && self.check(&token::Semi)) && self.is_start_of_stmt()
+      }
+      SwitchClauses => self.check_keyword(kw::Case) || self.check_keyword(kw::Default),
+      TypeMembers => self.look_ahead(|p| p.is_start_of_ty_member()),
+      ClassMembers => {
+        // A stray `;` is more likely the end of a statement than an empty member.
+        (self.check(&token::Semi) && !in_error_recovery) || self.is_start_of_class_element()
+      }
+      EnumMembers => self.check(&token::OpenDelim(token::Bracket)) || self.is_literal_prop_name(),
+      ObjectLiteralMembers => {
+        // A `.` is no member but more likely a member being typed than the end of the
+        // object.
+        matches!(
+          self.token.kind,
+          token::OpenDelim(token::Bracket)
+            | token::BinOp(token::Star)
+            | token::DotDotDot
+            | token::Dot
+        ) || self.is_literal_prop_name()
+      }
+      RestProperties => self.is_literal_prop_name(),
+      ObjectBindingElements => {
+        self.check(&token::OpenDelim(token::Bracket))
+          || self.check(&token::DotDotDot)
+          || self.is_literal_prop_name()
+      }
+      HeritageClauseElement => {
+        if self.check(&token::OpenDelim(token::Brace)) {
+          // `{ .. }` is only extended when it can't be the class body.
+          return self.look_ahead(|p| p.is_valid_heritage_clause_object_lit());
+        }
+        if self.is_heritage_clause() {
+          return false;
+        }
+        // While recovering, `this` and the like are more likely the start of a statement.
+        if in_error_recovery {
+          self.is_binding_ident()
+        } else {
+          self.is_start_of_lhs_expr()
+        }
+      }
+      VariableDeclarations => self.is_binding_ident_or_pat(),
+      ArrayBindingElements => {
+        self.check(&token::Comma) || self.check(&token::DotDotDot) || self.is_binding_ident_or_pat()
+      }
+      TypeParameters => self.token.is_ident(),
+      // A `.` is no element but more likely one being typed than the end of the array.
+      ArrayLiteralMembers if self.check(&token::Comma) || self.check(&token::Dot) => true,
+      ArrayLiteralMembers | ArgumentExpressions => {
+        self.check(&token::DotDotDot) || self.is_start_of_expr()
+      }
+      Parameters | JSDocParameters => self.is_start_of_param(),
+      TypeArguments | TupleElementTypes => self.check(&token::Comma) || self.token.can_begin_type(),
+      HeritageClauses => self.is_heritage_clause(),
+      ImportOrExportSpecifiers => {
+        // Not `import { a }, from 'b'`.
+        if self.check_keyword(kw::From) && self.look_ahead_next(|p| p.is_str_lit()) {
+          return false;
+        }
+        self.token.is_ident()
+      }
+      JsxAttributes => self.token.is_ident() || self.check(&token::OpenDelim(token::Brace)),
+      JsxChildren => true,
+      Count => false,
+    }
+  }
+
+  /// Reports the current token as no element of the list `kind`.
+  fn parsing_context_error(&mut self, kind: ParsingContext) {
+    use ParsingContext::*;
+    let keyword =
+      self.token.ident().filter(|ident| ident.name.is_keyword()).map(|ident| ident.name);
+    let msg = match kind {
+      SourceElements if self.check_keyword(kw::Default) => "'export' expected.",
+      SourceElements | BlockStatements => "Declaration or statement expected.",
+      SwitchClauses => "'case' or 'default' expected.",
+      SwitchClauseStatements => "Statement expected.",
+      TypeMembers | RestProperties => "Property or signature expected.",
+      ClassMembers => {
+        "Unexpected token. A constructor, method, accessor, or property was expected."
+      }
+      EnumMembers => "Enum member expected.",
+      HeritageClauseElement => "Expression expected.",
+      VariableDeclarations => match keyword {
+        Some(name) => {
+          let msg = format!("'{}' is not allowed as a variable declaration name.", name);
+          return self.error_at_token(&msg);
+        }
+        None => "Variable declaration expected.",
+      },
+      ObjectBindingElements => "Property destructuring pattern expected.",
+      ArrayBindingElements => "Array element destructuring pattern expected.",
+      ArgumentExpressions => "Argument expression expected.",
+      ObjectLiteralMembers => "Property assignment expected.",
+      ArrayLiteralMembers => "Expression or comma expected.",
+      Parameters => match keyword {
+        Some(name) => {
+          let msg = format!("'{}' is not allowed as a parameter name.", name);
+          return self.error_at_token(&msg);
+        }
+        None => "Parameter declaration expected.",
+      },
+      JSDocParameters => "Parameter declaration expected.",
+      TypeParameters => "Type parameter declaration expected.",
+      TypeArguments => "Type argument expected.",
+      TupleElementTypes => "Type expected.",
+      HeritageClauses => "Unexpected token. '{' expected.",
+      ImportOrExportSpecifiers if self.check_keyword(kw::From) => "'}' expected.",
+      ImportOrExportSpecifiers | JsxAttributes | JsxChildren | Count => "Identifier expected.",
+    };
+    self.error_at_token(msg);
+  }
+
+  /// Whether the current token can start an expression. Binary operators can too, a
+  /// missing operand is reported and the rest of the expression is parsed as usual.
+  pub(super) fn is_start_of_expr(&self) -> bool {
+    if self.token.can_begin_expr() {
+      return true;
+    }
+    match BinOpKind::from_token(&self.token) {
+      Some(BinOpKind::In) => !self.in_context(ContextFlags::DisallowIn),
+      Some(_) => true,
+      None => false,
+    }
+  }
+
+  /// Whether the current token can start a member access or call, an expression
+  /// without any operator.
+  fn is_start_of_lhs_expr(&mut self) -> bool {
+    match self.token.kind {
+      token::Ident(name) => {
+        !name.is_reserved()
+          || [kw::This, kw::Super, kw::Null, kw::True, kw::False, kw::Function, kw::Class, kw::New]
+            .contains(&name)
+          || name == kw::Import
+            && self.look_ahead(|p| {
+              p.bump();
+              matches!(p.token.kind, token::OpenDelim(token::Paren) | token::Lt | token::Dot)
+            })
+      }
+      token::Literal(lit) => {
+        !matches!(lit.kind, token::Template(token::Middle) | token::Template(token::Tail))
+      }
+      token::OpenDelim(_) | token::BinOp(token::Slash) | token::BinOpEq(token::Slash) => true,
+      _ => false,
+    }
+  }
+
+  /// Whether the current token can start a parameter. Unlike elsewhere `(` and
+  /// `function` don't start a type here.
+  fn is_start_of_param(&mut self) -> bool {
+    if self.check(&token::DotDotDot) || self.check(&token::At) || self.is_binding_ident_or_pat() {
+      return true;
+    }
+    if self.token.ident().and_then(|ident| Modifier::from_symbol(ident.name)).is_some() {
+      return true;
+    }
+    match self.token.kind {
+      token::OpenDelim(token::Paren) | token::BinOp(token::Minus) => false,
+      token::Ident(name) if name == kw::Function => false,
+      _ => self.token.can_begin_type(),
+    }
+  }
+
+  /// Whether a member of an interface or type literal starts here, a call, construct or
+  /// index signature, or a property or method with its modifiers. Consumes tokens, see
+  /// [Parser::look_ahead].
+  fn is_start_of_ty_member(&mut self) -> bool {
+    if matches!(self.token.kind, token::OpenDelim(token::Paren) | token::Lt)
+      || self.check_keyword(kw::Get)
+      || self.check_keyword(kw::Set)
+    {
+      return true;
+    }
+    // The last modifier may be the name, as in `readonly: T`.
+    let mut has_name = false;
+    while self.token.ident().and_then(|ident| Modifier::from_symbol(ident.name)).is_some() {
+      has_name = true;
+      self.bump();
+    }
+    if self.check(&token::OpenDelim(token::Bracket)) {
+      return true;
+    }
+    if self.is_literal_prop_name() {
+      has_name = true;
+      self.bump();
+    }
+    has_name
+      && (matches!(
+        self.token.kind,
+        token::OpenDelim(token::Paren) | token::Lt | token::Question | token::Colon | token::Comma
+      ) || self.can_parse_semicolon())
+  }
+
+  /// Whether `{` after `extends` is an object literal being extended rather than the
+  /// class body. An empty `{}` only is before `,`, `{`, `extends` or `implements`.
+  /// Consumes tokens, see [Parser::look_ahead].
+  fn is_valid_heritage_clause_object_lit(&mut self) -> bool {
+    self.bump();
+    if !self.check(&token::CloseDelim(token::Brace)) {
+      return true;
+    }
+    self.bump();
+    self.check(&token::Comma)
+      || self.check(&token::OpenDelim(token::Brace))
+      || self.is_heritage_clause()
+  }
+
+  pub(super) fn is_heritage_clause(&self) -> bool {
+    self.check_keyword(kw::Extends) || self.check_keyword(kw::Implements)
+  }
+
+  fn is_binding_ident_or_pat(&self) -> bool {
+    matches!(
+      self.token.kind,
+      token::OpenDelim(token::Bracket) | token::OpenDelim(token::Brace) | token::PrivateIdent(_)
+    ) || self.is_binding_ident()
+  }
+
+  /// Whether the current token is an identifier, a keyword, or a string or numeric literal.
+  fn is_literal_prop_name(&self) -> bool {
+    match self.token.kind {
+      token::Ident(_) => true,
+      token::Literal(lit) => matches!(lit.kind, token::Num | token::BigInt | token::Str(_)),
+      _ => false,
+    }
+  }
+}
@@ -0,0 +1,183 @@
+use super::expr::ASSIGNMENT;
+use super::Parser;
+use crate::context::ParsingContext;
+
+use tscore_ast::ptr::P;
+use tscore_ast::token;
+use tscore_ast::{
+  ArrayLit, AssignOpKind, BindingElement, BindingPattern, Expr, ExprKind, ObjectLit,
+  ObjectLitElKind, Pat, PatKind, PropName,
+};
+use tscore_span::Span;
+
+impl<'a> Parser<'a> {
+  /// Parses the name of a variable, a parameter or a `catch` clause, an identifier
+  /// or a destructuring pattern.
+  pub(super) fn parse_binding_pat(&mut self) -> Pat {
+    let lo = self.token.span.lo();
+    let kind = if self.check(&token::OpenDelim(token::Bracket)) {
+      PatKind::Array(P(self.parse_array_binding_pat()))
+    } else if self.check(&token::OpenDelim(token::Brace)) {
+      PatKind::Object(P(self.parse_object_binding_pat()))
+    } else {
+      let ident = self.parse_ident();
+      return Pat { kind: PatKind::Ident(ident), span: ident.span };
+    };
+    Pat { kind, span: self.span_from(lo) }
+  }
+
+  /// Parses `[a, , ...b]`.
+  fn parse_array_binding_pat(&mut self) -> BindingPattern {
+    let lo = self.token.span.lo();
+    self.expect(&token::OpenDelim(token::Bracket));
+    let elements = self.parse_delimited_list(ParsingContext::ArrayBindingElements, false, |p| {
+      if p.check(&token::Comma) {
+        let span = p.token.span.shrink_to_lo();
+        return BindingElement { prop_name: None, name: None, init: None, rest: false, span };
+      }
+      let lo = p.token.span.lo();
+      let rest = p.eat(&token::DotDotDot);
+      let name = Some(P(p.parse_binding_pat()));
+      let init = p.parse_binding_init();
+      BindingElement { prop_name: None, name, init, rest, span: p.span_from(lo) }
+    });
+    self.expect(&token::CloseDelim(token::Bracket));
+    self.check_rest_elements(&elements);
+    BindingPattern { elements, span: self.span_from(lo) }
+  }
+
+  /// Parses `{ a, b: c, ...d }`.
+  fn parse_object_binding_pat(&mut self) -> BindingPattern {
+    let lo = self.token.span.lo();
+    self.expect(&token::OpenDelim(token::Brace));
+    let elements = self.parse_delimited_list(ParsingContext::ObjectBindingElements, false, |p| {
+      let lo = p.token.span.lo();
+      let rest = p.eat(&token::DotDotDot);
+      // Like in object literals, only identifiers that can be bound are shorthands.
+      let is_shorthand_name = p.is_binding_ident();
+      let (prop_name, name) = match p.parse_prop_name() {
+        PropName::Ident(ident) if is_shorthand_name && !p.check(&token::Colon) => {
+          (None, Pat { kind: PatKind::Ident(ident), span: ident.span })
+        }
+        prop_name => {
+          p.expect(&token::Colon);
+          (Some(prop_name), p.parse_binding_pat())
+        }
+      };
+      let init = p.parse_binding_init();
+      BindingElement { prop_name, name: Some(P(name)), init, rest, span: p.span_from(lo) }
+    });
+    self.expect(&token::CloseDelim(token::Brace));
+    self.check_rest_elements(&elements);
+    BindingPattern { elements, span: self.span_from(lo) }
+  }
+
+  fn parse_binding_init(&mut self) -> Option<P<Expr>> {
+    if self.eat(&token::Eq) {
+      Some(self.parse_assign_expr())
+    } else {
+      None
+    }
+  }
+
+  /// Converts the left-hand side of an assignment or the target of a `for .. in` or
+  /// `for .. of` loop to a pattern, reporting what can't be assigned to. Array and object
+  /// literals become destructuring patterns with `allow_pat`.
+  pub(super) fn expr_to_pat(&mut self, expr: P<Expr>, allow_pat: bool, what: &str) -> Pat {
+    let span = expr.span;
+    let kind = match expr.into_inner().kind {
+      ExprKind::Ident(ident) => PatKind::Ident(ident),
+      ExprKind::Array(array) if allow_pat => PatKind::Array(P(self.array_lit_to_pat(array, span))),
+      ExprKind::Object(object) if allow_pat => {
+        PatKind::Object(P(self.object_lit_to_pat(object, span)))
+      }
+      kind => {
+        let expr = P(Expr { kind, span });
+        self.check_assign_target(&expr, what);
+        PatKind::Expr(expr)
+      }
+    };
+    Pat { kind, span }
+  }
+
+  fn array_lit_to_pat(&mut self, array: P<ArrayLit>, span: Span) -> BindingPattern {
+    let mut elements = Vec::new();
+    for el in array.into_inner().elements {
+      let span = el.span;
+      let (name, init, rest) = match el.into_inner().kind {
+        ExprKind::Omitted => (None, None, false),
+        ExprKind::Spread(expr) => {
+          let (name, init) = self.assign_el_to_pat(expr);
+          (Some(name), init, true)
+        }
+        kind => {
+          let (name, init) = self.assign_el_to_pat(P(Expr { kind, span }));
+          (Some(name), init, false)
+        }
+      };
+      elements.push(BindingElement { prop_name: None, name, init, rest, span });
+    }
+    self.check_rest_elements(&elements);
+    BindingPattern { elements, span }
+  }
+
+  fn object_lit_to_pat(&mut self, object: P<ObjectLit>, span: Span) -> BindingPattern {
+    let mut elements = Vec::new();
+    for el in object.into_inner().props {
+      let el = el.into_inner();
+      let (prop_name, (name, init), rest) = match el.kind {
+        ObjectLitElKind::PropAssign(prop) => {
+          let prop = prop.into_inner();
+          (Some(prop.name), self.assign_el_to_pat(prop.init), false)
+        }
+        // `{ a }` or `{ a = 1 }`, which is parsed as an assignment to `a`.
+        ObjectLitElKind::ShortPropAssign(prop) => {
+          let init = prop.into_inner().init;
+          if let ExprKind::Assign(assign) = &init.kind {
+            self.shorthand_inits.retain(|span| *span != assign.operator.span);
+          }
+          (None, self.assign_el_to_pat(init), false)
+        }
+        ObjectLitElKind::SpreadAssign(expr) => {
+          let name = self.expr_to_pat(expr, false, "The target of an object rest assignment");
+          (None, (P(name), None), true)
+        }
+        ObjectLitElKind::MethodDecl(_)
+        | ObjectLitElKind::Getter(_)
+        | ObjectLitElKind::Setter(_) => {
+          self.error(el.span, "Invalid destructuring assignment target.");
+          continue;
+        }
+      };
+      elements.push(BindingElement { prop_name, name: Some(name), init, rest, span: el.span });
+    }
+    self.check_rest_elements(&elements);
+    BindingPattern { elements, span }
+  }
+
+  /// Converts an element of an array or object literal being assigned to, where
+  /// `a = 1` is the target `a` with a default value.
+  fn assign_el_to_pat(&mut self, expr: P<Expr>) -> (P<Pat>, Option<P<Expr>>) {
+    let span = expr.span;
+    match expr.into_inner().kind {
+      ExprKind::Assign(assign) if assign.operator.node == AssignOpKind::Assign => {
+        let assign = assign.into_inner();
+        (assign.left, Some(assign.right))
+      }
+      kind => {
+        let target = P(Expr { kind, span });
+        (P(self.expr_to_pat(target, true, ASSIGNMENT)), None)
+      }
+    }
+  }
+
+  fn check_rest_elements(&mut self, elements: &[BindingElement]) {
+    for (i, el) in elements.iter().enumerate().filter(|(_, el)| el.rest) {
+      if i + 1 != elements.len() {
+        self.error(el.span, "A rest element must be last in a destructuring pattern.");
+      } else if let Some(init) = &el.init {
+        self.error(init.span, "A rest element cannot have an initializer.");
+      }
+    }
+  }
+}
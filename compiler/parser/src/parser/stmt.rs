@@ -0,0 +1,322 @@
+use super::expr::{FOR_IN_TARGET, FOR_OF_TARGET};
+use super::Parser;
+use crate::context::{ContextFlags, ParsingContext};
+
+use tscore_ast::ptr::P;
+use tscore_ast::token;
+use tscore_ast::{
+  Block, CatchBlock, Expr, ExprKind, ForCond, ForVal, IterCond, IterCondKind, Label, Module, Stmt,
+  StmtKind, SwitchBranch, VarDecl, VarDeclKind, VarDeclList,
+};
+use tscore_span::symbol::kw;
+use tscore_span::{BytePos, Span};
+
+impl<'a> Parser<'a> {
+  /// Parses the statements of a whole source file.
+  pub fn parse_module(&mut self) -> Module {
+    let items = self.parse_list(ParsingContext::SourceElements, |p| p.parse_stmt());
+    Module { items, span: Span::new(BytePos(0), self.token.span.hi()) }
+  }
+
+  /// Whether the current token can begin a statement.
+  pub(super) fn is_start_of_stmt(&mut self) -> bool {
+    let starts_stmt = [
+      kw::Var,
+      kw::Const,
+      kw::Function,
+      kw::Class,
+      kw::Enum,
+      kw::If,
+      kw::Do,
+      kw::While,
+      kw::For,
+      kw::Continue,
+      kw::Break,
+      kw::Return,
+      kw::With,
+      kw::Switch,
+      kw::Throw,
+      kw::Try,
+      kw::Catch,
+      kw::Finally,
+      kw::Debugger,
+      kw::Import,
+      kw::Export,
+    ];
+    match self.token.kind {
+      token::At | token::Semi | token::OpenDelim(token::Brace) => true,
+      token::Ident(name) if starts_stmt.contains(&name) => true,
+      _ => self.is_start_of_expr(),
+    }
+  }
+
+  /// Parses a statement.
+  pub fn parse_stmt(&mut self) -> Stmt {
+    let lo = self.token.span.lo();
+    let kind = if self.eat(&token::Semi) {
+      StmtKind::Empty
+    } else if self.check(&token::OpenDelim(token::Brace)) {
+      StmtKind::Block(P(self.parse_block()))
+    } else if self.is_start_of_decl() {
+      self.parse_decl()
+    } else if self.eat_keyword(kw::If) {
+      self.parse_if_stmt()
+    } else if self.check_keyword(kw::Do) {
+      self.parse_do_stmt()
+    } else if self.check_keyword(kw::While) {
+      self.parse_while_stmt()
+    } else if self.check_keyword(kw::For) {
+      self.parse_for_stmt()
+    } else if self.eat_keyword(kw::Continue) {
+      let label = self.parse_break_label();
+      StmtKind::Continue(label)
+    } else if self.eat_keyword(kw::Break) {
+      let label = self.parse_break_label();
+      StmtKind::Break(label)
+    } else if self.eat_keyword(kw::Return) {
+      let expr = if self.can_parse_semicolon() { None } else { Some(self.parse_expr()) };
+      self.expect_semicolon();
+      StmtKind::Ret(expr)
+    } else if self.eat_keyword(kw::With) {
+      let expr = self.parse_paren_cond();
+      StmtKind::With(expr, P(self.parse_stmt()))
+    } else if self.eat_keyword(kw::Switch) {
+      self.parse_switch_stmt()
+    } else if self.eat_keyword(kw::Throw) {
+      self.parse_throw_stmt()
+    } else if self.check_keyword(kw::Try)
+      || self.check_keyword(kw::Catch)
+      || self.check_keyword(kw::Finally)
+    {
+      self.parse_try_stmt()
+    } else if self.eat_keyword(kw::Debugger) {
+      self.expect_semicolon();
+      StmtKind::Debugger
+    } else {
+      self.parse_expr_or_labeled_stmt()
+    };
+    Stmt { kind, span: self.span_from(lo) }
+  }
+
+  /// Parses a block, `{ .. }`.
+  pub fn parse_block(&mut self) -> Block {
+    let lo = self.token.span.lo();
+    if !self.expect(&token::OpenDelim(token::Brace)) {
+      return Block { stmts: Vec::new(), span: self.span_from(lo), multi_line: None };
+    }
+    let multi_line = self.has_preceding_line_break();
+    let stmts = self.parse_list(ParsingContext::BlockStatements, |p| p.parse_stmt());
+    self.expect(&token::CloseDelim(token::Brace));
+    Block { stmts, span: self.span_from(lo), multi_line: Some(multi_line) }
+  }
+
+  /// `let` is only a keyword when a binding follows it, `let = 1` is an assignment.
+  pub(super) fn is_let_decl(&mut self) -> bool {
+    self.look_ahead_next(|p| {
+      p.is_binding_ident()
+        || p.check(&token::OpenDelim(token::Bracket))
+        || p.check(&token::OpenDelim(token::Brace))
+    })
+  }
+
+  /// Parses `var`, `let` or `const` and the declarations following it.
+  pub(super) fn parse_var_decl_list(&mut self) -> VarDeclList {
+    let lo = self.token.span.lo();
+    let kind = if self.eat_keyword(kw::Const) {
+      VarDeclKind::Const
+    } else if self.eat_keyword(kw::Let) {
+      VarDeclKind::Let
+    } else {
+      self.expect_keyword(kw::Var);
+      VarDeclKind::Var
+    };
+    let decls = self
+      .parse_delimited_list(ParsingContext::VariableDeclarations, false, |p| p.parse_var_decl());
+    VarDeclList { kind, decls, span: self.span_from(lo), js_docs: Vec::new() }
+  }
+
+  fn parse_var_decl(&mut self) -> VarDecl {
+    let lo = self.token.span.lo();
+    let name = self.parse_binding_pat();
+    let definite = !self.has_preceding_line_break() && self.eat(&token::Not);
+    let ty = if self.eat(&token::Colon) { Some(self.parse_ty().into_inner()) } else { None };
+    let init =
+      if self.eat(&token::Eq) { Some(self.parse_assign_expr().into_inner()) } else { None };
+    VarDecl { name, ty, definite, init, span: self.span_from(lo) }
+  }
+
+  fn parse_if_stmt(&mut self) -> StmtKind {
+    let cond = self.parse_paren_cond();
+    let then = P(self.parse_stmt());
+    let els = if self.eat_keyword(kw::Else) { Some(P(self.parse_stmt())) } else { None };
+    StmtKind::If(cond, then, els)
+  }
+
+  /// Parses the `(expr)` after `if`, `while`, `with` and `switch`.
+  fn parse_paren_cond(&mut self) -> P<Expr> {
+    self.expect(&token::OpenDelim(token::Paren));
+    let expr = self.parse_expr();
+    self.expect(&token::CloseDelim(token::Paren));
+    expr
+  }
+
+  fn parse_do_stmt(&mut self) -> StmtKind {
+    self.expect_keyword(kw::Do);
+    let body = P(self.parse_stmt());
+    let lo = self.token.span.lo();
+    self.expect_keyword(kw::While);
+    let cond = self.parse_paren_cond();
+    let cond = IterCond { kind: IterCondKind::Do(cond), span: self.span_from(lo) };
+    // From ES2015 a semicolon is inserted after `do .. while (..)` even on the same line,
+    // e.g. `do x; while (y) z`.
+    self.eat(&token::Semi);
+    StmtKind::Iter(P(cond), body)
+  }
+
+  fn parse_while_stmt(&mut self) -> StmtKind {
+    let lo = self.token.span.lo();
+    self.expect_keyword(kw::While);
+    let cond = self.parse_paren_cond();
+    let cond = IterCond { kind: IterCondKind::While(cond), span: self.span_from(lo) };
+    StmtKind::Iter(P(cond), P(self.parse_stmt()))
+  }
+
+  /// Parses `for (..;..;..)`, `for (.. in ..)`, `for (.. of ..)` and `for await (.. of ..)`.
+  fn parse_for_stmt(&mut self) -> StmtKind {
+    let lo = self.token.span.lo();
+    self.expect_keyword(kw::For);
+    let awaitness = self.eat_keyword(kw::Await);
+    self.expect(&token::OpenDelim(token::Paren));
+
+    // `in` is left to `for (.. in ..)`, `for (var a = b in c)` is not an `in` expression.
+    let init = self.with_context(ContextFlags::DisallowIn, 0, |p| {
+      if p.check(&token::Semi) {
+        None
+      } else if p.check_keyword(kw::Var) || p.check_keyword(kw::Let) || p.check_keyword(kw::Const) {
+        Some(ForVal::Decl(P(p.parse_var_decl_list())))
+      } else {
+        Some(ForVal::Expr(p.parse_expr()))
+      }
+    });
+
+    let kind = if awaitness && self.expect_keyword(kw::Of) || self.eat_keyword(kw::Of) {
+      let init = self.for_in_of_target(init, FOR_OF_TARGET);
+      IterCondKind::ForOf(P(init), self.parse_assign_expr(), awaitness)
+    } else if self.eat_keyword(kw::In) {
+      let init = self.for_in_of_target(init, FOR_IN_TARGET);
+      IterCondKind::ForIn(P(init), self.parse_expr())
+    } else {
+      self.expect(&token::Semi);
+      let test = if self.check(&token::Semi) { None } else { Some(self.parse_expr()) };
+      self.expect(&token::Semi);
+      let update =
+        if self.check(&token::CloseDelim(token::Paren)) { None } else { Some(self.parse_expr()) };
+      IterCondKind::For(P(ForCond { init, test, update }))
+    };
+    self.expect(&token::CloseDelim(token::Paren));
+    let cond = IterCond { kind, span: self.span_from(lo) };
+    StmtKind::Iter(P(cond), P(self.parse_stmt()))
+  }
+
+  /// Checks the initializer of a `for .. in` or `for .. of` loop, which is assigned
+  /// the values of the iteration.
+  fn for_in_of_target(&mut self, init: Option<ForVal>, what: &str) -> ForVal {
+    match init {
+      Some(ForVal::Expr(expr)) => ForVal::Pat(P(self.expr_to_pat(expr, true, what))),
+      Some(decl) => decl,
+      None => ForVal::Pat(P(self.expr_to_pat(self.missing_expr(), true, what))),
+    }
+  }
+
+  /// Parses the optional label of `break` and `continue`, which must be on the same line.
+  fn parse_break_label(&mut self) -> Option<Label> {
+    let label = if self.can_parse_semicolon() { None } else { Some(Label(self.parse_ident())) };
+    self.expect_semicolon();
+    label
+  }
+
+  /// Parses a `switch`, its clauses are the statements of its block.
+  fn parse_switch_stmt(&mut self) -> StmtKind {
+    let expr = self.parse_paren_cond();
+    let lo = self.token.span.lo();
+    let mut clauses = Vec::new();
+    let mut multi_line = None;
+    if self.expect(&token::OpenDelim(token::Brace)) {
+      multi_line = Some(self.has_preceding_line_break());
+      clauses = self.parse_list(ParsingContext::SwitchClauses, |p| p.parse_switch_clause());
+      self.expect(&token::CloseDelim(token::Brace));
+    }
+    StmtKind::Switch(expr, P(Block { stmts: clauses, span: self.span_from(lo), multi_line }))
+  }
+
+  fn parse_switch_clause(&mut self) -> Stmt {
+    let lo = self.token.span.lo();
+    let case = if self.eat_keyword(kw::Case) {
+      Some(self.parse_expr())
+    } else {
+      self.expect_keyword(kw::Default);
+      None
+    };
+    self.expect(&token::Colon);
+    let stmts = self.parse_list(ParsingContext::SwitchClauseStatements, |p| p.parse_stmt());
+    let branch = match case {
+      Some(expr) => SwitchBranch::Case(expr, stmts),
+      None => SwitchBranch::Default(stmts),
+    };
+    Stmt { kind: StmtKind::SwitchBranch(branch), span: self.span_from(lo) }
+  }
+
+  fn parse_throw_stmt(&mut self) -> StmtKind {
+    let expr = if self.has_preceding_line_break() {
+      self.error(self.prev_token.span.shrink_to_hi(), "Line break not permitted here.");
+      self.missing_expr()
+    } else {
+      self.parse_expr()
+    };
+    self.expect_semicolon();
+    StmtKind::Throw(expr)
+  }
+
+  /// Parses a `try` statement, which needs a `catch` or `finally` block. A stray `catch`
+  /// or `finally` is parsed the same, reporting the missing `try`.
+  fn parse_try_stmt(&mut self) -> StmtKind {
+    self.expect_keyword(kw::Try);
+    let block = P(self.parse_block());
+    let catch =
+      if self.check_keyword(kw::Catch) { Some(P(self.parse_catch_block())) } else { None };
+    let finally = if catch.is_none() || self.check_keyword(kw::Finally) {
+      self.expect_keyword(kw::Finally);
+      Some(self.parse_block())
+    } else {
+      None
+    };
+    StmtKind::Try(block, catch, finally)
+  }
+
+  fn parse_catch_block(&mut self) -> CatchBlock {
+    let lo = self.token.span.lo();
+    self.expect_keyword(kw::Catch);
+    let decl = if self.eat(&token::OpenDelim(token::Paren)) {
+      let decl = self.parse_var_decl();
+      self.expect(&token::CloseDelim(token::Paren));
+      Some(decl)
+    } else {
+      None
+    };
+    let block = self.parse_block();
+    CatchBlock { decl, block, span: self.span_from(lo) }
+  }
+
+  /// Parses an expression statement, or a labeled statement if the expression
+  /// turns out to be an identifier followed by `:`.
+  fn parse_expr_or_labeled_stmt(&mut self) -> StmtKind {
+    let expr = self.parse_expr();
+    if let ExprKind::Ident(ident) = expr.kind {
+      if ident.name != kw::Empty && self.eat(&token::Colon) {
+        return StmtKind::Label(Label(ident), P(self.parse_stmt()));
+      }
+    }
+    self.expect_semicolon();
+    StmtKind::Expr(expr)
+  }
+}
@@ -0,0 +1,4540 @@
+use super::*;
+
+use expect_test::{expect, Expect};
+use std::fmt::{Display, Write};
+use tscore_ast::ptr::P;
+use tscore_ast::*;
+use tscore_span::{with_default_session_globals, Pos};
+
+/// Prints an AST as an indented tree, one node per line with its span.
+#[derive(Default)]
+struct Printer {
+  out: String,
+  depth: usize,
+}
+
+fn sp(span: Span) -> String {
+  format!("{}..{}", span.lo().to_usize(), span.hi().to_usize())
+}
+
+impl Printer {
+  fn node(&mut self, label: impl Display, span: Span, children: impl FnOnce(&mut Self)) {
+    writeln!(self.out, "{}{} {}", "  ".repeat(self.depth), label, sp(span)).unwrap();
+    self.depth += 1;
+    children(self);
+    self.depth -= 1;
+  }
+
+  fn leaf(&mut self, label: impl Display, span: Span) {
+    self.node(label, span, |_| ())
+  }
+
+  fn none(&mut self) {
+    writeln!(self.out, "{}None", "  ".repeat(self.depth)).unwrap();
+  }
+
+  fn module(&mut self, module: &Module) {
+    self.node("Module", module.span, |p| module.items.iter().for_each(|stmt| p.stmt(stmt)));
+  }
+
+  fn block(&mut self, label: &str, block: &Block) {
+    self.node(label, block.span, |p| block.stmts.iter().for_each(|stmt| p.stmt(stmt)));
+  }
+
+  fn stmt(&mut self, stmt: &Stmt) {
+    let span = stmt.span;
+    match &stmt.kind {
+      StmtKind::Empty => self.leaf("Empty", span),
+      StmtKind::Debugger => self.leaf("Debugger", span),
+      StmtKind::Block(block) => self.block("Block", block),
+      StmtKind::If(cond, then, els) => self.node("If", span, |p| {
+        p.expr(cond);
+        p.stmt(then);
+        if let Some(els) = els {
+          p.stmt(els);
+        }
+      }),
+      StmtKind::Iter(cond, body) => {
+        let label = match &cond.kind {
+          IterCondKind::Do(_) => "Do",
+          IterCondKind::For(_) => "For",
+          IterCondKind::ForIn(..) => "ForIn",
+          IterCondKind::ForOf(.., false) => "ForOf",
+          IterCondKind::ForOf(.., true) => "ForAwaitOf",
+          IterCondKind::While(_) => "While",
+        };
+        self.node(label, span, |p| {
+          match &cond.kind {
+            IterCondKind::Do(expr) | IterCondKind::While(expr) => p.expr(expr),
+            IterCondKind::For(cond) => {
+              match &cond.init {
+                Some(init) => p.for_val(init),
+                None => p.none(),
+              }
+              p.opt_expr(&cond.test);
+              p.opt_expr(&cond.update);
+            }
+            IterCondKind::ForIn(init, expr) | IterCondKind::ForOf(init, expr, _) => {
+              p.for_val(init);
+              p.expr(expr);
+            }
+          }
+          p.stmt(body);
+        })
+      }
+      StmtKind::Break(label) => self.leaf(label_str("Break", label.as_ref()), span),
+      StmtKind::Continue(label) => self.leaf(label_str("Continue", label.as_ref()), span),
+      StmtKind::Ret(expr) => self.node("Return", span, |p| p.opt_expr(expr)),
+      StmtKind::With(expr, body) => self.node("With", span, |p| {
+        p.expr(expr);
+        p.stmt(body);
+      }),
+      StmtKind::Switch(expr, block) => self.node("Switch", span, |p| {
+        p.expr(expr);
+        p.block("Clauses", block);
+      }),
+      StmtKind::SwitchBranch(SwitchBranch::Case(expr, stmts)) => self.node("Case", span, |p| {
+        p.expr(expr);
+        stmts.iter().for_each(|stmt| p.stmt(stmt));
+      }),
+      StmtKind::SwitchBranch(SwitchBranch::Default(stmts)) => {
+        self.node("Default", span, |p| stmts.iter().for_each(|stmt| p.stmt(stmt)))
+      }
+      StmtKind::Label(label, body) => self.node(label_str("Label", Some(label)), span, |p| {
+        p.stmt(body);
+      }),
+      StmtKind::Throw(expr) => self.node("Throw", span, |p| p.expr(expr)),
+      StmtKind::Try(block, catch, finally) => self.node("Try", span, |p| {
+        p.block("Block", block);
+        if let Some(catch) = catch {
+          p.node("Catch", catch.span, |p| {
+            match &catch.decl {
+              Some(decl) => p.var_decl(decl),
+              None => p.none(),
+            }
+            p.block("Block", &catch.block);
+          });
+        }
+        if let Some(finally) = finally {
+          p.block("Finally", finally);
+        }
+      }),
+      StmtKind::Decl(decl) => self.decl(decl),
+      StmtKind::Expr(expr) => self.node("Expr", span, |p| p.expr(expr)),
+      kind => self.leaf(format!("{:?}", kind), span),
+    }
+  }
+
+  fn for_val(&mut self, val: &ForVal) {
+    match val {
+      ForVal::Expr(expr) => self.expr(expr),
+      ForVal::Decl(list) => self.var_decl_list(list),
+      ForVal::Pat(pat) => self.pat(pat),
+    }
+  }
+
+  fn decl(&mut self, decl: &Decl) {
+    if decl.is_export {
+      let label = if decl.is_default { "ExportDefault" } else { "Export" };
+      let inner = Decl { is_export: false, is_default: false, ..decl.clone() };
+      return self.node(label, decl.span, |p| p.decl(&inner));
+    }
+    if decl.ambient {
+      let inner = Decl { ambient: false, ..decl.clone() };
+      return self.node("Ambient", decl.span, |p| p.decl(&inner));
+    }
+    match &decl.kind {
+      DeclKind::Var(list) => self.var_decl_list(list),
+      DeclKind::Fn(f) => self.func("FnDecl", f),
+      DeclKind::Class(class) => self.class("ClassDecl", class),
+      DeclKind::Interface(interface) => {
+        self.node(format!("Interface {}", interface.name.name), interface.span, |p| {
+          p.generics(&interface.generics);
+          p.heritages(&interface.heritages);
+          interface.elements.iter().for_each(|member| p.ty_member(member));
+        })
+      }
+      DeclKind::Type(alias) => {
+        self.node(format!("TypeAlias {}", alias.name.name), alias.span, |p| {
+          p.generics(&alias.generics);
+          p.ty(&alias.ty);
+        })
+      }
+      DeclKind::Enum(e) => self.enum_decl(e),
+      DeclKind::Module(module) => self.module_decl(module),
+      DeclKind::Import(import) => {
+        let label = if import.type_only { "Import type" } else { "Import" };
+        self.node(label, import.span, |p| {
+          if let Some(default) = import.default {
+            p.leaf(format!("Default {}", default.name), default.span);
+          }
+          if let Some(namespace) = import.namespace {
+            p.leaf(format!("Namespace {}", namespace.name), namespace.span);
+          }
+          p.name_bindings(&import.names);
+          p.leaf(format!("From \"{}\"", import.from.symbol), import.from.span);
+          p.import_attributes(&import.attributes);
+        });
+      }
+      DeclKind::ImportEquals(import) => {
+        let ty = if import.type_only { " type" } else { "" };
+        let label = format!("ImportEquals{} {}", ty, import.name.name);
+        self.node(label, import.span, |p| match &import.module_ref {
+          ModuleRef::Require(lit) => p.leaf(format!("Require \"{}\"", lit.symbol), lit.span),
+          ModuleRef::Entity(name) => p.leaf(format!("Entity {}", entity_name_str(name)), decl.span),
+        });
+      }
+      DeclKind::Export(export) => {
+        let label = if export.all { "ExportAll" } else { "ExportNamed" };
+        let ty = if export.type_only { " type" } else { "" };
+        self.node(format!("{}{}", label, ty), export.span, |p| {
+          if let Some(namespace) = export.namespace {
+            p.leaf(format!("Namespace {}", namespace.name), namespace.span);
+          }
+          p.name_bindings(&export.names);
+          if let Some(from) = export.from {
+            p.leaf(format!("From \"{}\"", from.symbol), from.span);
+          }
+          p.import_attributes(&export.attributes);
+        });
+      }
+      DeclKind::ExportAssign(assign) => {
+        let label = if assign.is_export_equals { "ExportAssign =" } else { "ExportAssign default" };
+        self.node(label, assign.span, |p| p.expr(&assign.expr));
+      }
+      DeclKind::ExportAsNamespace(name) => {
+        self.leaf(format!("ExportAsNamespace {}", name.name), decl.span)
+      }
+    }
+  }
+
+  fn name_bindings(&mut self, names: &[NameBinding]) {
+    for binding in names {
+      let ty = if binding.type_only { "type " } else { "" };
+      let alias = binding.alias.map_or(String::new(), |alias| format!(" as {}", alias.name));
+      self.leaf(format!("Name {}{}{}", ty, binding.name.name, alias), binding.span);
+    }
+  }
+
+  fn import_attributes(&mut self, attributes: &[ImportAttribute]) {
+    for attribute in attributes {
+      self.node("Attribute", attribute.span, |p| {
+        p.prop_name(&attribute.name);
+        p.expr(&attribute.value);
+      });
+    }
+  }
+
+  fn enum_decl(&mut self, e: &Enum) {
+    let label = format!("{}Enum {}", if e.is_const { "Const" } else { "" }, e.name.name);
+    self.node(label, e.span, |p| {
+      for member in &e.members {
+        p.node("EnumMember", member.span, |p| {
+          p.prop_name(&member.name);
+          if let Some(init) = &member.init {
+            p.expr(init);
+          }
+        });
+      }
+    });
+  }
+
+  fn module_decl(&mut self, module: &ModuleDecl) {
+    let label = match &module.name {
+      ModuleName::Ident(name) if module.is_namespace => format!("Namespace {}", name.name),
+      ModuleName::Ident(name) => format!("Module {}", name.name),
+      ModuleName::Str(lit) => format!("Module \"{}\"", lit.symbol),
+      ModuleName::Global(_) => "Global".to_owned(),
+    };
+    self.node(label, module.span, |p| match &module.body {
+      Some(ModuleBody::Block(block)) => p.block("Block", block),
+      Some(ModuleBody::Decl(inner)) => p.module_decl(inner),
+      None => (),
+    });
+  }
+
+  fn var_decl_list(&mut self, list: &VarDeclList) {
+    self.node(format!("{:?}", list.kind), list.span, |p| {
+      list.decls.iter().for_each(|decl| p.var_decl(decl));
+    });
+  }
+
+  fn var_decl(&mut self, decl: &VarDecl) {
+    let label = if decl.definite { "VarDecl!" } else { "VarDecl" };
+    self.node(label, decl.span, |p| {
+      p.pat(&decl.name);
+      if let Some(ty) = &decl.ty {
+        p.ty(ty);
+      }
+      if let Some(init) = &decl.init {
+        p.expr(init);
+      }
+    });
+  }
+
+  fn pat(&mut self, pat: &Pat) {
+    match &pat.kind {
+      PatKind::Ident(ident) => self.leaf(format!("Ident {}", ident.name), pat.span),
+      PatKind::Array(array) => self.node("ArrayPat", pat.span, |p| {
+        array.elements.iter().for_each(|el| p.binding_el(el));
+      }),
+      PatKind::Object(object) => self.node("ObjectPat", pat.span, |p| {
+        object.elements.iter().for_each(|el| p.binding_el(el));
+      }),
+      PatKind::Expr(expr) => self.expr(expr),
+    }
+  }
+
+  fn binding_el(&mut self, el: &BindingElement) {
+    let name = match &el.name {
+      Some(name) => name,
+      None => return self.leaf("Omitted", el.span),
+    };
+    self.node(if el.rest { "RestEl" } else { "BindingEl" }, el.span, |p| {
+      if let Some(prop_name) = &el.prop_name {
+        p.prop_name(prop_name);
+      }
+      p.pat(name);
+      if let Some(init) = &el.init {
+        p.expr(init);
+      }
+    })
+  }
+
+  fn opt_expr(&mut self, expr: &Option<P<Expr>>) {
+    match expr {
+      Some(expr) => self.expr(expr),
+      None => self.none(),
+    }
+  }
+
+  fn expr(&mut self, expr: &Expr) {
+    let span = expr.span;
+    match &expr.kind {
+      ExprKind::Ident(ident) => self.leaf(format!("Ident {}", ident), span),
+      ExprKind::PrivateIdent(ident) => self.leaf(format!("PrivateIdent {}", ident), span),
+      ExprKind::Lit(lit) => match &lit.kind {
+        LitKind::Template(template) if !template.spans.is_empty() => self.template(template),
+        _ => self.leaf(format!("Lit {}", lit_str(lit)), span),
+      },
+      ExprKind::This => self.leaf("This", span),
+      ExprKind::Super => self.leaf("Super", span),
+      ExprKind::Paren(expr) => self.node("Paren", span, |p| p.expr(expr)),
+      ExprKind::Import => self.leaf("Import", span),
+      ExprKind::Omitted => self.leaf("Omitted", span),
+      ExprKind::Meta(name) => self.leaf(format!("Meta {}", name), span),
+      ExprKind::Spread(expr) => self.node("Spread", span, |p| p.expr(expr)),
+      ExprKind::NonNull(expr) => self.node("NonNull", span, |p| p.expr(expr)),
+      ExprKind::Unary(op, expr) => {
+        self.node(format!("Unary {}", op.node.as_str()), span, |p| p.expr(expr))
+      }
+      ExprKind::Update(op, expr, prefix) => {
+        let op = if op.node == UpdateOpKind::PlusPlus { "++" } else { "--" };
+        let fix = if *prefix { "prefix" } else { "postfix" };
+        self.node(format!("Update {} {}", op, fix), span, |p| p.expr(expr))
+      }
+      ExprKind::Binary(op, left, right) => {
+        self.node(format!("Binary {}", op.node.as_str()), span, |p| {
+          p.expr(left);
+          p.expr(right);
+        })
+      }
+      ExprKind::Assign(assign) => {
+        self.node(format!("Assign {:?}", assign.operator.node), span, |p| {
+          p.pat(&assign.left);
+          p.expr(&assign.right);
+        })
+      }
+      ExprKind::Cond(cond) => self.node("Cond", span, |p| {
+        p.expr(&cond.test);
+        p.expr(&cond.consequent);
+        p.expr(&cond.alternate);
+      }),
+      ExprKind::PropAccess(access) => {
+        let label = if access.optional { "PropAccess?." } else { "PropAccess" };
+        self.node(format!("{} {}", label, access.name.name), span, |p| p.expr(&access.expr))
+      }
+      ExprKind::ElementAccess(access) => {
+        let label = if access.optional { "ElementAccess?." } else { "ElementAccess" };
+        self.node(label, span, |p| {
+          p.expr(&access.expr);
+          p.expr(&access.member);
+        })
+      }
+      ExprKind::Call(call) => self.node(if call.optional { "Call?." } else { "Call" }, span, |p| {
+        p.expr(&call.expr);
+        call.type_args.iter().for_each(|ty| p.ty(ty));
+        call.args.iter().for_each(|arg| p.expr(arg));
+      }),
+      ExprKind::WithTypeArgs(expr) => self.node("WithTypeArgs", span, |p| {
+        p.expr(&expr.expr);
+        expr.type_args.iter().for_each(|ty| p.ty(ty));
+      }),
+      ExprKind::New(new) => {
+        self.node(if new.args.is_some() { "New" } else { "New without args" }, span, |p| {
+          p.expr(&new.expr);
+          new.type_args.iter().for_each(|ty| p.ty(ty));
+          new.args.iter().flatten().for_each(|arg| p.expr(arg));
+        })
+      }
+      ExprKind::TaggedTemplate(tag, type_args, template) => {
+        self.node("TaggedTemplate", span, |p| {
+          p.expr(tag);
+          type_args.iter().for_each(|ty| p.ty(ty));
+          p.template(template);
+        })
+      }
+      ExprKind::Array(array) => {
+        self.node("Array", span, |p| array.elements.iter().for_each(|el| p.expr(el)))
+      }
+      ExprKind::Object(object) => self.node("Object", span, |p| {
+        object.props.iter().for_each(|prop| p.object_lit_el(prop));
+      }),
+      ExprKind::Fn(f) => self.func("Fn", f),
+      ExprKind::ArrowFn(f) => self.func("ArrowFn", f),
+      ExprKind::Class(class) => self.class("ClassExpr", class),
+      ExprKind::Yield(expr, delegate) => {
+        self.node(if *delegate { "Yield*" } else { "Yield" }, span, |p| {
+          if let Some(expr) = expr {
+            p.expr(expr);
+          }
+        })
+      }
+      ExprKind::As(expr, ty) => self.node("As", span, |p| {
+        p.expr(expr);
+        p.ty(ty);
+      }),
+      ExprKind::Assert(ty, expr) => self.node("Assert", span, |p| {
+        p.ty(ty);
+        p.expr(expr);
+      }),
+      ExprKind::JsxEl(el) => self.jsx_el(el),
+      kind => self.leaf(format!("{:?}", kind), span),
+    }
+  }
+
+  fn jsx_el(&mut self, el: &JsxEl) {
+    let label = match (&el.opening_el, &el.closing_el) {
+      (None, _) => "JsxFragment",
+      (Some(_), None) => "JsxSelfClosingEl",
+      (Some(_), Some(_)) => "JsxEl",
+    };
+    self.node(label, el.span, |p| {
+      if let Some(opening) = &el.opening_el {
+        p.node(format!("Opening {}", jsx_tag_str(&opening.tag)), opening.span, |p| {
+          opening.type_args.iter().for_each(|ty| p.ty(ty));
+          opening.props.iter().for_each(|prop| p.jsx_prop(prop));
+        });
+      }
+      el.children.iter().for_each(|child| p.jsx_child(child));
+      if let Some(closing) = &el.closing_el {
+        p.leaf(format!("Closing {}", jsx_tag_str(&closing.tag)), closing.span);
+      }
+    })
+  }
+
+  fn jsx_prop(&mut self, prop: &JsxProp) {
+    match &prop.kind {
+      JsxPropKind::Attr(name, value) => self.node(format!("Attr {}", name), prop.span, |p| {
+        if let Some(value) = value {
+          p.expr(value);
+        }
+      }),
+      JsxPropKind::Spread(expr) => self.node("SpreadAttr", prop.span, |p| p.expr(expr)),
+    }
+  }
+
+  fn jsx_child(&mut self, child: &JsxChild) {
+    match &child.kind {
+      JsxChildKind::Text(text, only_whitespace) => {
+        let label = if *only_whitespace { "Text(whitespace)" } else { "Text" };
+        self.leaf(format!("{} {:?}", label, text), child.span)
+      }
+      JsxChildKind::Expr(expr) => {
+        self.node(if expr.spread { "JsxSpreadChild" } else { "JsxExpr" }, expr.span, |p| {
+          if let Some(expr) = &expr.expr {
+            p.expr(expr);
+          }
+        })
+      }
+      JsxChildKind::El(el) => self.jsx_el(el),
+    }
+  }
+
+  fn func(&mut self, kind: &str, f: &Fn) {
+    let mut label = String::new();
+    if f.asyncness.is_some() {
+      label.push_str("async ");
+    }
+    label.push_str(kind);
+    if f.generator.is_some() {
+      label.push('*');
+    }
+    if let Some(name) = f.name {
+      write!(label, " {}", name.name).unwrap();
+    }
+    self.node(label, f.span, |p| p.fn_sig_and_body(f));
+  }
+
+  fn fn_sig_and_body(&mut self, f: &Fn) {
+    self.sig(&f.sig);
+    match f.body.as_deref() {
+      Some(FnBody::Block(block)) => self.block("Block", block),
+      Some(FnBody::Expr(expr)) => self.expr(expr),
+      None => self.none(),
+    }
+  }
+
+  fn sig(&mut self, sig: &FnSig) {
+    self.generics(&sig.generics);
+    sig.params.iter().for_each(|param| self.param(param));
+    if let FnRet::Ty(ty) = &sig.ret {
+      self.node("Ret", ty.span, |p| p.ty(ty));
+    }
+  }
+
+  fn generics(&mut self, generics: &Generics) {
+    generics.params.iter().for_each(|param| self.generic_param(param));
+  }
+
+  fn generic_param(&mut self, param: &GenericParam) {
+    self.node(format!("TypeParam {}", param.name.name), param.span, |p| {
+      if let Some(constraint) = &param.constraint {
+        p.node("Extends", constraint.span, |p| p.ty(constraint));
+      }
+      if let Some(default) = &param.default {
+        p.node("Default", default.span, |p| p.ty(default));
+      }
+    });
+  }
+
+  fn param(&mut self, param: &FnParam) {
+    let mut label = vis_str(param.vis).to_owned();
+    if param.is_override {
+      label.push_str("override ");
+    }
+    if param.readonly {
+      label.push_str("readonly ");
+    }
+    label.push_str("Param");
+    if param.rest {
+      label.push_str("...");
+    }
+    if param.optional {
+      label.push('?');
+    }
+    self.node(label, param.span, |p| {
+      p.decorators(&param.decorators);
+      p.pat(&param.name);
+      if let Some(ty) = &param.ty {
+        p.ty(ty);
+      }
+      if let Some(init) = &param.init {
+        p.expr(init);
+      }
+    });
+  }
+
+  fn decorators(&mut self, decorators: &[Decorator]) {
+    for decorator in decorators {
+      self.node("Decorator", decorator.span, |p| p.expr(&decorator.expr));
+    }
+  }
+
+  fn class(&mut self, kind: &str, class: &Class) {
+    let mut label = String::new();
+    if class.is_abstract {
+      label.push_str("abstract ");
+    }
+    label.push_str(kind);
+    if let Some(name) = class.name {
+      write!(label, " {}", name.name).unwrap();
+    }
+    self.node(label, class.span, |p| {
+      p.decorators(&class.decorators);
+      p.generics(&class.generics);
+      p.heritages(&class.heritages);
+      class.elements.iter().for_each(|el| p.class_element(el));
+    });
+  }
+
+  fn heritages(&mut self, heritages: &[Heritage]) {
+    for heritage in heritages {
+      let (label, heritage) = match heritage {
+        Heritage::Extends(heritage) => ("Extends", heritage),
+        Heritage::Impl(heritage) => ("Implements", heritage),
+      };
+      self.node(label, heritage.span, |p| {
+        p.expr(&heritage.expr);
+        heritage.type_args.iter().for_each(|ty| p.ty(ty));
+      });
+    }
+  }
+
+  fn class_element(&mut self, el: &ClassElement) {
+    let mut label = vis_str(el.vis).to_owned();
+    let flags = [
+      (el.is_static, "static "),
+      (el.is_abstract, "abstract "),
+      (el.is_override, "override "),
+      (el.readonly, "readonly "),
+      (el.is_declare, "declare "),
+    ];
+    for (_, flag) in flags.iter().filter(|(set, _)| *set) {
+      label.push_str(flag);
+    }
+    let f = match &el.kind {
+      ClassElementKind::Constructor(f)
+      | ClassElementKind::Getter(f)
+      | ClassElementKind::Setter(f)
+      | ClassElementKind::Method(f) => Some(f),
+      _ => None,
+    };
+    if f.map_or(false, |f| f.asyncness.is_some()) {
+      label.push_str("async ");
+    }
+    label.push_str(match el.kind {
+      ClassElementKind::Semi => "Semi",
+      ClassElementKind::Constructor(_) => "Constructor",
+      ClassElementKind::Getter(_) => "Getter",
+      ClassElementKind::Setter(_) => "Setter",
+      ClassElementKind::IndexSig(_) => "IndexSig",
+      ClassElementKind::Method(_) => "Method",
+      ClassElementKind::Property(_) => "Property",
+    });
+    if f.map_or(false, |f| f.generator.is_some()) {
+      label.push('*');
+    }
+    if el.optional {
+      label.push('?');
+    }
+    if el.definite {
+      label.push('!');
+    }
+    self.node(label, el.span, |p| {
+      p.decorators(&el.decorators);
+      if let Some(name) = &el.name {
+        p.prop_name(name);
+      }
+      if let Some(f) = f {
+        p.fn_sig_and_body(f);
+      }
+      match &el.kind {
+        ClassElementKind::IndexSig(index) => {
+          index.params.iter().for_each(|param| p.param(param));
+          if let Some(ty) = &index.ty {
+            p.ty(ty);
+          }
+        }
+        ClassElementKind::Property(prop) => {
+          if let Some(ty) = &prop.ty {
+            p.ty(ty);
+          }
+          if let Some(init) = &prop.init {
+            p.expr(init);
+          }
+        }
+        _ => (),
+      }
+    });
+  }
+
+  fn template(&mut self, template: &TemplateLit) {
+    let head = template.head.map_or(String::new(), |head| head.symbol.to_string());
+    self.node(format!("Template {:?}", head), template.span, |p| {
+      for span in &template.spans {
+        let lit = span.lit.map_or("None".to_owned(), |lit| format!("{:?}", lit.symbol.as_str()));
+        p.node(format!("Span {}", lit), span.span, |p| p.expr(&span.expr));
+      }
+    });
+  }
+
+  fn object_lit_el(&mut self, el: &ObjectLitEl) {
+    match &el.kind {
+      ObjectLitElKind::PropAssign(prop) | ObjectLitElKind::ShortPropAssign(prop) => {
+        let label = match el.kind {
+          ObjectLitElKind::PropAssign(_) => "Prop",
+          _ => "ShortProp",
+        };
+        self.node(label, el.span, |p| {
+          p.prop_name(&prop.name);
+          p.expr(&prop.init);
+        })
+      }
+      ObjectLitElKind::SpreadAssign(expr) => self.node("Spread", el.span, |p| p.expr(expr)),
+      ObjectLitElKind::MethodDecl(f) | ObjectLitElKind::Getter(f) | ObjectLitElKind::Setter(f) => {
+        let kind = match el.kind {
+          ObjectLitElKind::Getter(_) => "Getter",
+          ObjectLitElKind::Setter(_) => "Setter",
+          _ => "Method",
+        };
+        let mut label = String::new();
+        if f.asyncness.is_some() {
+          label.push_str("async ");
+        }
+        label.push_str(kind);
+        if f.generator.is_some() {
+          label.push('*');
+        }
+        self.node(label, el.span, |p| {
+          if let Some(name) = &el.name {
+            p.prop_name(name);
+          }
+          p.fn_sig_and_body(f);
+        })
+      }
+    }
+  }
+
+  fn prop_name(&mut self, name: &PropName) {
+    match name {
+      PropName::Ident(ident) => self.leaf(format!("Ident {}", ident.name), ident.span),
+      PropName::NumLit(lit) => self.leaf(format!("Lit {}", lit_str(lit)), lit.span),
+      PropName::Expr(expr) => self.node("Computed", expr.span, |p| p.expr(expr)),
+      PropName::PrivateIdent(ident) => self.leaf(format!("PrivateIdent {}", ident), ident.span),
+    }
+  }
+
+  fn ty(&mut self, ty: &Ty) {
+    let span = ty.span;
+    if let Some(PropName::Ident(name)) = &ty.name {
+      let optional = if ty.optional { "?" } else { "" };
+      let unnamed = Ty { name: None, optional: false, ..ty.clone() };
+      return self.node(format!("Member {}{}", name.name, optional), span, |p| p.ty(&unnamed));
+    }
+    match &ty.kind {
+      TyKind::Keyword(name) => self.leaf(format!("Keyword {}", name), span),
+      TyKind::This => self.leaf("This", span),
+      TyKind::Import => self.leaf("Import", span),
+      TyKind::Fn(sig) => self.node("FnTy", span, |p| p.sig(sig)),
+      TyKind::Constructor(sig) => self.node("ConstructorTy", span, |p| p.sig(sig)),
+      TyKind::Ref(ty_ref) => match &ty_ref.kind {
+        RefTyKind::Ident(name, args) => {
+          self.node(format!("Ref {}", entity_name_str(name)), span, |p| {
+            args.iter().for_each(|arg| p.ty(arg))
+          })
+        }
+        kind => self.leaf(format!("{:?}", kind), span),
+      },
+      TyKind::Query(name) => self.leaf(format!("Typeof {}", entity_name_str(name)), span),
+      TyKind::Lit(lit) => self.leaf(format!("Lit {}", lit_str(lit)), span),
+      TyKind::TyLit(members) => {
+        self.node("TyLit", span, |p| members.iter().for_each(|member| p.ty_member(member)))
+      }
+      TyKind::Arr(ty) => self.node("Array", span, |p| p.ty(ty)),
+      TyKind::Tuple(tys) => self.node("Tuple", span, |p| tys.iter().for_each(|ty| p.ty(ty))),
+      TyKind::Rest(ty) => self.node("Rest", span, |p| p.ty(ty)),
+      TyKind::Optional(ty) => self.node("Optional", span, |p| p.ty(ty)),
+      TyKind::Union(tys) => self.node("Union", span, |p| tys.iter().for_each(|ty| p.ty(ty))),
+      TyKind::Intersection(tys) => {
+        self.node("Intersection", span, |p| tys.iter().for_each(|ty| p.ty(ty)))
+      }
+      TyKind::Cond(cond) => self.node("Cond", span, |p| {
+        let CondTy(check, extends, true_ty, false_ty) = &**cond;
+        [check, extends, true_ty, false_ty].iter().for_each(|ty| p.ty(ty));
+      }),
+      TyKind::Op(op) => {
+        let op_str = match op.kind {
+          OpTyKind::Keyof => "keyof",
+          OpTyKind::Readonly => "readonly",
+          OpTyKind::Uniq => "unique",
+        };
+        self.node(format!("Op {}", op_str), span, |p| p.ty(&op.target))
+      }
+      TyKind::Paren(ty) => self.node("Paren", span, |p| p.ty(ty)),
+      TyKind::Infer(param) => self.leaf(format!("Infer {}", param.name.name), span),
+      TyKind::Map(map) => {
+        let readonly =
+          map.readonly_flag.as_ref().map(|flag| format!(" {}readonly", flag_str(flag)));
+        let optional = map.optional_flag.as_ref().map(|flag| format!(" {}?", flag_str(flag)));
+        let label = format!("Map{}{}", readonly.unwrap_or_default(), optional.unwrap_or_default());
+        self.node(label, span, |p| {
+          p.generic_param(&map.type_param);
+          if let Some(name_type) = &map.name_type {
+            p.node("As", name_type.span, |p| p.ty(name_type));
+          }
+          match &map.ty {
+            Some(ty) => p.ty(ty),
+            None => p.none(),
+          }
+        })
+      }
+      TyKind::Index(index) => self.node("Index", span, |p| {
+        p.ty(&index.0);
+        p.ty(&index.1);
+      }),
+      TyKind::Template(template) => {
+        let head = template.head.map_or(String::new(), |head| head.symbol.to_string());
+        self.node(format!("Template {:?}", head), span, |p| {
+          for span in &template.spans {
+            let lit =
+              span.lit.map_or("None".to_owned(), |lit| format!("{:?}", lit.symbol.as_str()));
+            p.node(format!("Span {}", lit), span.span, |p| p.ty(&span.ty));
+          }
+        })
+      }
+      TyKind::Predicate(predicate) => {
+        let asserts = if predicate.asserts { "asserts " } else { "" };
+        let label = format!("Predicate {}{}", asserts, predicate.param.name);
+        self.node(label, span, |p| {
+          if let Some(ty) = &predicate.ty {
+            p.ty(ty);
+          }
+        })
+      }
+    }
+  }
+
+  fn ty_member(&mut self, member: &InterfaceElement) {
+    match member {
+      InterfaceElement::CallSig(sig) => self.node("CallSig", sig.span, |p| p.sig(sig)),
+      InterfaceElement::ConstructorSig(sig) => {
+        self.node("ConstructorSig", sig.span, |p| p.sig(sig))
+      }
+      InterfaceElement::PropSig(prop) => {
+        let readonly = if prop.readonly { "readonly " } else { "" };
+        let optional = if prop.optional { "?" } else { "" };
+        self.node(format!("{}PropSig{}", readonly, optional), prop.span, |p| {
+          p.prop_name(&prop.name);
+          if let Some(ty) = &prop.ty {
+            p.ty(ty);
+          }
+        })
+      }
+      InterfaceElement::MethodSig(method) => {
+        let optional = if method.optional { "?" } else { "" };
+        self.node(format!("MethodSig{}", optional), method.span, |p| {
+          p.prop_name(&method.name);
+          p.sig(&method.sig);
+        })
+      }
+      InterfaceElement::IndexSig(index) => {
+        let readonly = if index.readonly { "readonly " } else { "" };
+        self.node(format!("{}IndexSig", readonly), index.span, |p| {
+          index.params.iter().for_each(|param| p.param(param));
+          if let Some(ty) = &index.ty {
+            p.ty(ty);
+          }
+        })
+      }
+    }
+  }
+}
+
+fn entity_name_str(name: &EntityName) -> String {
+  match name {
+    EntityName::Ident(ident) => ident.name.to_string(),
+    EntityName::Qualified(left, right) => format!("{}.{}", entity_name_str(left), right.name),
+  }
+}
+
+fn vis_str(vis: Option<Visibility>) -> &'static str {
+  match vis {
+    Some(Visibility::Public) => "public ",
+    Some(Visibility::Protected) => "protected ",
+    Some(Visibility::Private) => "private ",
+    None => "",
+  }
+}
+
+fn flag_str(flag: &ModFlag) -> &'static str {
+  match flag {
+    ModFlag::Raw => "",
+    ModFlag::Plus => "+",
+    ModFlag::Minus => "-",
+  }
+}
+
+fn label_str(kind: &str, label: Option<&Label>) -> String {
+  match label {
+    Some(Label(ident)) => format!("{} {}", kind, ident),
+    None => kind.to_owned(),
+  }
+}
+
+fn jsx_tag_str(tag: &JsxTagNameKind) -> String {
+  match tag {
+    JsxTagNameKind::Ident(ident) => ident.to_string(),
+    JsxTagNameKind::This(_) => "this".to_owned(),
+    JsxTagNameKind::PropAccess(access) => {
+      format!("{}.{}", jsx_tag_expr_str(&access.expr), access.name)
+    }
+  }
+}
+
+fn jsx_tag_expr_str(expr: &Expr) -> String {
+  match &expr.kind {
+    ExprKind::Ident(ident) => ident.to_string(),
+    ExprKind::This => "this".to_owned(),
+    ExprKind::PropAccess(access) => format!("{}.{}", jsx_tag_expr_str(&access.expr), access.name),
+    kind => unreachable!("{:?} in a JSX tag name", kind),
+  }
+}
+
+fn lit_str(lit: &Lit) -> String {
+  match &lit.kind {
+    LitKind::Str(value) => format!("{:?}", value),
+    LitKind::Num(value) => value.to_string(),
+    LitKind::Bool(value) => value.to_string(),
+    LitKind::Null => "null".to_owned(),
+    LitKind::Template(template) => {
+      format!("`{}`", template.head.map_or(String::new(), |head| head.symbol.to_string()))
+    }
+    _ => lit.token.to_string(),
+  }
+}
+
+fn parse(src: &str, file_name: &str) -> String {
+  with_default_session_globals(|| {
+    let (module, errors) =
+      parse_source_file(file_name, src, ScriptTarget::ESNext, ScriptKind::Unknown);
+    let mut printer = Printer::default();
+    printer.module(&module);
+    for err in errors {
+      writeln!(printer.out, "error {}: {}", sp(err.span), err.msg).unwrap();
+    }
+    printer.out
+  })
+}
+
+fn check(src: &str, expect: Expect) {
+  expect.assert_eq(&parse(src, "test.ts"))
+}
+
+#[test]
+fn empty_file() {
+  check(
+    "",
+    expect![[r#"
+        Module 0..0
+    "#]],
+  );
+}
+
+#[test]
+fn blocks_and_expression_statements() {
+  check(
+    "a; { b\n 'c' } ;",
+    expect![[r#"
+        Module 0..15
+          Expr 0..2
+            Ident a 0..1
+          Block 3..13
+            Expr 5..6
+              Ident b 5..6
+            Expr 8..11
+              Lit "c" 8..11
+          Empty 14..15
+    "#]],
+  );
+}
+
+#[test]
+fn if_statements() {
+  check(
+    "if (a) b; else if (c) {} else d",
+    expect![[r#"
+        Module 0..31
+          If 0..31
+            Ident a 4..5
+            Expr 7..9
+              Ident b 7..8
+            If 15..31
+              Ident c 19..20
+              Block 22..24
+              Expr 30..31
+                Ident d 30..31
+    "#]],
+  );
+}
+
+#[test]
+fn loops() {
+  check(
+    "do a; while (b) while (c) ; for (;;) ; for (i; j; k) {} for (let x in o) ; for (x of xs) ; for await (const x of xs) ;",
+    expect![[r#"
+        Module 0..118
+          Do 0..15
+            Ident b 13..14
+            Expr 3..5
+              Ident a 3..4
+          While 16..27
+            Ident c 23..24
+            Empty 26..27
+          For 28..38
+            None
+            None
+            None
+            Empty 37..38
+          For 39..55
+            Ident i 44..45
+            Ident j 47..48
+            Ident k 50..51
+            Block 53..55
+          ForIn 56..74
+            Let 61..66
+              VarDecl 65..66
+                Ident x 65..66
+            Ident o 70..71
+            Empty 73..74
+          ForOf 75..90
+            Ident x 80..81
+            Ident xs 85..87
+            Empty 89..90
+          ForAwaitOf 91..118
+            Const 102..109
+              VarDecl 108..109
+                Ident x 108..109
+            Ident xs 113..115
+            Empty 117..118
+    "#]],
+  );
+}
+
+#[test]
+fn variable_statements() {
+  check(
+    "var a = 1, b; let c! = d; const e = 'e'; let = 1; let\nf",
+    expect![[r#"
+        Module 0..55
+          Var 0..12
+            VarDecl 4..9
+              Ident a 4..5
+              Lit 1 8..9
+            VarDecl 11..12
+              Ident b 11..12
+          Let 14..24
+            VarDecl! 18..24
+              Ident c 18..19
+              Ident d 23..24
+          Const 26..39
+            VarDecl 32..39
+              Ident e 32..33
+              Lit "e" 36..39
+          Expr 41..49
+            Assign Assign 41..48
+              Ident let 41..44
+              Lit 1 47..48
+          Let 50..55
+            VarDecl 54..55
+              Ident f 54..55
+    "#]],
+  );
+}
+
+#[test]
+fn break_continue_and_labels() {
+  check(
+    "outer: for (;;) { inner: while (a) { continue outer; break\ninner } }",
+    expect![[r#"
+        Module 0..68
+          Label outer 0..68
+            For 7..68
+              None
+              None
+              None
+              Block 16..68
+                Label inner 18..66
+                  While 25..66
+                    Ident a 32..33
+                    Block 35..66
+                      Continue outer 37..52
+                      Break 53..58
+                      Expr 59..64
+                        Ident inner 59..64
+    "#]],
+  );
+}
+
+#[test]
+fn return_throw_debugger_and_with() {
+  check(
+    "return; return a\nreturn\nb; throw c; debugger; with (d) e",
+    expect![[r#"
+        Module 0..56
+          Return 0..7
+            None
+          Return 8..16
+            Ident a 15..16
+          Return 17..23
+            None
+          Expr 24..26
+            Ident b 24..25
+          Throw 27..35
+            Ident c 33..34
+          Debugger 36..45
+          With 46..56
+            Ident d 52..53
+            Expr 55..56
+              Ident e 55..56
+    "#]],
+  );
+}
+
+#[test]
+fn throw_needs_expression_on_same_line() {
+  check(
+    "throw\na",
+    expect![[r#"
+        Module 0..7
+          Throw 0..5
+            Ident  6..6
+          Expr 6..7
+            Ident a 6..7
+        error 5..5: Line break not permitted here.
+    "#]],
+  );
+}
+
+#[test]
+fn switch_statements() {
+  check(
+    "switch (a) {\n case 1: b; c\n case 2:\n default: d; x }",
+    expect![[r#"
+        Module 0..52
+          Switch 0..52
+            Ident a 8..9
+            Clauses 11..52
+              Case 14..26
+                Lit 1 19..20
+                Expr 22..24
+                  Ident b 22..23
+                Expr 25..26
+                  Ident c 25..26
+              Case 28..35
+                Lit 2 33..34
+              Default 37..50
+                Expr 46..48
+                  Ident d 46..47
+                Expr 49..50
+                  Ident x 49..50
+    "#]],
+  );
+}
+
+#[test]
+fn try_statements() {
+  check(
+    "try {} catch (e) {} try {} finally {} try {} catch {} finally {}",
+    expect![[r#"
+        Module 0..64
+          Try 0..19
+            Block 4..6
+            Catch 7..19
+              VarDecl 14..15
+                Ident e 14..15
+              Block 17..19
+          Try 20..37
+            Block 24..26
+            Finally 35..37
+          Try 38..64
+            Block 42..44
+            Catch 45..53
+              None
+              Block 51..53
+            Finally 62..64
+    "#]],
+  );
+}
+
+#[test]
+fn try_without_catch_or_finally() {
+  check(
+    "try {} a; catch (e) {}",
+    expect![[r#"
+        Module 0..22
+          Try 0..6
+            Block 4..6
+            Finally 7..7
+          Expr 7..9
+            Ident a 7..8
+          Try 10..22
+            Block 10..10
+            Catch 10..22
+              VarDecl 17..18
+                Ident e 17..18
+              Block 20..22
+        error 7..8: 'finally' expected.
+        error 10..15: 'try' expected.
+    "#]],
+  );
+}
+
+#[test]
+fn missing_semicolons_and_parens() {
+  check(
+    "a b; if a) {} while (b {} var if = 1; )",
+    expect![[r#"
+        Module 0..39
+          Expr 0..1
+            Ident a 0..1
+          Expr 2..4
+            Ident b 2..3
+          If 5..13
+            Ident a 8..9
+            Block 11..13
+          While 14..25
+            Ident b 21..22
+            Block 23..25
+          Var 26..29
+          If 30..37
+            Assign Assign 33..36
+              Ident  33..33
+              Lit 1 35..36
+            Empty 36..37
+        error 2..3: ';' expected.
+        error 8..9: '(' expected.
+        error 23..24: ')' expected.
+        error 30..32: 'if' is not allowed as a variable declaration name.
+        error 33..34: '(' expected.
+        error 36..37: ')' expected.
+        error 38..39: Declaration or statement expected.
+    "#]],
+  );
+}
+
+#[test]
+fn literals() {
+  check(
+    "1; 0x10; 1_000n; 'a\\x41'; \"b\"; `c`; /d/g; true; false; null; this",
+    expect![[r#"
+        Module 0..65
+          Expr 0..2
+            Lit 1 0..1
+          Expr 3..8
+            Lit 16 3..7
+          Expr 9..16
+            Lit 1_000n 9..15
+          Expr 17..25
+            Lit "aA" 17..24
+          Expr 26..30
+            Lit "b" 26..29
+          Expr 31..35
+            Lit `c` 31..34
+          Expr 36..41
+            Lit /d/g 36..40
+          Expr 42..47
+            Lit true 42..46
+          Expr 48..54
+            Lit false 48..53
+          Expr 55..60
+            Lit null 55..59
+          Expr 61..65
+            This 61..65
+    "#]],
+  );
+}
+
+#[test]
+fn scanner_errors_are_reported() {
+  check(
+    "'abc\n1",
+    expect![[r#"
+        Module 0..6
+          Expr 0..4
+            Lit "abc" 0..4
+          Expr 5..6
+            Lit 1 5..6
+        error 0..4: Unterminated string literal.
+    "#]],
+  );
+}
+
+#[test]
+fn binary_precedence() {
+  check(
+    "a + b * c - d; a ** b ** c; a < b == c && d | e ^ f & g || h; a, b",
+    expect![[r#"
+      Module 0..66
+        Expr 0..14
+          Binary - 0..13
+            Binary + 0..9
+              Ident a 0..1
+              Binary * 4..9
+                Ident b 4..5
+                Ident c 8..9
+            Ident d 12..13
+        Expr 15..27
+          Binary ** 15..26
+            Ident a 15..16
+            Binary ** 20..26
+              Ident b 20..21
+              Ident c 25..26
+        Expr 28..61
+          Binary || 28..60
+            Binary && 28..55
+              Binary == 28..38
+                Binary < 28..33
+                  Ident a 28..29
+                  Ident b 32..33
+                Ident c 37..38
+              Binary | 42..55
+                Ident d 42..43
+                Binary ^ 46..55
+                  Ident e 46..47
+                  Binary & 50..55
+                    Ident f 50..51
+                    Ident g 54..55
+            Ident h 59..60
+        Expr 62..66
+          Binary , 62..66
+            Ident a 62..63
+            Ident b 65..66
+  "#]],
+  );
+}
+
+#[test]
+fn nullish_coalescing_mixed_with_logical_operators() {
+  check(
+    "a ?? b; a || b ?? c; a ?? b && c; (a || b) ?? c; a ?? (b && c)",
+    expect![[r#"
+      Module 0..62
+        Expr 0..7
+          Binary ?? 0..6
+            Ident a 0..1
+            Ident b 5..6
+        Expr 8..20
+          Binary ?? 8..19
+            Binary || 8..14
+              Ident a 8..9
+              Ident b 13..14
+            Ident c 18..19
+        Expr 21..33
+          Binary ?? 21..32
+            Ident a 21..22
+            Binary && 26..32
+              Ident b 26..27
+              Ident c 31..32
+        Expr 34..48
+          Binary ?? 34..47
+            Paren 34..42
+              Binary || 35..41
+                Ident a 35..36
+                Ident b 40..41
+            Ident c 46..47
+        Expr 49..62
+          Binary ?? 49..62
+            Ident a 49..50
+            Paren 54..62
+              Binary && 55..61
+                Ident b 55..56
+                Ident c 60..61
+      error 8..14: '||' and '??' operations cannot be mixed without parentheses.
+      error 26..32: '??' and '&&' operations cannot be mixed without parentheses.
+  "#]],
+  );
+}
+
+#[test]
+fn exponentiation_of_unary_expression() {
+  check(
+    "-a ** b; (-a) ** b; ++a ** b; <T>a ** b; typeof a ** b",
+    expect![[r#"
+      Module 0..54
+        Expr 0..8
+          Binary ** 0..7
+            Unary - 0..2
+              Ident a 1..2
+            Ident b 6..7
+        Expr 9..19
+          Binary ** 9..18
+            Paren 9..13
+              Unary - 10..12
+                Ident a 11..12
+            Ident b 17..18
+        Expr 20..29
+          Binary ** 20..28
+            Update ++ prefix 20..23
+              Ident a 22..23
+            Ident b 27..28
+        Expr 30..40
+          Binary ** 30..39
+            Assert 30..34
+              Ref T 31..32
+              Ident a 33..34
+            Ident b 38..39
+        Expr 41..54
+          Binary ** 41..54
+            Unary typeof 41..49
+              Ident a 48..49
+            Ident b 53..54
+      error 0..2: An unary expression with the '-' operator is not allowed in the left-hand side of an exponentiation expression. Consider enclosing the expression in parentheses.
+      error 30..34: A type assertion expression is not allowed in the left-hand side of an exponentiation expression. Consider enclosing the expression in parentheses.
+      error 41..49: An unary expression with the 'typeof' operator is not allowed in the left-hand side of an exponentiation expression. Consider enclosing the expression in parentheses.
+  "#]],
+  );
+}
+
+#[test]
+fn assignments() {
+  check(
+    "a = b = c; a += 1; a.b ||= c; a[0] &&= b; a ??= b; [a, b] = c; ({ a } = b)",
+    expect![[r#"
+        Module 0..74
+          Expr 0..10
+            Assign Assign 0..9
+              Ident a 0..1
+              Assign Assign 4..9
+                Ident b 4..5
+                Ident c 8..9
+          Expr 11..18
+            Assign AddAssign 11..17
+              Ident a 11..12
+              Lit 1 16..17
+          Expr 19..29
+            Assign OrAssign 19..28
+              PropAccess b 19..22
+                Ident a 19..20
+              Ident c 27..28
+          Expr 30..41
+            Assign AndAssign 30..40
+              ElementAccess 30..34
+                Ident a 30..31
+                Lit 0 32..33
+              Ident b 39..40
+          Expr 42..50
+            Assign QqAssign 42..49
+              Ident a 42..43
+              Ident b 48..49
+          Expr 51..62
+            Assign Assign 51..61
+              ArrayPat 51..57
+                BindingEl 52..53
+                  Ident a 52..53
+                BindingEl 55..56
+                  Ident b 55..56
+              Ident c 60..61
+          Expr 63..74
+            Paren 63..74
+              Assign Assign 64..73
+                ObjectPat 64..69
+                  BindingEl 66..67
+                    Ident a 66..67
+                Ident b 72..73
+    "#]],
+  );
+}
+
+#[test]
+fn invalid_assignment_targets() {
+  check(
+    "a + b = c; 1 = 2; [a] += b; a?.b = c; a?.b.c++; --a(); (a) = 1; a! = 1",
+    expect![[r#"
+      Module 0..70
+        Expr 0..10
+          Assign Assign 0..9
+            Binary + 0..5
+              Ident a 0..1
+              Ident b 4..5
+            Ident c 8..9
+        Expr 11..17
+          Assign Assign 11..16
+            Lit 1 11..12
+            Lit 2 15..16
+        Expr 18..27
+          Assign AddAssign 18..26
+            Array 18..21
+              Ident a 19..20
+            Ident b 25..26
+        Expr 28..37
+          Assign Assign 28..36
+            PropAccess?. b 28..32
+              Ident a 28..29
+            Ident c 35..36
+        Expr 38..47
+          Update ++ postfix 38..46
+            PropAccess c 38..44
+              PropAccess?. b 38..42
+                Ident a 38..39
+        Expr 48..54
+          Update -- prefix 48..53
+            Call 50..53
+              Ident a 50..51
+        Expr 55..63
+          Assign Assign 55..62
+            Paren 55..58
+              Ident a 56..57
+            Lit 1 61..62
+        Expr 64..70
+          Assign Assign 64..70
+            NonNull 64..66
+              Ident a 64..65
+            Lit 1 69..70
+      error 0..5: The left-hand side of an assignment expression must be a variable or a property access.
+      error 11..12: The left-hand side of an assignment expression must be a variable or a property access.
+      error 18..21: The left-hand side of an assignment expression must be a variable or a property access.
+      error 28..32: The left-hand side of an assignment expression may not be an optional property access.
+      error 38..44: The operand of an increment or decrement operator may not be an optional property access.
+      error 50..53: The operand of an increment or decrement operator must be a variable or a property access.
+  "#]],
+  );
+}
+
+#[test]
+fn conditional() {
+  check(
+    "a ? b : c ? d : e; a ? b = 1 : c",
+    expect![[r#"
+      Module 0..32
+        Expr 0..18
+          Cond 0..17
+            Ident a 0..1
+            Ident b 4..5
+            Cond 8..17
+              Ident c 8..9
+              Ident d 12..13
+              Ident e 16..17
+        Expr 19..32
+          Cond 19..32
+            Ident a 19..20
+            Assign Assign 23..28
+              Ident b 23..24
+              Lit 1 27..28
+            Ident c 31..32
+  "#]],
+  );
+}
+
+#[test]
+fn unary_and_update() {
+  check(
+    "!a; ~-+a; typeof a; void 0; delete a.b; await a; a++; --a; a\n++b",
+    expect![[r#"
+      Module 0..64
+        Expr 0..3
+          Unary ! 0..2
+            Ident a 1..2
+        Expr 4..9
+          Unary ~ 4..8
+            Unary - 5..8
+              Unary + 6..8
+                Ident a 7..8
+        Expr 10..19
+          Unary typeof 10..18
+            Ident a 17..18
+        Expr 20..27
+          Unary void 20..26
+            Lit 0 25..26
+        Expr 28..39
+          Unary delete 28..38
+            PropAccess b 35..38
+              Ident a 35..36
+        Expr 40..48
+          Unary await 40..47
+            Ident a 46..47
+        Expr 49..53
+          Update ++ postfix 49..52
+            Ident a 49..50
+        Expr 54..58
+          Update -- prefix 54..57
+            Ident a 56..57
+        Expr 59..60
+          Ident a 59..60
+        Expr 61..64
+          Update ++ prefix 61..64
+            Ident b 63..64
+  "#]],
+  );
+}
+
+#[test]
+fn await_as_identifier() {
+  check(
+    "await; await\na; await(a)",
+    expect![[r#"
+      Module 0..24
+        Expr 0..6
+          Ident await 0..5
+        Expr 7..12
+          Ident await 7..12
+        Expr 13..15
+          Ident a 13..14
+        Expr 16..24
+          Call 16..24
+            Ident await 16..21
+            Ident a 22..23
+  "#]],
+  );
+}
+
+#[test]
+fn member_access_and_calls() {
+  check(
+    "a.b[c](d, ...e).if; a?.b; a?.[b]; a?.(b); a?.b.c(); super.a(); super(); a!.b!",
+    expect![[r#"
+        Module 0..77
+          Expr 0..19
+            PropAccess if 0..18
+              Call 0..15
+                ElementAccess 0..6
+                  PropAccess b 0..3
+                    Ident a 0..1
+                  Ident c 4..5
+                Ident d 7..8
+                Spread 10..14
+                  Ident e 13..14
+          Expr 20..25
+            PropAccess?. b 20..24
+              Ident a 20..21
+          Expr 26..33
+            ElementAccess?. 26..32
+              Ident a 26..27
+              Ident b 30..31
+          Expr 34..41
+            Call?. 34..40
+              Ident a 34..35
+              Ident b 38..39
+          Expr 42..51
+            Call 42..50
+              PropAccess c 42..48
+                PropAccess?. b 42..46
+                  Ident a 42..43
+          Expr 52..62
+            Call 52..61
+              PropAccess a 52..59
+                Super 52..57
+          Expr 63..71
+            Call 63..70
+              Super 63..68
+          Expr 72..77
+            NonNull 72..77
+              PropAccess b 72..76
+                NonNull 72..74
+                  Ident a 72..73
+    "#]],
+  );
+}
+
+#[test]
+fn invalid_member_access() {
+  check(
+    "a[]; a?.; super; a?.`b`",
+    expect![[r#"
+      Module 0..23
+        Expr 0..4
+          ElementAccess 0..3
+            Ident a 0..1
+            Ident  2..2
+        Expr 5..9
+          PropAccess?.  5..8
+            Ident a 5..6
+        Expr 10..16
+          Super 10..15
+        Expr 17..23
+          TaggedTemplate 17..23
+            Ident a 17..18
+            Template "b" 20..23
+      error 2..3: An element access expression should take an argument.
+      error 8..9: Identifier expected.
+      error 15..16: 'super' must be followed by an argument list or member access.
+      error 20..23: Tagged template expressions are not permitted in an optional chain.
+  "#]],
+  );
+}
+
+#[test]
+fn new_expressions() {
+  check(
+    "new A; new A(b); new A.b(c)(d); new new A()(); new.target; new A?.b()",
+    expect![[r#"
+      Module 0..69
+        Expr 0..6
+          New without args 0..5
+            Ident A 4..5
+        Expr 7..16
+          New 7..15
+            Ident A 11..12
+            Ident b 13..14
+        Expr 17..31
+          Call 17..30
+            New 17..27
+              PropAccess b 21..24
+                Ident A 21..22
+              Ident c 25..26
+            Ident d 28..29
+        Expr 32..46
+          New 32..45
+            New 36..43
+              Ident A 40..41
+        Expr 47..58
+          Meta target 47..57
+        Expr 59..69
+          Call 59..69
+            PropAccess?. b 59..67
+              New without args 59..64
+                Ident A 63..64
+      error 64..66: Invalid optional chain from new expression. Did you mean to call 'A()'?
+  "#]],
+  );
+}
+
+#[test]
+fn type_arguments_in_expressions() {
+  check(
+    "f<T>(x); a.b<string>(); a?.<T>(); a?.b<T>(); new Map<string, number>(); tag<T>`a`; a < b > c; a < b >= c; f<T>;\nclass A implements B<T>, C<U> {}",
+    expect![[r#"
+        Module 0..144
+          Expr 0..8
+            Call 0..7
+              Ident f 0..1
+              Ref T 2..3
+              Ident x 5..6
+          Expr 9..23
+            Call 9..22
+              PropAccess b 9..12
+                Ident a 9..10
+              Keyword string 13..19
+          Expr 24..33
+            Call?. 24..32
+              Ident a 24..25
+              Ref T 28..29
+          Expr 34..44
+            Call 34..43
+              PropAccess?. b 34..38
+                Ident a 34..35
+              Ref T 39..40
+          Expr 45..71
+            New 45..70
+              Ident Map 49..52
+              Keyword string 53..59
+              Keyword number 61..67
+          Expr 72..82
+            TaggedTemplate 72..81
+              Ident tag 72..75
+              Ref T 76..77
+              Template "a" 78..81
+          Expr 83..93
+            Binary > 83..92
+              Binary < 83..88
+                Ident a 83..84
+                Ident b 87..88
+              Ident c 91..92
+          Expr 94..105
+            Binary >= 94..104
+              Binary < 94..99
+                Ident a 94..95
+                Ident b 98..99
+              Ident c 103..104
+          Expr 106..111
+            WithTypeArgs 106..110
+              Ident f 106..107
+              Ref T 108..109
+          ClassDecl A 112..144
+            Implements 131..135
+              Ident B 131..132
+              Ref T 133..134
+            Implements 137..141
+              Ident C 137..138
+              Ref U 139..140
+    "#]],
+  );
+}
+
+#[test]
+fn meta_properties() {
+  check(
+    "import.meta; import.metal; new.targets; import(a)",
+    expect![[r#"
+      Module 0..49
+        Expr 0..12
+          Meta meta 0..11
+        Expr 13..26
+          Meta metal 13..25
+        Expr 27..39
+          Meta targets 27..38
+        Expr 40..49
+          Call 40..49
+            Import 40..46
+            Ident a 47..48
+      error 20..25: 'metal' is not a valid meta-property for keyword 'import'. Did you mean 'meta'?
+      error 31..38: 'targets' is not a valid meta-property for keyword 'new'. Did you mean 'target'?
+  "#]],
+  );
+}
+
+#[test]
+fn templates() {
+  check(
+    "`a${b}c${d + e}f`; tag`a${b}`; a.b`c`.d; `a${b`",
+    expect![[r#"
+      Module 0..47
+        Expr 0..18
+          Template "a" 0..17
+            Span "c" 4..9
+              Ident b 4..5
+            Span "f" 9..17
+              Binary + 9..14
+                Ident d 9..10
+                Ident e 13..14
+        Expr 19..30
+          TaggedTemplate 19..29
+            Ident tag 19..22
+            Template "a" 22..29
+              Span "" 26..29
+                Ident b 26..27
+        Expr 31..40
+          PropAccess d 31..39
+            TaggedTemplate 31..37
+              PropAccess b 31..34
+                Ident a 31..32
+              Template "c" 34..37
+        Expr 41..47
+          Template "a" 41..47
+            Span None 45..47
+              TaggedTemplate 45..47
+                Ident b 45..46
+                Template "" 46..47
+      error 47..47: '}' expected.
+      error 47..47: Unterminated template literal.
+  "#]],
+  );
+}
+
+#[test]
+fn template_escapes() {
+  check(
+    "tag`\\u{`; `\\u{`",
+    expect![[r#"
+      Module 0..15
+        Expr 0..9
+          TaggedTemplate 0..8
+            Ident tag 0..3
+            Template "\\u{" 3..8
+        Expr 10..15
+          Lit `\u{` 10..15
+      error 11..14: Hexadecimal digit expected.
+  "#]],
+  );
+}
+
+#[test]
+fn array_literals() {
+  check(
+    "[]; [a, , ...b,]; [,]; [\n a\n]",
+    expect![[r#"
+      Module 0..29
+        Expr 0..3
+          Array 0..2
+        Expr 4..17
+          Array 4..16
+            Ident a 5..6
+            Omitted 8..8
+            Spread 10..14
+              Ident b 13..14
+        Expr 18..22
+          Array 18..21
+            Omitted 19..19
+        Expr 23..29
+          Array 23..29
+            Ident a 26..27
+  "#]],
+  );
+}
+
+#[test]
+fn object_literals() {
+  check(
+    "({ a, b: 1, 'c': 2, 3: d, [e]: f, ...g, h = 1, if: 1 });\n({ a() {} });\n({ get x() { return 1 } });\n({ set x(v) {} });\n({ async *g() {} });\n({ [k]() {} });\n({ get, set: 1, async, async() {}, get() {} })",
+    expect![[r#"
+        Module 0..201
+          Expr 0..56
+            Paren 0..55
+              Object 1..54
+                ShortProp 3..4
+                  Ident a 3..4
+                  Ident a 3..4
+                Prop 6..10
+                  Ident b 6..7
+                  Lit 1 9..10
+                Prop 12..18
+                  Lit "c" 12..15
+                  Lit 2 17..18
+                Prop 20..24
+                  Lit 3 20..21
+                  Ident d 23..24
+                Prop 26..32
+                  Computed 27..28
+                    Ident e 27..28
+                  Ident f 31..32
+                Spread 34..38
+                  Ident g 37..38
+                ShortProp 40..45
+                  Ident h 40..41
+                  Assign Assign 40..45
+                    Ident h 40..41
+                    Lit 1 44..45
+                Prop 47..52
+                  Ident if 47..49
+                  Lit 1 51..52
+          Expr 57..70
+            Paren 57..69
+              Object 58..68
+                Method 60..66
+                  Ident a 60..61
+                  Block 64..66
+          Expr 71..98
+            Paren 71..97
+              Object 72..96
+                Getter 74..94
+                  Ident x 78..79
+                  Block 82..94
+                    Return 84..92
+                      Lit 1 91..92
+          Expr 99..117
+            Paren 99..116
+              Object 100..115
+                Setter 102..113
+                  Ident x 106..107
+                  Param 108..109
+                    Ident v 108..109
+                  Block 111..113
+          Expr 118..138
+            Paren 118..137
+              Object 119..136
+                async Method* 121..134
+                  Ident g 128..129
+                  Block 132..134
+          Expr 139..154
+            Paren 139..153
+              Object 140..152
+                Method 142..150
+                  Computed 143..144
+                    Ident k 143..144
+                  Block 148..150
+          Expr 155..201
+            Paren 155..201
+              Object 156..200
+                ShortProp 158..161
+                  Ident get 158..161
+                  Ident get 158..161
+                Prop 163..169
+                  Ident set 163..166
+                  Lit 1 168..169
+                ShortProp 171..176
+                  Ident async 171..176
+                  Ident async 171..176
+                Method 178..188
+                  Ident async 178..183
+                  Block 186..188
+                Method 190..198
+                  Ident get 190..193
+                  Block 196..198
+        error 42..43: An '=' can only follow a property name when the containing object literal is part of a destructuring pattern.
+    "#]],
+  );
+}
+
+#[test]
+fn type_assertions() {
+  check(
+    "a as string; <number>a; a as A.B as const; a\nas",
+    expect![[r#"
+      Module 0..47
+        Expr 0..12
+          As 0..11
+            Ident a 0..1
+            Keyword string 5..11
+        Expr 13..23
+          Assert 13..22
+            Keyword number 14..20
+            Ident a 21..22
+        Expr 24..42
+          As 24..41
+            As 24..32
+              Ident a 24..25
+              Ref A.B 29..32
+            Ref const 36..41
+        Expr 43..44
+          Ident a 43..44
+        Expr 45..47
+          Ident as 45..47
+  "#]],
+  );
+}
+
+#[test]
+fn no_type_assertions_in_jsx_file() {
+  expect![[r#"
+      Module 0..4
+        Expr 0..4
+          JsxEl 0..4
+            Opening T 0..3
+            Text "a" 3..4
+            Closing  4..4
+      error 1..2: JSX element 'T' has no corresponding closing tag.
+      error 4..4: '</' expected.
+  "#]]
+  .assert_eq(&parse("<T>a", "test.tsx"));
+}
+
+#[test]
+fn for_in_and_of_targets() {
+  check(
+    "for (a.b of c); for (a + b in c); for ([a] of b); for (a?.b of c); for (var a = b in c in d);",
+    expect![[r#"
+        Module 0..93
+          ForOf 0..15
+            PropAccess b 5..8
+              Ident a 5..6
+            Ident c 12..13
+            Empty 14..15
+          ForIn 16..33
+            Binary + 21..26
+              Ident a 21..22
+              Ident b 25..26
+            Ident c 30..31
+            Empty 32..33
+          ForOf 34..49
+            ArrayPat 39..42
+              BindingEl 40..41
+                Ident a 40..41
+            Ident b 46..47
+            Empty 48..49
+          ForOf 50..66
+            PropAccess?. b 55..59
+              Ident a 55..56
+            Ident c 63..64
+            Empty 65..66
+          ForIn 67..93
+            Var 72..81
+              VarDecl 76..81
+                Ident a 76..77
+                Ident b 80..81
+            Binary in 85..91
+              Ident c 85..86
+              Ident d 90..91
+            Empty 92..93
+        error 21..26: The left-hand side of a 'for...in' statement must be a variable or a property access.
+        error 55..59: The left-hand side of a 'for...of' statement may not be an optional property access.
+    "#]],
+  );
+}
+
+#[test]
+fn in_operator_in_for_initializer() {
+  check(
+    "for (a = (b in c); ;); for (a = [b in c]; ;); for (a = b ? c in d : e; ;);",
+    expect![[r#"
+        Module 0..74
+          For 0..22
+            Assign Assign 5..17
+              Ident a 5..6
+              Paren 9..17
+                Binary in 10..16
+                  Ident b 10..11
+                  Ident c 15..16
+            None
+            None
+            Empty 21..22
+          For 23..45
+            Assign Assign 28..40
+              Ident a 28..29
+              Array 32..40
+                Binary in 33..39
+                  Ident b 33..34
+                  Ident c 38..39
+            None
+            None
+            Empty 44..45
+          For 46..74
+            Assign Assign 51..69
+              Ident a 51..52
+              Cond 55..69
+                Ident b 55..56
+                Binary in 59..65
+                  Ident c 59..60
+                  Ident d 64..65
+                Ident e 68..69
+            None
+            None
+            Empty 73..74
+    "#]],
+  );
+}
+
+#[test]
+fn arrow_functions() {
+  check(
+    "() => 1; a => a; (a) => a; (a, b) => { return a }; (a: A, b?: B, ...c) => a; (a = 1): T => a",
+    expect![[r#"
+        Module 0..92
+          Expr 0..8
+            ArrowFn 0..7
+              Lit 1 6..7
+          Expr 9..16
+            ArrowFn 9..15
+              Param 9..10
+                Ident a 9..10
+              Ident a 14..15
+          Expr 17..26
+            ArrowFn 17..25
+              Param 18..19
+                Ident a 18..19
+              Ident a 24..25
+          Expr 27..50
+            ArrowFn 27..49
+              Param 28..29
+                Ident a 28..29
+              Param 31..32
+                Ident b 31..32
+              Block 37..49
+                Return 39..47
+                  Ident a 46..47
+          Expr 51..76
+            ArrowFn 51..75
+              Param 52..56
+                Ident a 52..53
+                Ref A 55..56
+              Param? 58..63
+                Ident b 58..59
+                Ref B 62..63
+              Param... 65..69
+                Ident c 68..69
+              Ident a 74..75
+          Expr 77..92
+            ArrowFn 77..92
+              Param 78..83
+                Ident a 78..79
+                Lit 1 82..83
+              Ret 86..87
+                Ref T 86..87
+              Ident a 91..92
+    "#]],
+  );
+}
+
+#[test]
+fn async_and_generic_arrow_functions() {
+  check(
+    "async x => x; async (x) => await x; async () => {}; <T>(x: T) => x; <T, U extends T = T>() => 1",
+    expect![[r#"
+        Module 0..95
+          Expr 0..13
+            async ArrowFn 0..12
+              Param 6..7
+                Ident x 6..7
+              Ident x 11..12
+          Expr 14..35
+            async ArrowFn 14..34
+              Param 21..22
+                Ident x 21..22
+              Unary await 27..34
+                Ident x 33..34
+          Expr 36..51
+            async ArrowFn 36..50
+              Block 48..50
+          Expr 52..67
+            ArrowFn 52..66
+              TypeParam T 53..54
+              Param 56..60
+                Ident x 56..57
+                Ref T 59..60
+              Ident x 65..66
+          Expr 68..95
+            ArrowFn 68..95
+              TypeParam T 69..70
+              TypeParam U 72..87
+                Extends 82..83
+                  Ref T 82..83
+                Default 86..87
+                  Ref T 86..87
+              Lit 1 94..95
+    "#]],
+  );
+}
+
+#[test]
+fn parenthesized_expressions_are_not_arrow_functions() {
+  check(
+    "(a); (a, b); (a ? b : c); (a)(b); async(a); async; <T>(a); c ? (a) : b; async\nx => x",
+    expect![[r#"
+        Module 0..84
+          Expr 0..4
+            Paren 0..3
+              Ident a 1..2
+          Expr 5..12
+            Paren 5..11
+              Binary , 6..10
+                Ident a 6..7
+                Ident b 9..10
+          Expr 13..25
+            Paren 13..24
+              Cond 14..23
+                Ident a 14..15
+                Ident b 18..19
+                Ident c 22..23
+          Expr 26..33
+            Call 26..32
+              Paren 26..29
+                Ident a 27..28
+              Ident b 30..31
+          Expr 34..43
+            Call 34..42
+              Ident async 34..39
+              Ident a 40..41
+          Expr 44..50
+            Ident async 44..49
+          Expr 51..58
+            Assert 51..57
+              Ref T 52..53
+              Paren 54..57
+                Ident a 55..56
+          Expr 59..71
+            Cond 59..70
+              Ident c 59..60
+              Paren 63..66
+                Ident a 64..65
+              Ident b 69..70
+          Expr 72..77
+            Ident async 72..77
+          Expr 78..84
+            ArrowFn 78..84
+              Param 78..79
+                Ident x 78..79
+              Ident x 83..84
+    "#]],
+  );
+}
+
+#[test]
+fn arrow_function_errors() {
+  check(
+    "a\n=> a; () {}; (a) => ; => a",
+    expect![[r#"
+        Module 0..28
+          Expr 0..7
+            ArrowFn 0..6
+              Param 0..1
+                Ident a 0..1
+              Ident a 5..6
+          Expr 8..14
+            ArrowFn 8..13
+              Block 11..13
+          Expr 15..23
+            ArrowFn 15..21
+              Param 16..17
+                Ident a 16..17
+              Ident  22..22
+          Expr 27..28
+            Ident a 27..28
+        error 2..4: Line terminator not permitted before arrow.
+        error 11..12: '=>' expected.
+        error 22..23: Expression expected.
+        error 24..26: Declaration or statement expected.
+    "#]],
+  );
+}
+
+#[test]
+fn generic_arrow_functions_in_jsx_file() {
+  let src = "<T,>(x: T) => x; <T extends U>(x) => x";
+  expect![[r#"
+      Module 0..38
+        Expr 0..16
+          ArrowFn 0..15
+            TypeParam T 1..2
+            Param 5..9
+              Ident x 5..6
+              Ref T 8..9
+            Ident x 14..15
+        Expr 17..38
+          ArrowFn 17..38
+            TypeParam T 18..29
+              Extends 28..29
+                Ref U 28..29
+            Param 31..32
+              Ident x 31..32
+            Ident x 37..38
+  "#]]
+  .assert_eq(&parse(src, "test.tsx"));
+}
+
+#[test]
+fn concise_arrow_body_in_for_initializer() {
+  check(
+    "for (let f = () => a in b); for (let f = () => { a in b };;);",
+    expect![[r#"
+        Module 0..61
+          ForIn 0..27
+            Let 5..20
+              VarDecl 9..20
+                Ident f 9..10
+                ArrowFn 13..20
+                  Ident a 19..20
+            Ident b 24..25
+            Empty 26..27
+          For 28..61
+            Let 33..57
+              VarDecl 37..57
+                Ident f 37..38
+                ArrowFn 41..57
+                  Block 47..57
+                    Expr 49..55
+                      Binary in 49..55
+                        Ident a 49..50
+                        Ident b 54..55
+            None
+            None
+            Empty 60..61
+    "#]],
+  );
+}
+
+#[test]
+fn functions() {
+  check(
+    "function f(a, b) { return a } function g(): void; let h = function () {}; let i = function j<T>(this: T) {}",
+    expect![[r#"
+        Module 0..107
+          FnDecl f 0..29
+            Param 11..12
+              Ident a 11..12
+            Param 14..15
+              Ident b 14..15
+            Block 17..29
+              Return 19..27
+                Ident a 26..27
+          FnDecl g 30..49
+            Ret 44..48
+              Keyword void 44..48
+            None
+          Let 50..72
+            VarDecl 54..72
+              Ident h 54..55
+              Fn 58..72
+                Block 70..72
+          Let 74..107
+            VarDecl 78..107
+              Ident i 78..79
+              Fn j 82..107
+                TypeParam T 93..94
+                Param 96..103
+                  Ident this 96..100
+                  Ref T 102..103
+                Block 105..107
+    "#]],
+  );
+}
+
+#[test]
+fn async_functions_and_generators() {
+  check(
+    "async function* f() { yield; yield a; yield* b; await c } async\nfunction g() {}",
+    expect![[r#"
+        Module 0..79
+          async FnDecl* f 0..57
+            Block 20..57
+              Expr 22..28
+                Yield 22..27
+              Expr 29..37
+                Yield 29..36
+                  Ident a 35..36
+              Expr 38..47
+                Yield* 38..46
+                  Ident b 45..46
+              Expr 48..55
+                Unary await 48..55
+                  Ident c 54..55
+          Expr 58..63
+            Ident async 58..63
+          FnDecl g 64..79
+            Block 77..79
+    "#]],
+  );
+}
+
+#[test]
+fn yield_and_await_as_identifiers() {
+  check(
+    "function f(yield, await) { yield; await } function* g(yield) {} async function h(await) {}",
+    expect![[r#"
+        Module 0..90
+          FnDecl f 0..41
+            Param 11..16
+              Ident yield 11..16
+            Param 18..23
+              Ident await 18..23
+            Block 25..41
+              Expr 27..33
+                Ident yield 27..32
+              Expr 34..39
+                Ident await 34..39
+          FnDecl* g 42..63
+            Param 54..54
+              Ident  54..54
+            Block 61..63
+          async FnDecl h 64..90
+            Param 81..81
+              Ident  81..81
+            Block 88..90
+        error 54..59: Identifier expected. 'yield' is a reserved word that cannot be used here.
+        error 81..86: Identifier expected. 'await' is a reserved word that cannot be used here.
+    "#]],
+  );
+}
+
+#[test]
+fn keyword_and_reference_types() {
+  check(
+    "let a: string, b: string.A, c: A.B<C, D<E>>, d: A<>, e: typeof a.b, f: this, g: void",
+    expect![[r#"
+        Module 0..84
+          Let 0..84
+            VarDecl 4..13
+              Ident a 4..5
+              Keyword string 7..13
+            VarDecl 15..26
+              Ident b 15..16
+              Ref string.A 18..26
+            VarDecl 28..43
+              Ident c 28..29
+              Ref A.B 31..43
+                Ref C 35..36
+                Ref D 38..42
+                  Ref E 40..41
+            VarDecl 45..51
+              Ident d 45..46
+              Ref A 48..51
+            VarDecl 53..66
+              Ident e 53..54
+              Typeof a.b 56..66
+            VarDecl 68..75
+              Ident f 68..69
+              This 71..75
+            VarDecl 77..84
+              Ident g 77..78
+              Keyword void 80..84
+        error 50..51: Type argument list cannot be empty.
+    "#]],
+  );
+}
+
+#[test]
+fn literal_types() {
+  check(
+    "let a: \"a\" | 1 | -1 | 1n | -1n | true | false | null | `a`",
+    expect![[r#"
+        Module 0..58
+          Let 0..58
+            VarDecl 4..58
+              Ident a 4..5
+              Union 7..58
+                Lit "a" 7..10
+                Lit 1 13..14
+                Lit -1 17..19
+                Lit 1n 22..24
+                Lit -1n 27..30
+                Lit true 33..37
+                Lit false 40..45
+                Keyword null 48..52
+                Template "a" 55..58
+    "#]],
+  );
+}
+
+#[test]
+fn union_and_intersection_types() {
+  check(
+    "let a: A | B & C | D, b: | A | B, c: & A, d: (A | B) & C",
+    expect![[r#"
+        Module 0..56
+          Let 0..56
+            VarDecl 4..20
+              Ident a 4..5
+              Union 7..20
+                Ref A 7..8
+                Intersection 11..16
+                  Ref B 11..12
+                  Ref C 15..16
+                Ref D 19..20
+            VarDecl 22..32
+              Ident b 22..23
+              Union 25..32
+                Ref A 27..28
+                Ref B 31..32
+            VarDecl 34..40
+              Ident c 34..35
+              Intersection 37..40
+                Ref A 39..40
+            VarDecl 42..56
+              Ident d 42..43
+              Intersection 45..56
+                Paren 45..52
+                  Union 46..51
+                    Ref A 46..47
+                    Ref B 50..51
+                Ref C 55..56
+    "#]],
+  );
+}
+
+#[test]
+fn function_types_in_union_types() {
+  check(
+    "let a: A | () => B, b: A & new () => B, c: A | (() => B)",
+    expect![[r#"
+        Module 0..56
+          Let 0..56
+            VarDecl 4..18
+              Ident a 4..5
+              Union 7..18
+                Ref A 7..8
+                FnTy 11..18
+                  Ret 17..18
+                    Ref B 17..18
+            VarDecl 20..38
+              Ident b 20..21
+              Intersection 23..38
+                Ref A 23..24
+                ConstructorTy 27..38
+                  Ret 37..38
+                    Ref B 37..38
+            VarDecl 40..56
+              Ident c 40..41
+              Union 43..56
+                Ref A 43..44
+                Paren 47..56
+                  FnTy 48..55
+                    Ret 54..55
+                      Ref B 54..55
+        error 11..18: Function type notation must be parenthesized when used in a union type.
+        error 27..38: Constructor type notation must be parenthesized when used in an intersection type.
+    "#]],
+  );
+}
+
+#[test]
+fn type_operators() {
+  check(
+    "let a: keyof A[], b: readonly string[], c: unique symbol, d: keyof typeof a, e: infer U",
+    expect![[r#"
+        Module 0..87
+          Let 0..87
+            VarDecl 4..16
+              Ident a 4..5
+              Op keyof 7..16
+                Array 13..16
+                  Ref A 13..14
+            VarDecl 18..38
+              Ident b 18..19
+              Op readonly 21..38
+                Array 30..38
+                  Keyword string 30..36
+            VarDecl 40..56
+              Ident c 40..41
+              Op unique 43..56
+                Keyword symbol 50..56
+            VarDecl 58..75
+              Ident d 58..59
+              Op keyof 61..75
+                Typeof a 67..75
+            VarDecl 77..87
+              Ident e 77..78
+              Infer U 80..87
+    "#]],
+  );
+}
+
+#[test]
+fn array_and_indexed_access_types() {
+  check(
+    "let a: A[][], b: A[\"b\"][C], c: A\n[]",
+    expect![[r#"
+        Module 0..35
+          Let 0..32
+            VarDecl 4..12
+              Ident a 4..5
+              Array 7..12
+                Array 7..10
+                  Ref A 7..8
+            VarDecl 14..26
+              Ident b 14..15
+              Index 17..26
+                Index 17..23
+                  Ref A 17..18
+                  Lit "b" 19..22
+                Ref C 24..25
+            VarDecl 28..32
+              Ident c 28..29
+              Ref A 31..32
+          Expr 33..35
+            Array 33..35
+    "#]],
+  );
+}
+
+#[test]
+fn tuple_types() {
+  check(
+    "let a: [], b: [A, B?, ...C[]], c: [a: A, b?: B, ...c: C[]], d: [string, boolean,]",
+    expect![[r#"
+        Module 0..81
+          Let 0..81
+            VarDecl 4..9
+              Ident a 4..5
+              Tuple 7..9
+            VarDecl 11..29
+              Ident b 11..12
+              Tuple 14..29
+                Ref A 15..16
+                Optional 18..20
+                  Ref B 18..19
+                Rest 22..28
+                  Array 25..28
+                    Ref C 25..26
+            VarDecl 31..58
+              Ident c 31..32
+              Tuple 34..58
+                Member a 35..39
+                  Ref A 35..39
+                Member b? 41..46
+                  Ref B 41..46
+                Member c 48..57
+                  Rest 48..57
+                    Array 54..57
+                      Ref C 54..55
+            VarDecl 60..81
+              Ident d 60..61
+              Tuple 63..81
+                Keyword string 64..70
+                Keyword boolean 72..79
+    "#]],
+  );
+}
+
+#[test]
+fn function_and_constructor_types() {
+  check(
+    "let a: () => void, b: (a: A, b?: B, ...c) => R, c: <T>(a: T) => T, d: new (a) => A, e: (a) => b",
+    expect![[r#"
+        Module 0..95
+          Let 0..95
+            VarDecl 4..17
+              Ident a 4..5
+              FnTy 7..17
+                Ret 13..17
+                  Keyword void 13..17
+            VarDecl 19..46
+              Ident b 19..20
+              FnTy 22..46
+                Param 23..27
+                  Ident a 23..24
+                  Ref A 26..27
+                Param? 29..34
+                  Ident b 29..30
+                  Ref B 33..34
+                Param... 36..40
+                  Ident c 39..40
+                Ret 45..46
+                  Ref R 45..46
+            VarDecl 48..65
+              Ident c 48..49
+              FnTy 51..65
+                TypeParam T 52..53
+                Param 55..59
+                  Ident a 55..56
+                  Ref T 58..59
+                Ret 64..65
+                  Ref T 64..65
+            VarDecl 67..82
+              Ident d 67..68
+              ConstructorTy 70..82
+                Param 75..76
+                  Ident a 75..76
+                Ret 81..82
+                  Ref A 81..82
+            VarDecl 84..95
+              Ident e 84..85
+              FnTy 87..95
+                Param 88..89
+                  Ident a 88..89
+                Ret 94..95
+                  Ref b 94..95
+    "#]],
+  );
+}
+
+#[test]
+fn parenthesized_types_are_not_function_types() {
+  check(
+    "let a: (A), b: (A | B)[], c: (A) => B",
+    expect![[r#"
+        Module 0..37
+          Let 0..37
+            VarDecl 4..10
+              Ident a 4..5
+              Paren 7..10
+                Ref A 8..9
+            VarDecl 12..24
+              Ident b 12..13
+              Array 15..24
+                Paren 15..22
+                  Union 16..21
+                    Ref A 16..17
+                    Ref B 20..21
+            VarDecl 26..37
+              Ident c 26..27
+              FnTy 29..37
+                Param 30..31
+                  Ident A 30..31
+                Ret 36..37
+                  Ref B 36..37
+    "#]],
+  );
+}
+
+#[test]
+fn conditional_types() {
+  check(
+    "let a: A extends B ? C : D, b: A extends B ? C extends D ? E : F : G, c: A extends (B extends C ? D : E) ? F : G",
+    expect![[r#"
+        Module 0..112
+          Let 0..112
+            VarDecl 4..26
+              Ident a 4..5
+              Cond 7..26
+                Ref A 7..8
+                Ref B 17..18
+                Ref C 21..22
+                Ref D 25..26
+            VarDecl 28..68
+              Ident b 28..29
+              Cond 31..68
+                Ref A 31..32
+                Ref B 41..42
+                Cond 45..64
+                  Ref C 45..46
+                  Ref D 55..56
+                  Ref E 59..60
+                  Ref F 63..64
+                Ref G 67..68
+            VarDecl 70..112
+              Ident c 70..71
+              Cond 73..112
+                Ref A 73..74
+                Paren 83..104
+                  Cond 84..103
+                    Ref B 84..85
+                    Ref C 94..95
+                    Ref D 98..99
+                    Ref E 102..103
+                Ref F 107..108
+                Ref G 111..112
+    "#]],
+  );
+}
+
+#[test]
+fn conditional_type_in_extends_type() {
+  check(
+    "let a: A extends B extends C ? D : E ? F : G",
+    expect![[r#"
+        Module 0..44
+          Let 0..44
+            VarDecl 4..28
+              Ident a 4..5
+              Cond 7..28
+                Ref A 7..8
+                Ref B 17..18
+                Ref extends 19..26
+                Ref C 27..28
+            VarDecl 31..36
+              Ident D 31..32
+              Ref E 35..36
+            VarDecl 39..44
+              Ident F 39..40
+              Ref G 43..44
+        error 19..26: '?' expected.
+        error 27..28: ':' expected.
+        error 29..30: ',' expected.
+        error 37..38: ',' expected.
+    "#]],
+  );
+}
+
+#[test]
+fn type_literals() {
+  check(
+    "let a: { a: A; readonly b?: B, c, (a): R; new (a): R; d?(): void; [k: string]: V; readonly [k: number]: V; readonly: R }",
+    expect![[r#"
+        Module 0..120
+          Let 0..120
+            VarDecl 4..120
+              Ident a 4..5
+              TyLit 7..120
+                PropSig 9..14
+                  Ident a 9..10
+                  Ref A 12..13
+                readonly PropSig? 15..30
+                  Ident b 24..25
+                  Ref B 28..29
+                PropSig 31..33
+                  Ident c 31..32
+                CallSig 34..40
+                  Param 35..36
+                    Ident a 35..36
+                  Ret 39..40
+                    Ref R 39..40
+                ConstructorSig 42..52
+                  Param 47..48
+                    Ident a 47..48
+                  Ret 51..52
+                    Ref R 51..52
+                MethodSig? 54..65
+                  Ident d 54..55
+                  Ret 60..64
+                    Keyword void 60..64
+                IndexSig 66..81
+                  Param 67..76
+                    Ident k 67..68
+                    Keyword string 70..76
+                  Ref V 79..80
+                readonly IndexSig 82..106
+                  Param 92..101
+                    Ident k 92..93
+                    Keyword number 95..101
+                  Ref V 104..105
+                PropSig 107..118
+                  Ident readonly 107..115
+                  Ref R 117..118
+    "#]],
+  );
+}
+
+#[test]
+fn type_literal_computed_names_and_errors() {
+  check(
+    "let a: { [a]: A; \"b\": B; 1(): C; readonly c(): D; ) }",
+    expect![[r#"
+        Module 0..53
+          Let 0..53
+            VarDecl 4..53
+              Ident a 4..5
+              TyLit 7..53
+                PropSig 9..16
+                  Computed 10..11
+                    Ident a 10..11
+                  Ref A 14..15
+                PropSig 17..24
+                  Lit "b" 17..20
+                  Ref B 22..23
+                MethodSig 25..32
+                  Lit 1 25..26
+                  Ret 30..31
+                    Ref C 30..31
+                MethodSig 33..49
+                  Ident c 42..43
+                  Ret 47..48
+                    Ref D 47..48
+        error 33..41: 'readonly' modifier can only appear on a property declaration or index signature.
+        error 50..51: Property or signature expected.
+    "#]],
+  );
+}
+
+#[test]
+fn mapped_types() {
+  check(
+    "let a: { [P in K]: T }, b: { readonly [P in K]?: T }, c: { -readonly [P in K]-?: T; }, d: { +readonly [P in K as N]+? }",
+    expect![[r#"
+        Module 0..119
+          Let 0..119
+            VarDecl 4..22
+              Ident a 4..5
+              Map 7..22
+                TypeParam P 10..16
+                  Extends 15..16
+                    Ref K 15..16
+                Ref T 19..20
+            VarDecl 24..52
+              Ident b 24..25
+              Map readonly ? 27..52
+                TypeParam P 39..45
+                  Extends 44..45
+                    Ref K 44..45
+                Ref T 49..50
+            VarDecl 54..85
+              Ident c 54..55
+              Map -readonly -? 57..85
+                TypeParam P 70..76
+                  Extends 75..76
+                    Ref K 75..76
+                Ref T 81..82
+            VarDecl 87..119
+              Ident d 87..88
+              Map +readonly +? 90..119
+                TypeParam P 103..109
+                  Extends 108..109
+                    Ref K 108..109
+                As 113..114
+                  Ref N 113..114
+                None
+    "#]],
+  );
+}
+
+#[test]
+fn template_literal_types() {
+  check(
+    "let a: `a${B}c${D | E}f`, b: `${A}`, c: `a${B`",
+    expect![[r#"
+        Module 0..46
+          Let 0..45
+            VarDecl 4..24
+              Ident a 4..5
+              Template "a" 7..24
+                Span "c" 11..16
+                  Ref B 11..12
+                Span "f" 16..24
+                  Union 16..21
+                    Ref D 16..17
+                    Ref E 20..21
+            VarDecl 26..35
+              Ident b 26..27
+              Template "" 29..35
+                Span "" 32..35
+                  Ref A 32..33
+            VarDecl 37..45
+              Ident c 37..38
+              Template "a" 40..45
+                Span None 44..45
+                  Ref B 44..45
+          Expr 45..46
+            Lit `` 45..46
+        error 45..46: '}' expected.
+        error 46..46: Unterminated template literal.
+    "#]],
+  );
+}
+
+#[test]
+fn type_predicates() {
+  check(
+    "function f(a): a is A {} function g(a): asserts a {} function h(a): asserts a is A {} function i(): this is A {} let j: (a) => a is A",
+    expect![[r#"
+        Module 0..133
+          FnDecl f 0..24
+            Param 11..12
+              Ident a 11..12
+            Ret 15..21
+              Predicate a 15..21
+                Ref A 20..21
+            Block 22..24
+          FnDecl g 25..52
+            Param 36..37
+              Ident a 36..37
+            Ret 40..49
+              Predicate asserts a 40..49
+            Block 50..52
+          FnDecl h 53..85
+            Param 64..65
+              Ident a 64..65
+            Ret 68..82
+              Predicate asserts a 68..82
+                Ref A 81..82
+            Block 83..85
+          FnDecl i 86..112
+            Ret 100..109
+              Predicate this 100..109
+                Ref A 108..109
+            Block 110..112
+          Let 113..133
+            VarDecl 117..133
+              Ident j 117..118
+              FnTy 120..133
+                Param 121..122
+                  Ident a 121..122
+                Ret 127..133
+                  Predicate a 127..133
+                    Ref A 132..133
+    "#]],
+  );
+}
+
+#[test]
+fn type_predicate_requires_same_line() {
+  check(
+    "function f(a): a\nis; function g(a): asserts\na;",
+    expect![[r#"
+        Module 0..46
+          FnDecl f 0..16
+            Param 11..12
+              Ident a 11..12
+            Ret 15..16
+              Ref a 15..16
+            None
+          Expr 17..20
+            Ident is 17..19
+          FnDecl g 21..43
+            Param 32..33
+              Ident a 32..33
+            Ret 36..43
+              Ref asserts 36..43
+            None
+          Expr 44..46
+            Ident a 44..45
+    "#]],
+  );
+}
+
+#[test]
+fn arrow_function_with_broken_return_type() {
+  check(
+    "a ? (b) : c => d : e; (a): => b",
+    expect![[r#"
+        Module 0..31
+          Expr 0..21
+            Cond 0..20
+              Ident a 0..1
+              ArrowFn 4..16
+                Param 5..6
+                  Ident b 5..6
+                Ret 10..11
+                  Ref c 10..11
+                Ident d 15..16
+              Ident e 19..20
+          Expr 22..25
+            Paren 22..25
+              Ident a 23..24
+          Expr 30..31
+            Ident b 30..31
+        error 25..26: ';' expected.
+        error 27..29: Declaration or statement expected.
+    "#]],
+  );
+}
+
+#[test]
+fn class_declarations_and_expressions() {
+  check(
+    "class A {}\nclass B<T> extends A implements I, J<T> {}\nabstract class C extends D.E<T> {}\nlet c = class extends A {};\nclass implements I {}",
+    expect![[r#"
+        Module 0..138
+          ClassDecl A 0..10
+          ClassDecl B 11..53
+            TypeParam T 19..20
+            Extends 30..31
+              Ident A 30..31
+            Implements 43..44
+              Ident I 43..44
+            Implements 46..50
+              Ident J 46..47
+              Ref T 48..49
+          abstract ClassDecl C 54..88
+            Extends 79..85
+              PropAccess E 79..82
+                Ident D 79..80
+              Ref T 83..84
+          Let 89..115
+            VarDecl 93..115
+              Ident c 93..94
+              ClassExpr 97..115
+                Extends 111..112
+                  Ident A 111..112
+          ClassDecl 117..138
+            Implements 134..135
+              Ident I 134..135
+    "#]],
+  );
+}
+
+#[test]
+fn class_heritage_errors() {
+  check(
+    "class A extends {}\nabstract\nclass B {}",
+    expect![[r#"
+        Module 0..38
+          ClassDecl A 0..18
+          Expr 19..27
+            Ident abstract 19..27
+          ClassDecl B 28..38
+        error 15..15: 'extends' list cannot be empty.
+    "#]],
+  );
+}
+
+#[test]
+fn class_members() {
+  check(
+    "class A {\n  a;\n  b?: T = 1;\n  c!: T;\n  'd' = 2;\n  1: T;\n  [e]: T;\n  #f = 3;\n  ;\n  g() {}\n  *h<T>(a: T): T;\n  async i() { await a; }\n  get j(): T { return 1; }\n  set j(v) {}\n  get() {}\n  delete() {}\n  [k: string]: T;\n  static\n  l = 4;\n}",
+    expect![[r#"
+        Module 0..235
+          ClassDecl A 0..235
+            Property 12..14
+              Ident a 12..13
+            Property? 17..27
+              Ident b 17..18
+              Ref T 21..22
+              Lit 1 25..26
+            Property! 30..36
+              Ident c 30..31
+              Ref T 34..35
+            Property 39..47
+              Lit "d" 39..42
+              Lit 2 45..46
+            Property 50..55
+              Lit 1 50..51
+              Ref T 53..54
+            Property 58..65
+              Computed 59..60
+                Ident e 59..60
+              Ref T 63..64
+            Property 68..75
+              PrivateIdent #f 68..70
+              Lit 3 73..74
+            Semi 78..79
+            Method 82..88
+              Ident g 82..83
+              Block 86..88
+            Method* 91..106
+              Ident h 92..93
+              TypeParam T 94..95
+              Param 97..101
+                Ident a 97..98
+                Ref T 100..101
+              Ret 104..105
+                Ref T 104..105
+              None
+            async Method 109..131
+              Ident i 115..116
+              Block 119..131
+                Expr 121..129
+                  Unary await 121..128
+                    Ident a 127..128
+            Getter 134..158
+              Ident j 138..139
+              Ret 143..144
+                Ref T 143..144
+              Block 145..158
+                Return 147..156
+                  Lit 1 154..155
+            Setter 161..172
+              Ident j 165..166
+              Param 167..168
+                Ident v 167..168
+              Block 170..172
+            Method 175..183
+              Ident get 175..178
+              Block 181..183
+            Method 186..197
+              Ident delete 186..192
+              Block 195..197
+            IndexSig 200..215
+              Param 201..210
+                Ident k 201..202
+                Keyword string 204..210
+              Ref T 213..214
+            static Property 218..233
+              Ident l 227..228
+              Lit 4 231..232
+    "#]],
+  );
+}
+
+#[test]
+fn class_member_errors() {
+  check(
+    "class A {\n  a b;\n  %;\n  if\n  constructor\n}",
+    expect![[r#"
+        Module 0..42
+          ClassDecl A 0..16
+            Property 12..13
+              Ident a 12..13
+            Property 14..16
+              Ident b 14..15
+          Expr 19..21
+            Binary % 19..20
+              Ident  19..19
+              Ident  20..20
+          If 24..40
+            Ident constructor 29..40
+            Expr 41..41
+              Ident  41..41
+        error 14..15: ';' expected.
+        error 19..20: Unexpected token. A constructor, method, accessor, or property was expected.
+        error 20..21: Expression expected.
+        error 29..40: '(' expected.
+        error 41..42: ')' expected.
+    "#]],
+  );
+}
+
+#[test]
+fn class_modifiers() {
+  check(
+    "abstract class A {\n  public static readonly a = 1;\n  protected abstract b(): void;\n  private declare c: T;\n  public override d() {}\n  static async *e() {}\n  readonly [f: string]: T;\n}",
+    expect![[r#"
+        Module 0..183
+          abstract ClassDecl A 0..183
+            public static readonly Property 21..50
+              Ident a 44..45
+              Lit 1 48..49
+            protected abstract Method 53..82
+              Ident b 72..73
+              Ret 77..81
+                Keyword void 77..81
+              None
+            private declare Property 85..106
+              Ident c 101..102
+              Ref T 104..105
+            public override Method 109..131
+              Ident d 125..126
+              Block 129..131
+            static async Method* 134..154
+              Ident e 148..149
+              Block 152..154
+            readonly IndexSig 157..181
+              Param 167..176
+                Ident f 167..168
+                Keyword string 170..176
+              Ref T 179..180
+    "#]],
+  );
+}
+
+#[test]
+fn class_modifier_errors() {
+  check(
+    "class A {\n  public public a;\n  static public b;\n  readonly static c;\n  abstract d();\n  readonly e() {}\n  private #f;\n  declare g() {}\n  static constructor() {}\n  public [h: string]: T;\n  async get i() {}\n  override declare j;\n}\nabstract class B {\n  static abstract a();\n  private abstract b();\n  override abstract c();\n  abstract constructor() {}\n}",
+    expect![[r#"
+        Module 0..348
+          ClassDecl A 0..227
+            public Property 12..28
+              Ident a 26..27
+            public static Property 31..47
+              Ident b 45..46
+            static readonly Property 50..68
+              Ident c 66..67
+            abstract Method 71..84
+              Ident d 80..81
+              None
+            readonly Method 87..102
+              Ident e 96..97
+              Block 100..102
+            private Property 105..116
+              PrivateIdent #f 113..115
+            declare Method 119..133
+              Ident g 127..128
+              Block 131..133
+            static Constructor 136..159
+              Block 157..159
+            public IndexSig 162..184
+              Param 170..179
+                Ident h 170..171
+                Keyword string 173..179
+              Ref T 182..183
+            Getter 187..203
+              Ident i 197..198
+              Block 201..203
+            override declare Property 206..225
+              Ident j 223..224
+          abstract ClassDecl B 228..348
+            static abstract Method 249..269
+              Ident a 265..266
+              None
+            private abstract Method 272..293
+              Ident b 289..290
+              None
+            abstract override Method 296..318
+              Ident c 314..315
+              None
+            abstract Constructor 321..346
+              Block 344..346
+        error 19..25: Accessibility modifier already seen.
+        error 38..44: 'public' modifier must precede 'static' modifier.
+        error 59..65: 'static' modifier must precede 'readonly' modifier.
+        error 71..79: Abstract methods can only appear within an abstract class.
+        error 87..95: 'readonly' modifier can only appear on a property declaration or index signature.
+        error 105..112: An accessibility modifier cannot be used with a private identifier.
+        error 119..126: 'declare' modifier cannot appear on class elements of this kind.
+        error 136..142: 'static' modifier cannot appear on a constructor declaration.
+        error 162..168: 'public' modifier cannot appear on an index signature.
+        error 187..192: 'async' modifier cannot be used here.
+        error 215..222: 'declare' modifier cannot be used with 'override' modifier.
+        error 256..264: 'static' modifier cannot be used with 'abstract' modifier.
+        error 280..288: 'private' modifier cannot be used with 'abstract' modifier.
+        error 305..313: 'abstract' modifier must precede 'override' modifier.
+        error 321..329: 'abstract' modifier can only appear on a class, method, or property declaration.
+    "#]],
+  );
+}
+
+#[test]
+fn constructors_and_parameter_properties() {
+  check(
+    "class A {\n  constructor(private a, public readonly b: T, @inject() c = 1) {}\n  constructor(private a);\n  m(readonly a) {}\n  constructor<T>(public ...a): T {}\n  'constructor'() {}\n}\nlet f = (private a) => a;",
+    expect![[r#"
+        Module 0..206
+          ClassDecl A 0..180
+            Constructor 12..76
+              private Param 24..33
+                Ident a 32..33
+              public readonly Param 35..55
+                Ident b 51..52
+                Ref T 54..55
+              Param 57..72
+                Decorator 57..66
+                  Call 58..66
+                    Ident inject 58..64
+                Ident c 67..68
+                Lit 1 71..72
+              Block 74..76
+            Constructor 79..102
+              private Param 91..100
+                Ident a 99..100
+              None
+            Method 105..121
+              Ident m 105..106
+              readonly Param 107..117
+                Ident a 116..117
+              Block 119..121
+            Constructor 124..157
+              TypeParam T 136..137
+              public Param... 139..150
+                Ident a 149..150
+              Ret 153..154
+                Ref T 153..154
+              Block 155..157
+            Constructor 160..178
+              Block 176..178
+          Let 181..205
+            VarDecl 185..205
+              Ident f 185..186
+              ArrowFn 189..205
+                private Param 190..199
+                  Ident a 198..199
+                Ident a 204..205
+        error 91..100: A parameter property is only allowed in a constructor implementation.
+        error 107..117: A parameter property is only allowed in a constructor implementation.
+        error 136..137: Type parameters cannot appear on a constructor declaration.
+        error 139..150: A parameter property cannot be declared using a rest parameter.
+        error 153..154: Type annotation cannot appear on a constructor declaration.
+        error 190..199: A parameter property is only allowed in a constructor implementation.
+    "#]],
+  );
+}
+
+#[test]
+fn parameter_modifier_errors() {
+  check(
+    "class A {\n  constructor(static a, declare b, async c, abstract d, readonly readonly e) {}\n}",
+    expect![[r#"
+        Module 0..91
+          ClassDecl A 0..91
+            Constructor 12..89
+              Param 24..32
+                Ident a 31..32
+              Param 34..43
+                Ident b 42..43
+              Param 45..52
+                Ident c 51..52
+              Param 54..64
+                Ident d 63..64
+              readonly Param 66..85
+                Ident e 84..85
+              Block 87..89
+        error 24..30: 'static' modifier cannot appear on a parameter.
+        error 34..41: 'declare' modifier cannot appear on a parameter.
+        error 45..50: 'async' modifier cannot appear on a parameter.
+        error 54..62: 'abstract' modifier can only appear on a class, method, or property declaration.
+        error 75..83: 'readonly' modifier already seen.
+    "#]],
+  );
+}
+
+#[test]
+fn decorators() {
+  check(
+    "@dec @a.b(c)\n@d abstract class A {\n  @e [f]: T;\n  @g() @h\n  static i() {}\n  @j\n}\nlet b = @k class {};\n@l let c;",
+    expect![[r#"
+        Module 0..111
+          abstract ClassDecl A 0..80
+            Decorator 0..4
+              Ident dec 1..4
+            Decorator 5..12
+              Call 6..12
+                PropAccess b 6..9
+                  Ident a 6..7
+                Ident c 10..11
+            Decorator 13..15
+              Ident d 14..15
+            Property 37..47
+              Decorator 37..39
+                Ident e 38..39
+              Computed 41..42
+                Ident f 41..42
+              Ref T 45..46
+            static Method 50..73
+              Decorator 50..54
+                Call 51..54
+                  Ident g 51..52
+              Decorator 55..57
+                Ident h 56..57
+              Ident i 67..68
+              Block 71..73
+            Property 76..78
+              Decorator 76..78
+                Ident j 77..78
+              Ident  79..79
+          Let 81..100
+            VarDecl 85..100
+              Ident b 85..86
+              ClassExpr 89..100
+                Decorator 89..91
+                  Ident k 90..91
+          Empty 102..104
+          Let 105..110
+            VarDecl 109..110
+              Ident c 109..110
+        error 79..80: Declaration expected.
+        error 105..108: Declaration expected.
+    "#]],
+  );
+}
+
+#[test]
+fn private_names_and_property_initializers() {
+  check(
+    "function* g() {\n  class A {\n    #a = yield;\n    m() { return this.#a + a?.b.#c; }\n  }\n}",
+    expect![[r#"
+        Module 0..87
+          FnDecl* g 0..87
+            Block 14..87
+              ClassDecl A 18..85
+                Property 32..43
+                  PrivateIdent #a 32..34
+                  Ident yield 37..42
+                Method 48..81
+                  Ident m 48..49
+                  Block 52..81
+                    Return 54..79
+                      Binary + 61..78
+                        PropAccess #a 61..68
+                          This 61..65
+                        PropAccess #c 71..78
+                          PropAccess?. b 71..75
+                            Ident a 71..72
+    "#]],
+  );
+}
+
+#[test]
+fn private_names_in_in_expressions() {
+  check(
+    "class A {\n  #a;\n  static is(o) { return #a in o && !(#a in o); }\n}\n#a;",
+    expect![[r#"
+        Module 0..70
+          ClassDecl A 0..66
+            Property 12..15
+              PrivateIdent #a 12..14
+            static Method 18..64
+              Ident is 25..27
+              Param 28..29
+                Ident o 28..29
+              Block 31..64
+                Return 33..62
+                  Binary && 40..61
+                    Binary in 40..47
+                      PrivateIdent #a 40..42
+                      Ident o 46..47
+                    Unary ! 51..61
+                      Paren 52..61
+                        Binary in 53..60
+                          PrivateIdent #a 53..55
+                          Ident o 59..60
+          Expr 67..67
+            Ident  67..67
+          Empty 69..70
+        error 67..69: Expression expected.
+    "#]],
+  );
+}
+
+#[test]
+fn enums() {
+  check(
+    "enum A { a, 'b' = 1, [c] = a | 2, 3, }\nconst enum B {}\nenum C { a = 'x' in y }",
+    expect![[r#"
+        Module 0..78
+          Enum A 0..38
+            EnumMember 9..10
+              Ident a 9..10
+            EnumMember 12..19
+              Lit "b" 12..15
+              Lit 1 18..19
+            EnumMember 21..32
+              Computed 22..23
+                Ident c 22..23
+              Binary | 27..32
+                Ident a 27..28
+                Lit 2 31..32
+            EnumMember 34..35
+              Lit 3 34..35
+          ConstEnum B 39..54
+          Enum C 55..78
+            EnumMember 64..76
+              Ident a 64..65
+              Binary in 68..76
+                Lit "x" 68..71
+                Ident y 75..76
+    "#]],
+  );
+}
+
+#[test]
+fn enum_errors() {
+  check(
+    "enum { a }\nenum D { a b }\nenum E { a = }",
+    expect![[r#"
+        Module 0..40
+          Enum  0..10
+            EnumMember 7..8
+              Ident a 7..8
+          Enum D 11..25
+            EnumMember 20..21
+              Ident a 20..21
+            EnumMember 22..23
+              Ident b 22..23
+          Enum E 26..40
+            EnumMember 35..38
+              Ident a 35..36
+              Ident  39..39
+        error 5..6: Identifier expected.
+        error 22..23: An enum member name must be followed by a ',', '=', or '}'.
+        error 39..40: Expression expected.
+    "#]],
+  );
+}
+
+#[test]
+fn namespaces_and_modules() {
+  check(
+    "namespace A.B.C { let a; }\nmodule D { function f() {} }\nnamespace\nE {}\nmodule.exports = 1;",
+    expect![[r#"
+        Module 0..90
+          Namespace A 0..26
+            Namespace B 12..26
+              Namespace C 14..26
+                Block 16..26
+                  Let 18..23
+                    VarDecl 22..23
+                      Ident a 22..23
+          Module D 27..55
+            Block 36..55
+              FnDecl f 38..53
+                Block 51..53
+          Expr 56..65
+            Ident namespace 56..65
+          Expr 66..67
+            Ident E 66..67
+          Block 68..70
+          Expr 71..90
+            Assign Assign 71..89
+              PropAccess exports 71..85
+                Ident module 71..77
+              Lit 1 88..89
+        error 68..69: ';' expected.
+    "#]],
+  );
+}
+
+#[test]
+fn ambient_declarations() {
+  check(
+    "declare module 'a' { const a: number; }\ndeclare module 'b';\ndeclare global { var a: A; }\ndeclare namespace N { class C {} }\ndeclare const enum E { a }\ndeclare function f(): void;\ndeclare abstract class D {}\ndeclare\nlet x;",
+    expect![[r#"
+        Module 0..221
+          Ambient 0..39
+            Module "a" 8..39
+              Block 19..39
+                Ambient 21..37
+                  Const 21..36
+                    VarDecl 27..36
+                      Ident a 27..28
+                      Keyword number 30..36
+          Ambient 40..59
+            Module "b" 48..59
+          Ambient 60..88
+            Global 68..88
+              Block 75..88
+                Ambient 77..86
+                  Var 77..85
+                    VarDecl 81..85
+                      Ident a 81..82
+                      Ref A 84..85
+          Ambient 89..123
+            Namespace N 97..123
+              Block 109..123
+                Ambient 111..121
+                  ClassDecl C 111..121
+          Ambient 124..150
+            ConstEnum E 132..150
+              EnumMember 147..148
+                Ident a 147..148
+          Ambient 151..178
+            FnDecl f 159..178
+              Ret 173..177
+                Keyword void 173..177
+              None
+          Ambient 179..206
+            abstract ClassDecl D 187..206
+          Expr 207..214
+            Ident declare 207..214
+          Let 215..220
+            VarDecl 219..220
+              Ident x 219..220
+    "#]],
+  );
+}
+
+#[test]
+fn interfaces_and_type_aliases() {
+  check(
+    "interface A<T> extends B<T>, C { a: T; b(): void }\ntype D<T = string> = A<T> | null;\ndeclare interface E {}\ndeclare type F = E;\ndeclare module 'x' { interface G {} }\ninterface\nH {}\ntype = 1;",
+    expect![[r#"
+        Module 0..190
+          Interface A 0..50
+            TypeParam T 12..13
+            Extends 23..27
+              Ident B 23..24
+              Ref T 25..26
+            Extends 29..30
+              Ident C 29..30
+            PropSig 33..38
+              Ident a 33..34
+              Ref T 36..37
+            MethodSig 39..48
+              Ident b 39..40
+              Ret 44..48
+                Keyword void 44..48
+          TypeAlias D 51..84
+            TypeParam T 58..68
+              Default 62..68
+                Keyword string 62..68
+            Union 72..83
+              Ref A 72..76
+                Ref T 74..75
+              Keyword null 79..83
+          Ambient 85..107
+            Interface E 93..107
+          Ambient 108..127
+            TypeAlias F 116..127
+              Ref E 125..126
+          Ambient 128..165
+            Module "x" 136..165
+              Block 147..165
+                Ambient 149..163
+                  Interface G 149..163
+          Expr 166..175
+            Ident interface 166..175
+          Expr 176..177
+            Ident H 176..177
+          Block 178..180
+          Expr 181..190
+            Assign Assign 181..189
+              Ident type 181..185
+              Lit 1 188..189
+        error 178..179: ';' expected.
+    "#]],
+  );
+}
+
+#[test]
+fn declaration_files_are_ambient() {
+  let src = "declare let a: number;\nfunction f(): void;\nnamespace N { enum E { a } }";
+  expect![[r#"
+      Module 0..71
+        Ambient 0..22
+          Let 8..21
+            VarDecl 12..21
+              Ident a 12..13
+              Keyword number 15..21
+        Ambient 23..42
+          FnDecl f 23..42
+            Ret 37..41
+              Keyword void 37..41
+            None
+        Ambient 43..71
+          Namespace N 43..71
+            Block 55..71
+              Ambient 57..69
+                Enum E 57..69
+                  EnumMember 66..67
+                    Ident a 66..67
+  "#]]
+  .assert_eq(&parse(src, "test.d.ts"));
+}
+
+#[test]
+fn imports() {
+  check(
+    "import a from 'a';\nimport b, { c, d as e } from 'b';\nimport f, * as g from 'c';\nimport 'd';\nimport {} from 'e';\nimport(h);\nimport.meta;",
+    expect![[r#"
+        Module 0..135
+          Import 0..18
+            Default a 7..8
+            From "a" 14..17
+          Import 19..52
+            Default b 26..27
+            Name c 31..32
+            Name d as e 34..40
+            From "b" 48..51
+          Import 53..79
+            Default f 60..61
+            Namespace g 68..69
+            From "c" 75..78
+          Import 80..91
+            From "d" 87..90
+          Import 92..111
+            From "e" 107..110
+          Expr 112..122
+            Call 112..121
+              Import 112..118
+              Ident h 119..120
+          Expr 123..135
+            Meta meta 123..134
+    "#]],
+  );
+}
+
+#[test]
+fn type_only_imports_and_exports() {
+  check(
+    "import type A from 'a';\nimport type { B } from 'b';\nimport type * as C from 'c';\nimport type from 'd';\nimport { type D, type as E, type as, type as as F, type } from 'e';\nexport type { G };\nexport type * from 'f';\nexport { type H };",
+    expect![[r#"
+        Module 0..232
+          Import type 0..23
+            Default A 12..13
+            From "a" 19..22
+          Import type 24..51
+            Name B 38..39
+            From "b" 47..50
+          Import type 52..80
+            Namespace C 69..70
+            From "c" 76..79
+          Import 81..102
+            Default type 88..92
+            From "d" 98..101
+          Import 103..170
+            Name type D 112..118
+            Name type as E 120..129
+            Name type as 131..138
+            Name type as as F 140..152
+            Name type 154..158
+            From "e" 166..169
+          ExportNamed type 171..189
+            Name G 185..186
+          ExportAll type 190..213
+            From "f" 209..212
+          ExportNamed 214..232
+            Name type H 223..229
+    "#]],
+  );
+}
+
+#[test]
+fn import_equals() {
+  check(
+    "import a = require('a');\nimport b = A.B.C;\nimport type c = require('c');\nexport import d = D;\nimport e = require(f);",
+    expect![[r#"
+        Module 0..116
+          ImportEquals a 0..24
+            Require "a" 19..22
+          ImportEquals b 25..42
+            Entity A.B.C 25..42
+          ImportEquals type c 43..72
+            Require "c" 67..70
+          Export 73..93
+            ImportEquals d 73..93
+              Entity D 73..93
+          ImportEquals e 94..113
+            Require "" 113..113
+          Expr 113..114
+            Ident f 113..114
+          Empty 115..116
+        error 113..114: String literal expected.
+        error 114..115: ';' expected.
+    "#]],
+  );
+}
+
+#[test]
+fn import_attributes() {
+  check(
+    "import a from 'a' with { type: 'json' };\nimport 'b' with { 'c': d, };\nexport * from 'e' with { type: 'json' };\nimport f from 'f' assert { type: 'json' };\nimport g from 'g'\nassert(h);",
+    expect![[r#"
+        Module 0..182
+          Import 0..40
+            Default a 7..8
+            From "a" 14..17
+            Attribute 25..37
+              Ident type 25..29
+              Lit "json" 31..37
+          Import 41..69
+            From "b" 48..51
+            Attribute 59..65
+              Lit "c" 59..62
+              Ident d 64..65
+          ExportAll 70..110
+            From "e" 84..87
+            Attribute 95..107
+              Ident type 95..99
+              Lit "json" 101..107
+          Import 111..153
+            Default f 118..119
+            From "f" 125..128
+            Attribute 138..150
+              Ident type 138..142
+              Lit "json" 144..150
+          Import 154..171
+            Default g 161..162
+            From "g" 168..171
+          Expr 172..182
+            Call 172..181
+              Ident assert 172..178
+              Ident h 179..180
+    "#]],
+  );
+}
+
+#[test]
+fn import_errors() {
+  check(
+    "import { default } from 'a';\nimport { default as a, b c } from 'b';\nimport * from 'c';\nimport d from e;",
+    expect![[r#"
+        Module 0..103
+          Import 0..28
+            Name default 9..16
+            From "a" 24..27
+          Import 29..67
+            Name default as a 38..50
+            Name b 52..53
+            Name c 54..55
+            From "b" 63..66
+          Import 68..86
+            Namespace from 77..81
+            From "c" 82..85
+          Import 87..100
+            Default d 94..95
+            From "" 101..101
+          Expr 101..103
+            Ident e 101..102
+        error 9..16: Identifier expected.
+        error 54..55: ',' expected.
+        error 77..81: 'as' expected.
+        error 82..85: 'from' expected.
+        error 101..102: String literal expected.
+    "#]],
+  );
+}
+
+#[test]
+fn exports() {
+  check(
+    "export { a, b as c };\nexport { d } from 'd';\nexport * from 'e';\nexport * as f from 'f';\nexport = g;\nexport as namespace H;",
+    expect![[r#"
+        Module 0..122
+          ExportNamed 0..21
+            Name a 9..10
+            Name b as c 12..18
+          ExportNamed 22..44
+            Name d 31..32
+            From "d" 40..43
+          ExportAll 45..63
+            From "e" 59..62
+          ExportAll 64..87
+            Namespace f 76..77
+            From "f" 83..86
+          ExportAssign = 88..99
+            Ident g 97..98
+          ExportAsNamespace H 100..122
+    "#]],
+  );
+}
+
+#[test]
+fn exported_declarations() {
+  check(
+    "export const a = 1;\nexport function b() {}\nexport class C {}\nexport abstract class D {}\nexport enum E {}\nexport namespace F {}\nexport declare function g(): void;\nexport async function h() {}\nexport interface I {}\nexport type J = I;",
+    expect![[r#"
+        Module 0..231
+          Export 0..19
+            Const 7..18
+              VarDecl 13..18
+                Ident a 13..14
+                Lit 1 17..18
+          Export 20..42
+            FnDecl b 27..42
+              Block 40..42
+          Export 43..60
+            ClassDecl C 50..60
+          Export 61..87
+            abstract ClassDecl D 68..87
+          Export 88..104
+            Enum E 95..104
+          Export 105..126
+            Namespace F 112..126
+              Block 124..126
+          Export 127..161
+            Ambient 127..161
+              FnDecl g 142..161
+                Ret 156..160
+                  Keyword void 156..160
+                None
+          Export 162..190
+            async FnDecl h 169..190
+              Block 188..190
+          Export 191..212
+            Interface I 198..212
+          Export 213..231
+            TypeAlias J 220..231
+              Ref I 229..230
+    "#]],
+  );
+}
+
+#[test]
+fn export_default() {
+  check(
+    "export default function () {}\nexport default class {}\nexport default abstract class A {}\nexport default async function b() {}\nexport default c + 1;\nexport default (function () {});\nexport default await d;\nexport default interface E {}",
+    expect![[r#"
+        Module 0..234
+          ExportDefault 0..29
+            FnDecl 15..29
+              Block 27..29
+          ExportDefault 30..53
+            ClassDecl 45..53
+          ExportDefault 54..88
+            abstract ClassDecl A 69..88
+          ExportDefault 89..125
+            async FnDecl b 104..125
+              Block 123..125
+          ExportAssign default 126..147
+            Binary + 141..146
+              Ident c 141..142
+              Lit 1 145..146
+          ExportAssign default 148..180
+            Paren 163..179
+              Fn 164..178
+                Block 176..178
+          ExportAssign default 181..204
+            Unary await 196..203
+              Ident d 202..203
+          ExportDefault 205..234
+            Interface E 220..234
+    "#]],
+  );
+}
+
+#[test]
+fn export_errors() {
+  check(
+    "export 1;\nexport default;\n@a export @b class A {}\n@c export { d };\nexport { e } 'f';",
+    expect![[r#"
+        Module 0..84
+          Empty 0..6
+          Expr 7..9
+            Lit 1 7..8
+          ExportAssign default 10..25
+            Ident  24..24
+          Export 26..49
+            ClassDecl A 26..49
+              Decorator 26..28
+                Ident a 27..28
+              Decorator 36..38
+                Ident b 37..38
+          ExportNamed 50..66
+            Name d 62..63
+          ExportNamed 67..84
+            Name e 76..77
+            From "f" 80..83
+        error 7..8: Declaration expected.
+        error 24..25: Expression expected.
+        error 36..37: Decorators may not appear after 'export' or 'export default' if they also appear before 'export'.
+        error 50..52: Decorators are not valid here.
+        error 80..83: 'from' expected.
+    "#]],
+  );
+}
+
+#[test]
+fn jsx_elements() {
+  let src = "<a>text {b} <c-d e=\"f\" g={h} i /></a>;";
+  expect![[r#"
+      Module 0..38
+        Expr 0..38
+          JsxEl 0..37
+            Opening a 0..3
+            Text "text " 3..8
+            JsxExpr 8..11
+              Ident b 9..10
+            Text " " 11..12
+            JsxSelfClosingEl 12..33
+              Opening c-d 12..33
+                Attr e 17..22
+                  Lit "f" 19..22
+                Attr g 23..28
+                  Ident h 26..27
+                Attr i 29..30
+            Closing a 33..37
+  "#]]
+  .assert_eq(&parse(src, "test.tsx"));
+}
+
+#[test]
+fn jsx_children() {
+  let src = "<a>\n  x &amp; y\n  {...b}{}\n  <b>{c}</b>\n</a>";
+  expect![[r#"
+      Module 0..44
+        Expr 0..44
+          JsxEl 0..44
+            Opening a 0..3
+            Text "\n  x & y\n  " 3..18
+            JsxSpreadChild 18..24
+              Ident b 22..23
+            JsxExpr 24..26
+            Text(whitespace) "\n  " 26..29
+            JsxEl 29..39
+              Opening b 29..32
+              JsxExpr 32..35
+                Ident c 33..34
+              Closing b 35..39
+            Text(whitespace) "\n" 39..40
+            Closing a 40..44
+  "#]]
+  .assert_eq(&parse(src, "test.tsx"));
+}
+
+#[test]
+fn jsx_fragments_and_self_closing_elements() {
+  let src = "<><a /><b.c.d></b.c.d></>; <this />; <svg:rect {...p} />";
+  expect![[r#"
+      Module 0..56
+        Expr 0..26
+          JsxFragment 0..25
+            JsxSelfClosingEl 2..7
+              Opening a 2..7
+            JsxEl 7..22
+              Opening b.c.d 7..14
+              Closing b.c.d 14..22
+        Expr 27..36
+          JsxSelfClosingEl 27..35
+            Opening this 27..35
+        Expr 37..56
+          JsxSelfClosingEl 37..56
+            Opening svg:rect 37..56
+              SpreadAttr 47..53
+                Ident p 51..52
+  "#]]
+  .assert_eq(&parse(src, "test.tsx"));
+}
+
+#[test]
+fn jsx_attribute_values_and_type_arguments() {
+  let src = "<A<string> a=<b /> c='d&amp;' e={f, g} />";
+  expect![[r#"
+      Module 0..41
+        Expr 0..41
+          JsxSelfClosingEl 0..41
+            Opening A 0..41
+              Keyword string 3..9
+              Attr a 11..18
+                JsxSelfClosingEl 13..18
+                  Opening b 13..18
+              Attr c 19..29
+                Lit "d&" 21..29
+              Attr e 30..38
+                Binary , 33..37
+                  Ident f 33..34
+                  Ident g 36..37
+  "#]]
+  .assert_eq(&parse(src, "test.tsx"));
+}
+
+#[test]
+fn jsx_mismatched_closing_tags() {
+  let src = "<a><b></a>; <c></d>; <e.f></e.g>; <>x</h>;";
+  expect![[r#"
+      Module 0..42
+        Expr 0..11
+          JsxEl 0..10
+            Opening a 0..3
+            JsxEl 3..6
+              Opening b 3..6
+              Closing  6..6
+            Closing a 6..10
+        Expr 12..20
+          JsxEl 12..19
+            Opening c 12..15
+            Closing d 15..19
+        Expr 21..33
+          JsxEl 21..32
+            Opening e.f 21..26
+            Closing e.g 26..32
+        Expr 34..42
+          JsxFragment 34..41
+            Text "x" 36..37
+      error 4..5: JSX element 'b' has no corresponding closing tag.
+      error 17..18: Expected corresponding JSX closing tag for 'c'.
+      error 28..31: Expected corresponding JSX closing tag for 'e.f'.
+      error 39..40: Expected corresponding closing tag for JSX fragment.
+  "#]]
+  .assert_eq(&parse(src, "test.tsx"));
+}
+
+#[test]
+fn jsx_unclosed_elements() {
+  let src = "<a><b>text";
+  expect![[r#"
+      Module 0..10
+        Expr 0..10
+          JsxEl 0..10
+            Opening a 0..3
+            JsxEl 3..10
+              Opening b 3..6
+              Text "text" 6..10
+              Closing  10..10
+            Closing  10..10
+      error 1..2: JSX element 'a' has no corresponding closing tag.
+      error 4..5: JSX element 'b' has no corresponding closing tag.
+      error 10..10: '</' expected.
+  "#]]
+  .assert_eq(&parse(src, "test.tsx"));
+  expect![[r#"
+      Module 0..6
+        Expr 0..6
+          JsxFragment 0..6
+            Text "text" 2..6
+      error 0..2: JSX fragment has no corresponding closing tag.
+      error 6..6: '</' expected.
+  "#]]
+  .assert_eq(&parse("<>text", "test.tsx"));
+}
+
+#[test]
+fn jsx_errors() {
+  let src = "<a /><b />; <c d={} e=1 />; <f 1 />;";
+  expect![[r#"
+      Module 0..36
+        Expr 0..11
+          Binary , 0..10
+            JsxSelfClosingEl 0..5
+              Opening a 0..5
+            JsxSelfClosingEl 5..10
+              Opening b 5..10
+        Expr 12..22
+          JsxSelfClosingEl 12..22
+            Opening c 12..22
+              Attr d 15..19
+              Attr e 20..22
+        Expr 22..27
+          Binary > 22..26
+            Binary / 22..25
+              Lit 1 22..23
+              Ident  25..25
+            Ident  26..26
+        Expr 28..30
+          JsxSelfClosingEl 28..30
+            Opening f 28..30
+        Expr 31..36
+          Binary > 31..35
+            Binary / 31..34
+              Lit 1 31..32
+              Ident  34..34
+            Ident  35..35
+      error 0..10: JSX expressions must have one parent element.
+      error 17..19: JSX attributes must only be assigned a non-empty 'expression'.
+      error 22..23: '{' or JSX element expected.
+      error 25..26: Expression expected.
+      error 26..27: Expression expected.
+      error 31..32: Identifier expected.
+      error 34..35: Expression expected.
+      error 35..36: Expression expected.
+  "#]]
+  .assert_eq(&parse(src, "test.tsx"));
+}
+
+#[test]
+fn binding_patterns() {
+  check(
+    "let [a, , b = 1, ...c] = d;\nconst { e, f: [g], 'h': i = 2, [j]: { k }, ...l } = m;",
+    expect![[r#"
+        Module 0..82
+          Let 0..26
+            VarDecl 4..26
+              ArrayPat 4..22
+                BindingEl 5..6
+                  Ident a 5..6
+                Omitted 8..8
+                BindingEl 10..15
+                  Ident b 10..11
+                  Lit 1 14..15
+                RestEl 17..21
+                  Ident c 20..21
+              Ident d 25..26
+          Const 28..81
+            VarDecl 34..81
+              ObjectPat 34..77
+                BindingEl 36..37
+                  Ident e 36..37
+                BindingEl 39..45
+                  Ident f 39..40
+                  ArrayPat 42..45
+                    BindingEl 43..44
+                      Ident g 43..44
+                BindingEl 47..57
+                  Lit "h" 47..50
+                  Ident i 52..53
+                  Lit 2 56..57
+                BindingEl 59..69
+                  Computed 60..61
+                    Ident j 60..61
+                  ObjectPat 64..69
+                    BindingEl 66..67
+                      Ident k 66..67
+                RestEl 71..75
+                  Ident l 74..75
+              Ident m 80..81
+    "#]],
+  );
+}
+
+#[test]
+fn binding_patterns_in_params_and_catch() {
+  check(
+    "function f([a], { b } = {}, ...[c]) {}\n(({ d: e }) => e);\ntry {} catch ({ message }) {}",
+    expect![[r#"
+        Module 0..87
+          FnDecl f 0..38
+            Param 11..14
+              ArrayPat 11..14
+                BindingEl 12..13
+                  Ident a 12..13
+            Param 16..26
+              ObjectPat 16..21
+                BindingEl 18..19
+                  Ident b 18..19
+              Object 24..26
+            Param... 28..34
+              ArrayPat 31..34
+                BindingEl 32..33
+                  Ident c 32..33
+            Block 36..38
+          Expr 39..57
+            Paren 39..56
+              ArrowFn 40..55
+                Param 41..49
+                  ObjectPat 41..49
+                    BindingEl 43..47
+                      Ident d 43..44
+                      Ident e 46..47
+                Ident e 54..55
+          Try 58..87
+            Block 62..64
+            Catch 65..87
+              VarDecl 72..83
+                ObjectPat 72..83
+                  BindingEl 74..81
+                    Ident message 74..81
+              Block 85..87
+    "#]],
+  );
+}
+
+#[test]
+fn binding_pattern_errors() {
+  check(
+    "let [...a, b] = c;\nlet { ...d = 1 } = e;\nlet { if } = f;\nlet [g.h] = i;",
+    expect![[r#"
+        Module 0..71
+          Let 0..17
+            VarDecl 4..17
+              ArrayPat 4..13
+                RestEl 5..9
+                  Ident a 8..9
+                BindingEl 11..12
+                  Ident b 11..12
+              Ident c 16..17
+          Let 19..39
+            VarDecl 23..39
+              ObjectPat 23..35
+                RestEl 25..33
+                  Ident d 28..29
+                  Lit 1 32..33
+              Ident e 38..39
+          Let 41..55
+            VarDecl 45..55
+              ObjectPat 45..51
+                BindingEl 47..49
+                  Ident if 47..49
+                  Ident  50..50
+              Ident f 54..55
+          Let 57..70
+            VarDecl 61..70
+              ArrayPat 61..66
+                BindingEl 62..63
+                  Ident g 62..63
+                BindingEl 64..65
+                  Ident h 64..65
+              Ident i 69..70
+        error 5..9: A rest element must be last in a destructuring pattern.
+        error 32..33: A rest element cannot have an initializer.
+        error 50..51: ':' expected.
+        error 63..64: ',' expected.
+    "#]],
+  );
+}
+
+#[test]
+fn assignment_patterns() {
+  check(
+    "[a, b] = [b, a];\n({ c, d: e.f, g = 1, h: [i] = [], ...j } = k);\nfor ([l, m] of n);",
+    expect![[r#"
+        Module 0..82
+          Expr 0..16
+            Assign Assign 0..15
+              ArrayPat 0..6
+                BindingEl 1..2
+                  Ident a 1..2
+                BindingEl 4..5
+                  Ident b 4..5
+              Array 9..15
+                Ident b 10..11
+                Ident a 13..14
+          Expr 17..63
+            Paren 17..62
+              Assign Assign 18..61
+                ObjectPat 18..57
+                  BindingEl 20..21
+                    Ident c 20..21
+                  BindingEl 23..29
+                    Ident d 23..24
+                    PropAccess f 26..29
+                      Ident e 26..27
+                  BindingEl 31..36
+                    Ident g 31..32
+                    Lit 1 35..36
+                  BindingEl 38..49
+                    Ident h 38..39
+                    ArrayPat 41..44
+                      BindingEl 42..43
+                        Ident i 42..43
+                    Array 47..49
+                  RestEl 51..55
+                    Ident j 54..55
+                Ident k 60..61
+          ForOf 64..82
+            ArrayPat 69..75
+              BindingEl 70..71
+                Ident l 70..71
+              BindingEl 73..74
+                Ident m 73..74
+            Ident n 79..80
+            Empty 81..82
+    "#]],
+  );
+}
+
+#[test]
+fn assignment_pattern_errors() {
+  check(
+    "[a, 1] = b;\n({ c: d() } = e);\n({ ...[f] } = g);\n[...h, i] = j;\n[m] += n;\nk = { o = 1 };\n({ p = 1 } = q);\nfor ({ r = 1 } of s);\n(({ t = 1 }) => t);",
+    expect![[r#"
+        Module 0..146
+          Expr 0..11
+            Assign Assign 0..10
+              ArrayPat 0..6
+                BindingEl 1..2
+                  Ident a 1..2
+                BindingEl 4..5
+                  Lit 1 4..5
+              Ident b 9..10
+          Expr 12..29
+            Paren 12..28
+              Assign Assign 13..27
+                ObjectPat 13..23
+                  BindingEl 15..21
+                    Ident c 15..16
+                    Call 18..21
+                      Ident d 18..19
+                Ident e 26..27
+          Expr 30..47
+            Paren 30..46
+              Assign Assign 31..45
+                ObjectPat 31..41
+                  RestEl 33..39
+                    Array 36..39
+                      Ident f 37..38
+                Ident g 44..45
+          Expr 48..62
+            Assign Assign 48..61
+              ArrayPat 48..57
+                RestEl 49..53
+                  Ident h 52..53
+                BindingEl 55..56
+                  Ident i 55..56
+              Ident j 60..61
+          Expr 63..72
+            Assign AddAssign 63..71
+              Array 63..66
+                Ident m 64..65
+              Ident n 70..71
+          Expr 73..87
+            Assign Assign 73..86
+              Ident k 73..74
+              Object 77..86
+                ShortProp 79..84
+                  Ident o 79..80
+                  Assign Assign 79..84
+                    Ident o 79..80
+                    Lit 1 83..84
+          Expr 88..104
+            Paren 88..103
+              Assign Assign 89..102
+                ObjectPat 89..98
+                  BindingEl 91..96
+                    Ident p 91..92
+                    Lit 1 95..96
+                Ident q 101..102
+          ForOf 105..126
+            ObjectPat 110..119
+              BindingEl 112..117
+                Ident r 112..113
+                Lit 1 116..117
+            Ident s 123..124
+            Empty 125..126
+          Expr 127..146
+            Paren 127..145
+              ArrowFn 128..144
+                Param 129..138
+                  ObjectPat 129..138
+                    BindingEl 131..136
+                      Ident t 131..132
+                      Lit 1 135..136
+                Ident t 143..144
+        error 4..5: The left-hand side of an assignment expression must be a variable or a property access.
+        error 18..21: The left-hand side of an assignment expression must be a variable or a property access.
+        error 36..39: The target of an object rest assignment must be a variable or a property access.
+        error 49..53: A rest element must be last in a destructuring pattern.
+        error 63..66: The left-hand side of an assignment expression must be a variable or a property access.
+        error 81..82: An '=' can only follow a property name when the containing object literal is part of a destructuring pattern.
+    "#]],
+  );
+}
+
+#[test]
+fn list_recovery_skips_tokens() {
+  check(
+    "class A {\n  a: number;\n  )\n  b() {}\n}\nf(a, ?, b);\nlet o = { a: 1; b: 2 };\nfunction g(a b, c) {}",
+    expect![[r#"
+        Module 0..95
+          ClassDecl A 0..37
+            Property 12..22
+              Ident a 12..13
+              Keyword number 15..21
+            Method 29..35
+              Ident b 29..30
+              Block 33..35
+          Expr 38..49
+            Call 38..48
+              Ident f 38..39
+              Ident a 40..41
+              Ident b 46..47
+          Let 50..72
+            VarDecl 54..72
+              Ident o 54..55
+              Object 58..72
+                Prop 60..64
+                  Ident a 60..61
+                  Lit 1 63..64
+                Prop 66..70
+                  Ident b 66..67
+                  Lit 2 69..70
+          FnDecl g 74..95
+            Param 85..86
+              Ident a 85..86
+            Param 87..88
+              Ident b 87..88
+            Param 90..91
+              Ident c 90..91
+            Block 93..95
+        error 25..26: Unexpected token. A constructor, method, accessor, or property was expected.
+        error 43..44: Argument expression expected.
+        error 44..45: Argument expression expected.
+        error 64..65: ',' expected.
+        error 87..88: ',' expected.
+    "#]],
+  );
+}
+
+#[test]
+fn list_recovery_resumes_enclosing_list() {
+  check(
+    "function f() {\n  g(a, b\n  if (c) {}\n}\nclass A {\n  a = [1, 2\n  while(b) {}\n}",
+    expect![[r#"
+        Module 0..75
+          FnDecl f 0..37
+            Block 13..37
+              Expr 17..23
+                Call 17..23
+                  Ident g 17..18
+                  Ident a 19..20
+                  Ident b 22..23
+              If 26..35
+                Ident c 30..31
+                Block 33..35
+          ClassDecl A 38..75
+            Property 50..59
+              Ident a 50..51
+              Array 54..59
+                Lit 1 55..56
+                Lit 2 58..59
+            Method 62..73
+              Ident while 62..67
+              Param 68..69
+                Ident b 68..69
+              Block 71..73
+        error 26..28: ',' expected.
+        error 62..67: ',' expected.
+    "#]],
+  );
+}
+
+#[test]
+fn list_recovery_missing_commas() {
+  check(
+    "let [a b] = c, { d e } = f;\nlet g: [A B];\nclass C<T U> {}",
+    expect![[r#"
+      Module 0..57
+        Let 0..26
+          VarDecl 4..13
+            ArrayPat 4..9
+              BindingEl 5..6
+                Ident a 5..6
+              BindingEl 7..8
+                Ident b 7..8
+            Ident c 12..13
+          VarDecl 15..26
+            ObjectPat 15..22
+              BindingEl 17..18
+                Ident d 17..18
+              BindingEl 19..20
+                Ident e 19..20
+            Ident f 25..26
+        Let 28..40
+          VarDecl 32..40
+            Ident g 32..33
+            Tuple 35..40
+              Ref A 36..37
+              Ref B 38..39
+        ClassDecl C 42..57
+          TypeParam T 50..51
+          TypeParam U 52..53
+      error 7..8: ',' expected.
+      error 19..20: ',' expected.
+      error 38..39: ',' expected.
+      error 52..53: ',' expected.
+  "#]],
+  );
+}
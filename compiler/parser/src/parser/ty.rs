@@ -0,0 +1,638 @@
+use super::Parser;
+use crate::context::{ContextFlags, ParsingContext, TokenFlags};
+
+use tscore_ast::ptr::P;
+use tscore_ast::token::{self, BinOpToken};
+use tscore_ast::{
+  CondTy, EntityName, FnParam, FnRet, FnSig, GenericParam, Generics, IndexSig, IndexTy,
+  InterfaceElement, Lit, LitKind, MapTy, MethodSig, ModFlag, OpTy, OpTyKind, PredicateTy, PropName,
+  PropSig, RefTy, RefTyKind, TemplateTy, TemplateTySpan, Ty, TyKind,
+};
+use tscore_span::symbol::{kw, Ident, Symbol};
+use tscore_span::BytePos;
+
+impl<'a> Parser<'a> {
+  /// Parses a type. A conditional type, `A extends B ? C : D`, binds loosest, then
+  /// function types, unions, intersections, type operators and `[]`.
+  pub fn parse_ty(&mut self) -> P<Ty> {
+    if self.is_start_of_fn_ty() {
+      return self.parse_fn_ty();
+    }
+    let lo = self.token.span.lo();
+    let ty = self.parse_union_ty();
+    if self.in_context(ContextFlags::DisallowConditionalTypes)
+      || self.has_preceding_line_break()
+      || !self.eat_keyword(kw::Extends)
+    {
+      return ty;
+    }
+    // `A extends B extends C ? D : E ? F : G` is an error, the extends type may not be
+    // a conditional type itself unless parenthesized.
+    let extends = self.with_context(ContextFlags::DisallowConditionalTypes, 0, |p| p.parse_ty());
+    self.expect(&token::Question);
+    let unset = ContextFlags::DisallowConditionalTypes;
+    let true_ty = self.with_context(0, unset, |p| p.parse_ty());
+    self.expect(&token::Colon);
+    let false_ty = self.with_context(0, unset, |p| p.parse_ty());
+    self.mk_ty(lo, TyKind::Cond(P(CondTy(ty, extends, true_ty, false_ty))))
+  }
+
+  /// Parses the return type of a function, which may also be a type predicate.
+  pub(super) fn parse_ret_ty(&mut self) -> P<Ty> {
+    let is_predicate = self.is_binding_ident()
+      && self.look_ahead_next(|p| p.check_keyword(kw::Is) && !p.has_preceding_line_break());
+    if !is_predicate {
+      return self.parse_ty();
+    }
+    let lo = self.token.span.lo();
+    let param = self.parse_ident();
+    self.parse_predicate_ty(lo, false, param)
+  }
+
+  /// Parses the `is T` of a type predicate, the parameter already being consumed.
+  fn parse_predicate_ty(&mut self, lo: BytePos, asserts: bool, param: Ident) -> P<Ty> {
+    let ty = if self.check_keyword(kw::Is) && !self.has_preceding_line_break() {
+      self.bump();
+      Some(self.parse_ty())
+    } else {
+      None
+    };
+    self.mk_ty(lo, TyKind::Predicate(P(PredicateTy { asserts, param, ty })))
+  }
+
+  /// Whether `new`, `<` or a `(` unambiguously starting a parameter list is next.
+  fn is_start_of_fn_ty(&mut self) -> bool {
+    if self.check(&token::Lt) || self.check_keyword(kw::New) {
+      return true;
+    }
+    self.check(&token::OpenDelim(token::Paren))
+      && self.look_ahead(|p| p.is_unambiguously_start_of_fn_ty())
+  }
+
+  fn is_unambiguously_start_of_fn_ty(&mut self) -> bool {
+    self.bump();
+    if self.check(&token::CloseDelim(token::Paren)) || self.check(&token::DotDotDot) {
+      // `()` or `(...`
+      return true;
+    }
+    if !self.skip_param_start() {
+      return false;
+    }
+    // `(a:`, `(a,`, `(a?` or `(a=`, and `(a) =>` but not `(a)`.
+    match self.token.kind {
+      token::Colon | token::Comma | token::Question | token::Eq => true,
+      token::CloseDelim(token::Paren) => {
+        self.bump();
+        self.check(&token::Arrow)
+      }
+      _ => false,
+    }
+  }
+
+  fn skip_param_start(&mut self) -> bool {
+    if self.is_binding_ident() || self.check_keyword(kw::This) {
+      self.bump();
+      return true;
+    }
+    if self.check(&token::OpenDelim(token::Bracket)) || self.check(&token::OpenDelim(token::Brace))
+    {
+      // A destructuring parameter, if it parses without errors.
+      let errors = self.errors.len();
+      self.parse_binding_pat();
+      return self.errors.len() == errors;
+    }
+    false
+  }
+
+  /// Parses `<T>(a: A) => R` or `new (a: A) => R`.
+  fn parse_fn_ty(&mut self) -> P<Ty> {
+    let lo = self.token.span.lo();
+    let is_constructor = self.eat_keyword(kw::New);
+    let generics = self.parse_generics();
+    let params = self.parse_fn_param_list(ContextFlags::None);
+    self.check_param_props(&params);
+    self.expect(&token::Arrow);
+    let ret = FnRet::Ty(self.parse_ret_ty());
+    let sig = P(FnSig { generics, params, ret, span: self.span_from(lo) });
+    let kind = if is_constructor { TyKind::Constructor(sig) } else { TyKind::Fn(sig) };
+    self.mk_ty(lo, kind)
+  }
+
+  fn parse_union_ty(&mut self) -> P<Ty> {
+    self.parse_union_or_intersection_ty(token::Or, Self::parse_intersection_ty)
+  }
+
+  fn parse_intersection_ty(&mut self) -> P<Ty> {
+    self.parse_union_or_intersection_ty(token::And, Self::parse_ty_operator)
+  }
+
+  /// Parses `A | B | C` or `A & B & C`, with an optional leading operator.
+  fn parse_union_or_intersection_ty(
+    &mut self,
+    op: BinOpToken,
+    parse_constituent: fn(&mut Self) -> P<Ty>,
+  ) -> P<Ty> {
+    let lo = self.token.span.lo();
+    let is_union = op == token::Or;
+    let op = token::BinOp(op);
+    let has_leading_op = self.eat(&op);
+    let ty = match self.parse_fn_ty_to_error(is_union, has_leading_op) {
+      Some(ty) => ty,
+      None => parse_constituent(self),
+    };
+    if !has_leading_op && !self.check(&op) {
+      return ty;
+    }
+    let mut tys = vec![ty];
+    while self.eat(&op) {
+      let ty = match self.parse_fn_ty_to_error(is_union, true) {
+        Some(ty) => ty,
+        None => parse_constituent(self),
+      };
+      tys.push(ty);
+    }
+    self.mk_ty(lo, if is_union { TyKind::Union(tys) } else { TyKind::Intersection(tys) })
+  }
+
+  /// A function type after `|` or `&` would swallow the rest of the union or
+  /// intersection, so it must be parenthesized. It is parsed anyway to recover.
+  fn parse_fn_ty_to_error(&mut self, is_union: bool, after_op: bool) -> Option<P<Ty>> {
+    if !after_op || !self.is_start_of_fn_ty() {
+      return None;
+    }
+    let ty = self.parse_fn_ty();
+    let what = if matches!(ty.kind, TyKind::Constructor(_)) { "Constructor" } else { "Function" };
+    let within = if is_union { "a union" } else { "an intersection" };
+    let msg = format!("{} type notation must be parenthesized when used in {} type.", what, within);
+    self.error(ty.span, &msg);
+    Some(ty)
+  }
+
+  /// Parses `keyof T`, `unique T`, `readonly T` or `infer T`.
+  fn parse_ty_operator(&mut self) -> P<Ty> {
+    let lo = self.token.span.lo();
+    let kind = match self.token.ident() {
+      Some(ident) if ident.name == kw::KeyOf => Some(OpTyKind::Keyof),
+      Some(ident) if ident.name == kw::Unique => Some(OpTyKind::Uniq),
+      Some(ident) if ident.name == kw::Readonly => Some(OpTyKind::Readonly),
+      _ => None,
+    };
+    if let Some(kind) = kind {
+      self.bump();
+      let target = self.parse_ty_operator();
+      let span = self.span_from(lo);
+      return self.mk_ty(lo, TyKind::Op(P(OpTy { kind, target, span })));
+    }
+    if self.eat_keyword(kw::Infer) {
+      let name = self.parse_ident();
+      let param = GenericParam { name, constraint: None, default: None, span: name.span };
+      return self.mk_ty(lo, TyKind::Infer(P(param)));
+    }
+    // Conditional types are allowed again inside brackets, e.g. in `A extends (B extends
+    // C ? D : E) ? F : G`.
+    self.with_context(0, ContextFlags::DisallowConditionalTypes, |p| p.parse_postfix_ty())
+  }
+
+  /// Parses array types and indexed access types, `A[]` and `A["b"]`.
+  fn parse_postfix_ty(&mut self) -> P<Ty> {
+    let lo = self.token.span.lo();
+    let mut ty = self.parse_non_array_ty();
+    while !self.has_preceding_line_break() && self.eat(&token::OpenDelim(token::Bracket)) {
+      let kind = if self.token.can_begin_type() {
+        TyKind::Index(P(IndexTy(ty, self.parse_ty())))
+      } else {
+        TyKind::Arr(ty)
+      };
+      self.expect(&token::CloseDelim(token::Bracket));
+      ty = self.mk_ty(lo, kind);
+    }
+    ty
+  }
+
+  fn parse_non_array_ty(&mut self) -> P<Ty> {
+    let lo = self.token.span.lo();
+    let keyword_tys = [
+      kw::Any,
+      kw::Unknown,
+      kw::String,
+      kw::Number,
+      kw::BigInt,
+      kw::Boolean,
+      kw::SymbolLower,
+      kw::Object,
+      kw::Never,
+      kw::Undefined,
+      kw::Void,
+      kw::Null,
+    ];
+    let kind = match self.token.kind.clone() {
+      // `string.A` is a reference to a namespace member, not the keyword.
+      token::Ident(name)
+        if keyword_tys.contains(&name) && !self.look_ahead_next(|p| p.check(&token::Dot)) =>
+      {
+        self.bump();
+        TyKind::Keyword(name)
+      }
+      token::Ident(name) if name == kw::True || name == kw::False => {
+        TyKind::Lit(P(self.parse_lit()))
+      }
+      token::Ident(name) if name == kw::This => {
+        let this = Ident::new(name, self.token.span);
+        self.bump();
+        if self.check_keyword(kw::Is) && !self.has_preceding_line_break() {
+          return self.parse_predicate_ty(lo, false, this);
+        }
+        TyKind::This
+      }
+      token::Ident(name) if name == kw::TypeOf => {
+        self.bump();
+        TyKind::Query(self.parse_entity_name("Type expected."))
+      }
+      token::Ident(name)
+        if name == kw::Asserts
+          && self.look_ahead_next(|p| p.token.is_ident() && !p.has_preceding_line_break()) =>
+      {
+        self.bump();
+        // `asserts this` or `asserts a`, optionally followed by `is T`.
+        let param = if self.check_keyword(kw::This) {
+          let this = Ident::new(kw::This, self.token.span);
+          self.bump();
+          this
+        } else {
+          self.parse_ident()
+        };
+        return self.parse_predicate_ty(lo, true, param);
+      }
+      token::Literal(lit) if lit.kind == token::Template(token::NoSubstitution) => {
+        TyKind::Template(P(self.parse_template_ty()))
+      }
+      token::Literal(lit) if lit.kind == token::Template(token::Head) => {
+        TyKind::Template(P(self.parse_template_ty()))
+      }
+      token::Literal(lit) if lit.kind != token::RegExp => TyKind::Lit(P(self.parse_lit())),
+      token::BinOp(token::Minus)
+        if self.look_ahead_next(|p| {
+          matches!(p.token.kind, token::Literal(lit) if lit.kind == token::Num || lit.kind == token::BigInt)
+        }) =>
+      {
+        self.bump();
+        let lit = self.parse_lit();
+        let kind = match lit.kind {
+          LitKind::Num(value) => LitKind::Num(-value),
+          kind => kind,
+        };
+        let symbol = Symbol::intern(&format!("-{}", lit.token.symbol));
+        let token = token::Lit::new(lit.token.kind, symbol, lit.token.suffix);
+        TyKind::Lit(P(Lit { token, kind, span: self.span_from(lo) }))
+      }
+      token::OpenDelim(token::Brace) if self.look_ahead(|p| p.is_start_of_mapped_ty()) => {
+        TyKind::Map(P(self.parse_mapped_ty()))
+      }
+      token::OpenDelim(token::Brace) => {
+        self.bump();
+        let members = self.parse_ty_members();
+        self.expect(&token::CloseDelim(token::Brace));
+        TyKind::TyLit(members)
+      }
+      token::OpenDelim(token::Bracket) => TyKind::Tuple(self.parse_tuple_els()),
+      token::OpenDelim(token::Paren) => {
+        self.bump();
+        let ty = self.parse_ty();
+        self.expect(&token::CloseDelim(token::Paren));
+        TyKind::Paren(ty)
+      }
+      _ => {
+        let name = self.parse_entity_name("Type expected.");
+        let args = if self.check(&token::Lt) && !self.has_preceding_line_break() {
+          self.parse_ty_args()
+        } else {
+          Vec::new()
+        };
+        TyKind::Ref(P(RefTy { kind: RefTyKind::Ident(name, args), span: self.span_from(lo) }))
+      }
+    };
+    self.mk_ty(lo, kind)
+  }
+
+  /// Parses the type arguments of a type reference, `<A, B>`.
+  pub(super) fn parse_ty_args(&mut self) -> Vec<P<Ty>> {
+    self.expect(&token::Lt);
+    if self.check(&token::Gt) {
+      self.error_at_token("Type argument list cannot be empty.");
+    }
+    let args = self.parse_delimited_list(ParsingContext::TypeArguments, false, |p| p.parse_ty());
+    self.expect_gt();
+    args
+  }
+
+  /// Parses the elements of a tuple type, `[A, B?, ...C]` or `[a: A, b?: B, ...c: C]`.
+  fn parse_tuple_els(&mut self) -> Vec<P<Ty>> {
+    self.parse_bracketed_list(
+      ParsingContext::TupleElementTypes,
+      &token::OpenDelim(token::Bracket),
+      &token::CloseDelim(token::Bracket),
+      |p| p.parse_tuple_el(),
+    )
+  }
+
+  fn parse_tuple_el(&mut self) -> P<Ty> {
+    let lo = self.token.span.lo();
+    if !self.look_ahead(|p| p.is_tuple_el_name()) {
+      return self.parse_tuple_el_ty();
+    }
+    let rest = self.eat(&token::DotDotDot);
+    let name = PropName::Ident(self.parse_ident_name());
+    let optional = self.eat(&token::Question);
+    self.expect(&token::Colon);
+    let ty = self.parse_tuple_el_ty();
+    let ty = if rest { self.mk_ty(lo, TyKind::Rest(ty)) } else { ty };
+    let span = self.span_from(lo);
+    ty.map(|ty| Ty { name: Some(name), optional, span, ..ty })
+  }
+
+  /// Whether `a:`, `a?:` or `...a:` names a tuple element.
+  fn is_tuple_el_name(&mut self) -> bool {
+    self.eat(&token::DotDotDot);
+    if !self.token.is_ident() {
+      return false;
+    }
+    self.bump();
+    self.eat(&token::Question);
+    self.check(&token::Colon)
+  }
+
+  fn parse_tuple_el_ty(&mut self) -> P<Ty> {
+    let lo = self.token.span.lo();
+    if self.eat(&token::DotDotDot) {
+      let ty = self.parse_ty();
+      return self.mk_ty(lo, TyKind::Rest(ty));
+    }
+    let ty = self.parse_ty();
+    if self.eat(&token::Question) {
+      return self.mk_ty(lo, TyKind::Optional(ty));
+    }
+    ty
+  }
+
+  /// Whether `{ [P in`, `{ readonly [P in` or `{ +readonly` starts a mapped type.
+  fn is_start_of_mapped_ty(&mut self) -> bool {
+    self.bump();
+    if self.eat(&token::BinOp(token::Plus)) || self.eat(&token::BinOp(token::Minus)) {
+      return self.check_keyword(kw::Readonly);
+    }
+    self.eat_keyword(kw::Readonly);
+    if !self.eat(&token::OpenDelim(token::Bracket)) || !self.is_binding_ident() {
+      return false;
+    }
+    self.bump();
+    self.check_keyword(kw::In)
+  }
+
+  /// Parses `{ readonly [P in K as N]?: T }`.
+  fn parse_mapped_ty(&mut self) -> MapTy {
+    let lo = self.token.span.lo();
+    self.expect(&token::OpenDelim(token::Brace));
+    let readonly_flag = match self.parse_mod_sign() {
+      Some(flag) => {
+        self.expect_keyword(kw::Readonly);
+        Some(flag)
+      }
+      None if self.eat_keyword(kw::Readonly) => Some(ModFlag::Raw),
+      None => None,
+    };
+    self.expect(&token::OpenDelim(token::Bracket));
+    let param_lo = self.token.span.lo();
+    let name = self.parse_ident();
+    self.expect_keyword(kw::In);
+    let constraint = Some(self.parse_ty());
+    let type_param =
+      GenericParam { name, constraint, default: None, span: self.span_from(param_lo) };
+    let name_type = if self.eat_keyword(kw::As) { Some(self.parse_ty()) } else { None };
+    self.expect(&token::CloseDelim(token::Bracket));
+    let optional_flag = match self.parse_mod_sign() {
+      Some(flag) => {
+        self.expect(&token::Question);
+        Some(flag)
+      }
+      None if self.eat(&token::Question) => Some(ModFlag::Raw),
+      None => None,
+    };
+    let ty = if self.eat(&token::Colon) { Some(self.parse_ty()) } else { None };
+    self.expect_semicolon();
+    self.expect(&token::CloseDelim(token::Brace));
+    MapTy { readonly_flag, optional_flag, type_param, name_type, ty, span: self.span_from(lo) }
+  }
+
+  /// Consumes the `+` or `-` of a modifier in a mapped type, e.g. `-readonly` or `+?`.
+  fn parse_mod_sign(&mut self) -> Option<ModFlag> {
+    if self.eat(&token::BinOp(token::Plus)) {
+      Some(ModFlag::Plus)
+    } else if self.eat(&token::BinOp(token::Minus)) {
+      Some(ModFlag::Minus)
+    } else {
+      None
+    }
+  }
+
+  /// Parses the members of an object type literal or an interface, up to the `}`.
+  pub(super) fn parse_ty_members(&mut self) -> Vec<InterfaceElement> {
+    self.parse_list(ParsingContext::TypeMembers, |p| p.parse_ty_member())
+  }
+
+  fn parse_ty_member(&mut self) -> InterfaceElement {
+    let lo = self.token.span.lo();
+    if self.check(&token::OpenDelim(token::Paren)) || self.check(&token::Lt) {
+      let sig = self.parse_fn_sig(ContextFlags::None);
+      self.parse_ty_member_semicolon();
+      return InterfaceElement::CallSig(P(sig));
+    }
+    if self.check_keyword(kw::New)
+      && self.look_ahead_next(|p| p.check(&token::OpenDelim(token::Paren)) || p.check(&token::Lt))
+    {
+      self.bump();
+      let sig = self.parse_fn_sig(ContextFlags::None);
+      let sig = FnSig { span: self.span_from(lo), ..sig };
+      self.parse_ty_member_semicolon();
+      return InterfaceElement::ConstructorSig(P(sig));
+    }
+    let readonly_span = self.token.span;
+    let readonly = self.check_keyword(kw::Readonly)
+      && self.look_ahead_next(|p| !p.has_preceding_line_break() && p.can_follow_modifier());
+    if readonly {
+      self.bump();
+    }
+    if self.check(&token::OpenDelim(token::Bracket))
+      && self.look_ahead(|p| p.is_unambiguously_index_sig())
+    {
+      return InterfaceElement::IndexSig(P(self.parse_index_sig(lo, readonly)));
+    }
+    let name = self.parse_prop_name();
+    let optional = self.eat(&token::Question);
+    if self.check(&token::OpenDelim(token::Paren)) || self.check(&token::Lt) {
+      if readonly {
+        let msg =
+          "'readonly' modifier can only appear on a property declaration or index signature.";
+        self.error(readonly_span, msg);
+      }
+      let sig = self.parse_fn_sig(ContextFlags::None);
+      self.parse_ty_member_semicolon();
+      let span = self.span_from(lo);
+      return InterfaceElement::MethodSig(P(MethodSig { name, optional, sig, span }));
+    }
+    let ty = if self.eat(&token::Colon) { Some(self.parse_ty()) } else { None };
+    self.parse_ty_member_semicolon();
+    let span = self.span_from(lo);
+    InterfaceElement::PropSig(P(PropSig { name, optional, readonly, ty, span }))
+  }
+
+  /// Members are separated by semicolons or commas.
+  fn parse_ty_member_semicolon(&mut self) {
+    if !self.eat(&token::Comma) {
+      self.expect_semicolon();
+    }
+  }
+
+  /// Whether the token after a modifier like `readonly` makes it a modifier rather
+  /// than a name, as in `readonly a` versus `readonly: boolean`.
+  pub(super) fn can_follow_modifier(&self) -> bool {
+    self.check(&token::OpenDelim(token::Bracket))
+      || self.check(&token::OpenDelim(token::Brace))
+      || self.check(&token::BinOp(token::Star))
+      || self.check(&token::DotDotDot)
+      || self.token.is_ident()
+      || self.token.is_lit()
+      || matches!(self.token.kind, token::PrivateIdent(_))
+  }
+
+  /// Whether `[` starts an index signature, `[a: string]`, rather than a computed
+  /// property name, `[a]`.
+  pub(super) fn is_unambiguously_index_sig(&mut self) -> bool {
+    self.bump();
+    if self.check(&token::DotDotDot) || self.check(&token::CloseDelim(token::Bracket)) {
+      return true;
+    }
+    if !self.is_binding_ident() {
+      return false;
+    }
+    self.bump();
+    if self.check(&token::Colon) || self.check(&token::Comma) {
+      return true;
+    }
+    if !self.eat(&token::Question) {
+      return false;
+    }
+    self.check(&token::Colon)
+      || self.check(&token::Comma)
+      || self.check(&token::CloseDelim(token::Bracket))
+  }
+
+  pub(super) fn parse_index_sig(&mut self, lo: BytePos, readonly: bool) -> IndexSig {
+    self.expect(&token::OpenDelim(token::Bracket));
+    let params = self.parse_index_sig_params();
+    self.check_param_props(&params);
+    self.expect(&token::CloseDelim(token::Bracket));
+    let ty = if self.eat(&token::Colon) { Some(self.parse_ty()) } else { None };
+    self.parse_ty_member_semicolon();
+    IndexSig { readonly, params, ty, span: self.span_from(lo) }
+  }
+
+  fn parse_index_sig_params(&mut self) -> Vec<FnParam> {
+    let unset = ContextFlags::DisallowIn | ContextFlags::Yield | ContextFlags::Await;
+    self.with_context(0, unset, |p| {
+      p.parse_delimited_list(ParsingContext::Parameters, false, |p| p.parse_fn_param())
+    })
+  }
+
+  /// Parses a template literal type, `\`${A}-${B}\``.
+  fn parse_template_ty(&mut self) -> TemplateTy {
+    let lo = self.token.span.lo();
+    if self.scanner.token_flags() & TokenFlags::ContainsInvalidEscape != 0 {
+      self.token = self.scanner.rescan_template_head_or_no_substitution_template();
+    }
+    let (head, mut kind) = self.parse_template_part();
+    let mut spans = Vec::new();
+    while kind == token::Head || kind == token::Middle {
+      let span_lo = self.token.span.lo();
+      let ty = self.parse_ty();
+      let lit = if self.check(&token::CloseDelim(token::Brace)) {
+        self.token = self.scanner.rescan_template_token(false);
+        let (lit, next) = self.parse_template_part();
+        kind = next;
+        Some(lit)
+      } else {
+        self.error_at_token("'}' expected.");
+        kind = token::Tail;
+        None
+      };
+      spans.push(P(TemplateTySpan { span: self.span_from(span_lo), ty, lit }));
+    }
+    TemplateTy { span: self.span_from(lo), head: Some(head), spans }
+  }
+
+  /// Parses the type parameters of a declaration, `<T, U extends T = T>`, if present.
+  pub fn parse_generics(&mut self) -> Generics {
+    if !self.check(&token::Lt) {
+      return self.empty_generics();
+    }
+    let lo = self.token.span.lo();
+    self.bump();
+    // A trailing comma is allowed, as in `<T,>() => {}` in a JSX file.
+    let params =
+      self.parse_delimited_list(ParsingContext::TypeParameters, false, |p| p.parse_generic_param());
+    self.expect_gt();
+    Generics { span: self.span_from(lo), params }
+  }
+
+  fn parse_generic_param(&mut self) -> GenericParam {
+    let lo = self.token.span.lo();
+    let name = self.parse_ident();
+    let constraint = if self.eat_keyword(kw::Extends) { Some(self.parse_ty()) } else { None };
+    let default = if self.eat(&token::Eq) { Some(self.parse_ty()) } else { None };
+    GenericParam { name, constraint, default, span: self.span_from(lo) }
+  }
+
+  /// The type parameters of a declaration without any, an empty span where they would be.
+  pub(super) fn empty_generics(&self) -> Generics {
+    Generics { span: self.token.span.shrink_to_lo(), params: Vec::new() }
+  }
+
+  /// Parses a possibly qualified name, `A.B.C`, reporting `msg` if there is no name at all.
+  /// Reserved words are allowed as in `as const`, the checker reports them where they make
+  /// no sense.
+  pub(super) fn parse_entity_name(&mut self, msg: &str) -> P<EntityName> {
+    let mut name = match self.token.ident() {
+      Some(ident) => {
+        self.bump();
+        EntityName::Ident(ident)
+      }
+      None => EntityName::Ident(self.parse_ident_or(msg)),
+    };
+    while self.eat(&token::Dot) {
+      name = EntityName::Qualified(P(name), self.parse_ident_name());
+    }
+    P(name)
+  }
+
+  fn mk_ty(&self, lo: BytePos, kind: TyKind) -> P<Ty> {
+    P(Ty { kind, name: None, optional: false, span: self.span_from(lo) })
+  }
+}
+
+/// Whether the return type of what looks like an arrow function is too broken for it to
+/// be one, so that `a ? (b) : c => d` is parsed as a conditional instead.
+pub(super) fn ty_has_arrow_fn_blocking_parse_error(ty: &Ty) -> bool {
+  match &ty.kind {
+    TyKind::Ref(ty_ref) => match &ty_ref.kind {
+      RefTyKind::Ident(name, _) => {
+        matches!(&**name, EntityName::Ident(ident) if ident.name == kw::Empty)
+      }
+      RefTyKind::Expr(_) => false,
+    },
+    TyKind::Fn(sig) | TyKind::Constructor(sig) => match &sig.ret {
+      FnRet::Ty(ret) => ty_has_arrow_fn_blocking_parse_error(ret),
+      FnRet::Void => false,
+    },
+    TyKind::Paren(ty) => ty_has_arrow_fn_blocking_parse_error(ty),
+    _ => false,
+  }
+}
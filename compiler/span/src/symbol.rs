@@ -20,16 +20,19 @@ mod tests;
 
 // The proc macro code for this is in `src/librustc_macros/src/symbols.rs`.
 symbols! {
-    // After modifying this list adjust `is_special`, `is_used_keyword`/`is_unused_keyword`,
-    // this should be rarely necessary though if the keywords are kept in alphabetic order.
+    // After modifying this list adjust `Symbol::keyword_kind`, which classifies
+    // each keyword as reserved, strict-mode reserved, contextual or type-only.
     Keywords {
       Empty: "",
       Abstract: "abstract",
       Any: "any",
       As: "as",
+      Assert: "assert",
       Asserts: "asserts",
       Async: "async",
       Await: "await",
+      BigInt: "bigint",
+      Boolean: "boolean",
       Break: "break",
       Case: "case",
       Catch: "catch",
@@ -58,18 +61,21 @@ symbols! {
       Import: "import",
       Infer: "infer",
       In: "in",
-      InstanceOf: "instanceOf",
+      InstanceOf: "instanceof",
       Interface: "interface",
       Intrinsic: "intrinsic",
       Is: "is",
-      KeyOf: "keyOf",
+      KeyOf: "keyof",
       Let: "let",
       Module: "module",
       Namespace: "namespace",
       Never: "never",
       New: "new",
       Null: "null",
+      Number: "number",
+      Object: "object",
       Of: "of",
+      Override: "override",
       Package: "package",
       Private: "private",
       Protected: "protected",
@@ -77,15 +83,19 @@ symbols! {
       Readonly: "readonly",
       Require: "require",
       Return: "return",
+      Set: "set",
       Static: "static",
+      String: "string",
       Super: "super",
       Switch: "switch",
+      // `Symbol` would clash with the `Symbol` type itself.
+      SymbolLower: "symbol",
       This: "this",
       Throw: "throw",
       True: "true",
       Try: "try",
       Type: "type",
-      TypeOf: "typeOf",
+      TypeOf: "typeof",
       Undefined: "undefined",
       Unique: "unique",
       Unknown: "unknown",
@@ -121,6 +131,8 @@ symbols! {
       Object,
       String,
       // Symbol,
+      meta,
+      target,
     }
 }
 
@@ -417,9 +429,135 @@ pub mod sym {
   }
 }
 
+/// How a keyword is treated by the scanner and parser.
+///
+/// Keywords are scanned as plain identifiers; whether one acts as a keyword
+/// depends on its kind and, for the non-reserved kinds, on where it appears.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum KeywordKind {
+  /// Always reserved, e.g. `class` or `typeof`.
+  Reserved,
+  /// Reserved only in strict mode code, e.g. `let` or `yield`.
+  StrictReserved,
+  /// A keyword only in certain positions, e.g. `async` or `readonly`.
+  Contextual,
+  /// A predefined type name, only a keyword in a type, e.g. `string` or `never`.
+  Type,
+}
+
 impl Symbol {
+  /// Classifies this symbol if it is a keyword.
+  pub fn keyword_kind(self) -> Option<KeywordKind> {
+    use KeywordKind::*;
+
+    Some(match self {
+      kw::Break
+      | kw::Case
+      | kw::Catch
+      | kw::Class
+      | kw::Const
+      | kw::Continue
+      | kw::Debugger
+      | kw::Default
+      | kw::Delete
+      | kw::Do
+      | kw::Else
+      | kw::Enum
+      | kw::Export
+      | kw::Extends
+      | kw::False
+      | kw::Finally
+      | kw::For
+      | kw::Function
+      | kw::If
+      | kw::Import
+      | kw::In
+      | kw::InstanceOf
+      | kw::New
+      | kw::Null
+      | kw::Return
+      | kw::Super
+      | kw::Switch
+      | kw::This
+      | kw::Throw
+      | kw::True
+      | kw::Try
+      | kw::TypeOf
+      | kw::Var
+      | kw::Void
+      | kw::While
+      | kw::With => Reserved,
+      kw::Implements
+      | kw::Interface
+      | kw::Let
+      | kw::Package
+      | kw::Private
+      | kw::Protected
+      | kw::Public
+      | kw::Static
+      | kw::Yield => StrictReserved,
+      kw::Abstract
+      | kw::As
+      | kw::Assert
+      | kw::Asserts
+      | kw::Async
+      | kw::Await
+      | kw::Constructor
+      | kw::Declare
+      | kw::From
+      | kw::Get
+      | kw::Global
+      | kw::Infer
+      | kw::Intrinsic
+      | kw::Is
+      | kw::KeyOf
+      | kw::Module
+      | kw::Namespace
+      | kw::Of
+      | kw::Override
+      | kw::Readonly
+      | kw::Require
+      | kw::Set
+      | kw::Type
+      | kw::Unique => Contextual,
+      kw::Any
+      | kw::BigInt
+      | kw::Boolean
+      | kw::Never
+      | kw::Number
+      | kw::Object
+      | kw::String
+      | kw::SymbolLower
+      | kw::Undefined
+      | kw::Unknown => Type,
+      _ => return None,
+    })
+  }
+
+  /// Returns `true` if the symbol is a keyword of any kind.
+  pub fn is_keyword(self) -> bool {
+    self.keyword_kind().is_some()
+  }
+
+  /// Returns `true` if the symbol can never be used as an identifier.
   pub fn is_reserved(self) -> bool {
-    todo!()
+    self.keyword_kind() == Some(KeywordKind::Reserved)
+  }
+
+  /// Returns `true` if the symbol can't be used as an identifier in strict mode code.
+  pub fn is_strict_mode_reserved(self) -> bool {
+    matches!(self.keyword_kind(), Some(KeywordKind::Reserved) | Some(KeywordKind::StrictReserved))
+  }
+
+  /// Returns `true` if the symbol is a keyword only in certain positions,
+  /// and an ordinary identifier everywhere else.
+  pub fn is_contextual_keyword(self) -> bool {
+    self.keyword_kind() == Some(KeywordKind::Contextual)
+  }
+
+  /// Returns `true` if the symbol names a predefined type, like `string`.
+  pub fn is_type_keyword(self) -> bool {
+    self.keyword_kind() == Some(KeywordKind::Type)
   }
 
   /// Returns `true` if the symbol is `true` or `false`.
@@ -434,12 +572,17 @@ impl Symbol {
 }
 
 impl Ident {
-  /// Returns `true` if the token is either a special identifier or a keyword.
+  /// Returns `true` if the identifier is a reserved word. Contextual keywords
+  /// like `async` are not reserved; use `Symbol::keyword_kind` to tell them apart.
   pub fn is_reserved(self) -> bool {
-    // Note: `span.edition()` is relatively expensive, don't call it unless necessary.
     self.name.is_reserved()
   }
 
+  /// Returns `true` if the identifier is reserved in strict mode code.
+  pub fn is_strict_mode_reserved(self) -> bool {
+    self.name.is_strict_mode_reserved()
+  }
+
   /// We see this identifier in a normal identifier position, like variable name or a type.
   /// How was it written originally? Did it use the raw form? Let's try to guess.
   pub fn is_raw_guess(self) -> bool {
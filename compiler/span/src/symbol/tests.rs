@@ -23,3 +23,24 @@ fn without_first_quote_test() {
     assert_eq!(i.without_first_quote().name, kw::Break);
   });
 }
+
+#[test]
+fn keyword_kind_test() {
+  SESSION_GLOBALS.set(&SessionGlobals::new(), || {
+    assert_eq!(Symbol::intern("class").keyword_kind(), Some(KeywordKind::Reserved));
+    assert_eq!(Symbol::intern("instanceof").keyword_kind(), Some(KeywordKind::Reserved));
+    assert_eq!(Symbol::intern("typeof").keyword_kind(), Some(KeywordKind::Reserved));
+    assert_eq!(Symbol::intern("yield").keyword_kind(), Some(KeywordKind::StrictReserved));
+    assert_eq!(Symbol::intern("async").keyword_kind(), Some(KeywordKind::Contextual));
+    assert_eq!(Symbol::intern("keyof").keyword_kind(), Some(KeywordKind::Contextual));
+    assert_eq!(Symbol::intern("symbol").keyword_kind(), Some(KeywordKind::Type));
+    assert_eq!(Symbol::intern("instanceOf").keyword_kind(), None);
+    assert_eq!(Symbol::intern("foo").keyword_kind(), None);
+    assert_eq!(kw::Empty.keyword_kind(), None);
+
+    assert!(kw::Let.is_strict_mode_reserved());
+    assert!(!kw::Let.is_reserved());
+    assert!(kw::Readonly.is_contextual_keyword());
+    assert!(kw::Never.is_type_keyword());
+  });
+}